@@ -18,6 +18,13 @@ pub trait Application: Component {
         std::mem::forget(rc_comp);
     }
 
+    /// Mount the app to the given element, the app will be unmounted when the returned
+    /// handle is dropped (or [`AppHandle::unmount`] is called). This is useful for apps
+    /// that only live in a part of the page, or in tests.
+    fn mount(root: web_sys::Element) -> AppHandle<Self> {
+        AppHandle(mount_to_element::<Self>(root))
+    }
+
     fn mount_to_element_id(id: &str) {
         let root = crate::utils::document()
             .get_element_by_id(id)
@@ -34,6 +41,30 @@ pub trait Application: Component {
     }
 }
 
+/// A handle of a mounted app, returned by [`Application::mount`]. On dropping, the rendered
+/// nodes are removed from the root element, the state (including all child components) and
+/// window listeners of the app are dropped, and the router of the app is removed.
+pub struct AppHandle<A: Application>(RcComp<A>);
+
+impl<A: Application> AppHandle<A> {
+    pub fn comp(&self) -> Comp<A> {
+        self.0.comp()
+    }
+
+    pub fn unmount(self) {
+        drop(self);
+    }
+}
+
+impl<A: Application> Drop for AppHandle<A> {
+    fn drop(&mut self) {
+        // Child components remove their routing callbacks in dropping, so the router
+        // must be removed after unmounting the component.
+        self.0.unmount();
+        routing::remove_router::<<<A as Component>::Routes as Routes>::Router>();
+    }
+}
+
 pub fn mount_to_element<A: Application>(root: web_sys::Element) -> RcComp<A> {
     root.set_text_content(None);
     let rc_comp = RcComp::with_ws_root(root);
@@ -45,9 +76,9 @@ pub fn mount_to_element<A: Application>(root: web_sys::Element) -> RcComp<A> {
         None if std::any::TypeId::of::<()>()
             != std::any::TypeId::of::<<<A as Component>::Routes as Routes>::Router>() =>
         {
-            log::warn!(
+            crate::diagnostics::warn(format_args!(
                 "You may want to implement `Application::init_router()` to return Some(router)"
-            );
+            ));
         }
         _ => {}
     }
@@ -63,3 +94,69 @@ pub fn mount_to_element<A: Application>(root: web_sys::Element) -> RcComp<A> {
     rc_comp.first_render();
     rc_comp
 }
+
+#[cfg(test)]
+mod application_tests {
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::render::html::{HtmlTag, MethodsForHtmlElementContent};
+
+    struct App {
+        changes: Rc<Cell<u32>>,
+    }
+
+    impl Component for App {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.rupdate("mounted");
+        }
+    }
+
+    impl super::Application for App {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                changes: Rc::new(Cell::new(0)),
+            }
+        }
+    }
+
+    fn dispatch_change() {
+        let event = web_sys::Event::new("change")
+            .expect_throw("application_tests::dispatch_change new event");
+        crate::utils::window()
+            .dispatch_event(&event)
+            .expect_throw("application_tests::dispatch_change dispatch");
+    }
+
+    #[wasm_bindgen_test]
+    fn mount_then_unmount() {
+        use super::Application;
+        let root = crate::dom::Element::new_ns(HtmlTag("div"));
+        let app = App::mount(root.ws_element().clone().into_inner());
+        assert_eq!(
+            Some("mounted".to_string()),
+            root.ws_element().as_ref().text_content()
+        );
+
+        let comp = app.comp();
+        comp.window_event_named(
+            "change",
+            crate::events::Change::on_window(
+                comp.handler_mut(|state: &mut App| state.changes.set(state.changes.get() + 1)),
+            ),
+        );
+        let changes = app.0.comp_instance().state().changes.clone();
+        dispatch_change();
+        assert_eq!(1, changes.get());
+
+        app.unmount();
+        assert_eq!(0, root.ws_element().as_ref().child_nodes().length());
+        // The state is dropped
+        assert_eq!(1, Rc::strong_count(&changes));
+        dispatch_change();
+        assert_eq!(1, changes.get());
+    }
+}
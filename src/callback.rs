@@ -22,10 +22,10 @@ impl<C, Cl, A, F> FnArg<C, A> for CbArg<F>
 where
     C: Component,
     Cl: 'static + Into<Checklist<C>>,
-    F: Fn(&C) -> Cl,
+    F: Fn(&C, A) -> Cl,
 {
-    fn execute(&self, state: &mut C, _a: A) -> Checklist<C> {
-        (self.0)(state).into()
+    fn execute(&self, state: &mut C, a: A) -> Checklist<C> {
+        (self.0)(state, a).into()
     }
 }
 
@@ -284,6 +284,58 @@ pub trait CallbackArg<A> {
     /// will be add to update queue. If this fails (maybe because of a bug) to work,
     /// you can try `.call` or `.queue`.
     fn call_or_queue(&self, a: A);
+
+    /// Create a callback that converts its argument with `f` before passing it to
+    /// this callback. For example, map an `InputEvent` to the value of the input.
+    fn map_arg<B: 'static>(self, f: impl Fn(B) -> A + 'static) -> crate::CallbackArg<B>
+    where
+        Self: Sized + 'static,
+        A: 'static,
+    {
+        Box::new(CallbackMapArg {
+            callback: self,
+            map: f,
+            phantom: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<A> CallbackArg<A> for Box<dyn CallbackArg<A>> {
+    fn call(&self, a: A) {
+        (**self).call(a);
+    }
+
+    fn queue(&self, a: A) {
+        (**self).queue(a);
+    }
+
+    fn call_or_queue(&self, a: A) {
+        (**self).call_or_queue(a);
+    }
+}
+
+pub struct CallbackMapArg<Cb, F, A> {
+    callback: Cb,
+    map: F,
+    phantom: std::marker::PhantomData<fn(A)>,
+}
+
+impl<A, B, Cb, F> CallbackArg<B> for CallbackMapArg<Cb, F, A>
+where
+    Cb: CallbackArg<A>,
+    F: Fn(B) -> A,
+{
+    fn call(&self, b: B) {
+        self.callback.call((self.map)(b));
+    }
+
+    fn queue(&self, b: B) {
+        self.callback.queue((self.map)(b));
+    }
+
+    fn call_or_queue(&self, b: B) {
+        self.callback.call_or_queue((self.map)(b));
+    }
 }
 
 impl<C, A> CallbackArg<A> for CallbackFn<C, A>
@@ -396,3 +448,175 @@ where
         }
     }
 }
+
+/// The object-safe version of [`CallbackOnce`], used by [`crate::CallbackOnce`] to
+/// store a one-shot callback in a `Box`.
+pub trait BoxedCallbackOnce {
+    fn call_boxed(self: Box<Self>);
+    fn queue_boxed(self: Box<Self>);
+    fn call_or_queue_boxed(self: Box<Self>);
+}
+
+impl<T: CallbackOnce> BoxedCallbackOnce for T {
+    fn call_boxed(self: Box<Self>) {
+        (*self).call();
+    }
+
+    fn queue_boxed(self: Box<Self>) {
+        (*self).queue();
+    }
+
+    fn call_or_queue_boxed(self: Box<Self>) {
+        (*self).call_or_queue();
+    }
+}
+
+impl CallbackOnce for Box<dyn BoxedCallbackOnce> {
+    fn call(self) {
+        self.call_boxed();
+    }
+
+    fn queue(self) {
+        self.queue_boxed();
+    }
+
+    fn call_or_queue(self) {
+        self.call_or_queue_boxed();
+    }
+}
+
+/// The object-safe version of [`CallbackOnceArg`], used by [`crate::CallbackOnceArg`]
+/// to store a one-shot callback in a `Box`.
+pub trait BoxedCallbackOnceArg<A> {
+    fn call_boxed(self: Box<Self>, a: A);
+    fn queue_boxed(self: Box<Self>, a: A);
+    fn call_or_queue_boxed(self: Box<Self>, a: A);
+}
+
+impl<A, T: CallbackOnceArg<A>> BoxedCallbackOnceArg<A> for T {
+    fn call_boxed(self: Box<Self>, a: A) {
+        (*self).call(a);
+    }
+
+    fn queue_boxed(self: Box<Self>, a: A) {
+        (*self).queue(a);
+    }
+
+    fn call_or_queue_boxed(self: Box<Self>, a: A) {
+        (*self).call_or_queue(a);
+    }
+}
+
+impl<A> CallbackOnceArg<A> for Box<dyn BoxedCallbackOnceArg<A>> {
+    fn call(self, a: A) {
+        self.call_boxed(a);
+    }
+
+    fn queue(self, a: A) {
+        self.queue_boxed(a);
+    }
+
+    fn call_or_queue(self, a: A) {
+        self.call_or_queue_boxed(a);
+    }
+}
+
+/// A callback that delays invoking the wrapped callback until it has not been
+/// called for `duration`. Only the argument of the last call is delivered.
+pub struct CallbackDebounced<A> {
+    pub duration: std::time::Duration,
+    pub callback: Rc<dyn CallbackArg<A>>,
+    pub pending: Rc<std::cell::RefCell<Option<crate::timer::Timeout>>>,
+}
+
+impl<A: 'static> CallbackDebounced<A> {
+    fn schedule(&self, a: A) {
+        let callback = self.callback.clone();
+        let timeout = crate::timer::Timeout::new(self.duration, move || callback.call_or_queue(a));
+        // Replacing the pending timeout drops (and clears) the previous one.
+        *self.pending.borrow_mut() = Some(timeout);
+    }
+}
+
+impl<A: 'static> CallbackArg<A> for CallbackDebounced<A> {
+    fn call(&self, a: A) {
+        self.schedule(a);
+    }
+
+    fn queue(&self, a: A) {
+        self.schedule(a);
+    }
+
+    fn call_or_queue(&self, a: A) {
+        self.schedule(a);
+    }
+}
+
+pub struct ThrottleState<A> {
+    timeout: Option<crate::timer::Timeout>,
+    trailing: Option<A>,
+}
+
+impl<A> Default for ThrottleState<A> {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            trailing: None,
+        }
+    }
+}
+
+/// A callback that invokes the wrapped callback at most once per `duration`.
+/// Calls in between are dropped, except the last one, which is delivered when
+/// the interval ends.
+pub struct CallbackThrottled<A> {
+    pub duration: std::time::Duration,
+    pub callback: Rc<dyn CallbackArg<A>>,
+    pub state: Rc<std::cell::RefCell<ThrottleState<A>>>,
+}
+
+impl<A: 'static> CallbackThrottled<A> {
+    fn throttle(&self, a: A) {
+        let mut state = self.state.borrow_mut();
+        if state.timeout.is_some() {
+            state.trailing = Some(a);
+            return;
+        }
+        drop(state);
+        self.callback.call_or_queue(a);
+        start_throttle_timer(self.duration, self.callback.clone(), self.state.clone());
+    }
+}
+
+fn start_throttle_timer<A: 'static>(
+    duration: std::time::Duration,
+    callback: Rc<dyn CallbackArg<A>>,
+    state: Rc<std::cell::RefCell<ThrottleState<A>>>,
+) {
+    let cloned_state = state.clone();
+    let timeout = crate::timer::Timeout::new(duration, move || {
+        let trailing = cloned_state.borrow_mut().trailing.take();
+        match trailing {
+            Some(a) => {
+                callback.call_or_queue(a);
+                start_throttle_timer(duration, callback, cloned_state);
+            }
+            None => cloned_state.borrow_mut().timeout = None,
+        }
+    });
+    state.borrow_mut().timeout = Some(timeout);
+}
+
+impl<A: 'static> CallbackArg<A> for CallbackThrottled<A> {
+    fn call(&self, a: A) {
+        self.throttle(a);
+    }
+
+    fn queue(&self, a: A) {
+        self.throttle(a);
+    }
+
+    fn call_or_queue(&self, a: A) {
+        self.throttle(a);
+    }
+}
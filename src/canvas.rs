@@ -0,0 +1,129 @@
+use wasm_bindgen::{JsCast, JsValue};
+
+/// A thin wrapper of the `CanvasRenderingContext2d` of a `<canvas>` element. Use
+/// [`Canvas2d::context`] for anything that is not covered by the wrapper.
+pub struct Canvas2d {
+    canvas: web_sys::HtmlCanvasElement,
+    context: web_sys::CanvasRenderingContext2d,
+}
+
+impl Canvas2d {
+    /// Returns `None` if the canvas does not support the 2d context (or a context of
+    /// another type has already been requested from it).
+    pub fn new(canvas: web_sys::HtmlCanvasElement) -> Option<Self> {
+        let context = canvas
+            .get_context("2d")
+            .ok()
+            .flatten()?
+            .unchecked_into::<web_sys::CanvasRenderingContext2d>();
+        Some(Self { canvas, context })
+    }
+
+    /// Returns `None` if the `WsRef` is not set yet or the 2d context is not available.
+    pub fn from_ws_ref(canvas: &crate::WsRef<web_sys::HtmlCanvasElement>) -> Option<Self> {
+        canvas.get().as_ref().cloned().and_then(Self::new)
+    }
+
+    pub fn canvas(&self) -> &web_sys::HtmlCanvasElement {
+        &self.canvas
+    }
+
+    pub fn context(&self) -> &web_sys::CanvasRenderingContext2d {
+        &self.context
+    }
+
+    pub fn width(&self) -> u32 {
+        self.canvas.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.canvas.height()
+    }
+
+    /// Clear the whole canvas.
+    pub fn clear(&self) {
+        self.context
+            .clear_rect(0.0, 0.0, self.width() as f64, self.height() as f64);
+    }
+
+    /// Fill a rectangle with the given `style`, which is a CSS color, such as `"red"`
+    /// or `"#ff0000"`.
+    pub fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64, style: &str) {
+        self.context.set_fill_style_str(style);
+        self.context.fill_rect(x, y, width, height);
+    }
+
+    /// Draw the text with the given CSS `font` (such as `"16px sans-serif"`) and fill `style`.
+    pub fn draw_text(
+        &self,
+        text: &str,
+        x: f64,
+        y: f64,
+        font: &str,
+        style: &str,
+    ) -> Result<(), JsValue> {
+        self.context.set_font(font);
+        self.context.set_fill_style_str(style);
+        self.context.fill_text(text, x, y)
+    }
+}
+
+#[cfg(test)]
+mod canvas_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::render::html::{HamsForDistinctNames, HemsForDistinctNames};
+
+    struct State {
+        canvas: crate::WsRef<web_sys::HtmlCanvasElement>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.canvas(|c| {
+                self.canvas.set(&c);
+                c.width(40).height(20);
+            });
+        }
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                canvas: crate::WsRef::none(),
+            }
+        }
+    }
+
+    fn pixel(canvas: &super::Canvas2d, x: f64, y: f64) -> Vec<u8> {
+        canvas
+            .context()
+            .get_image_data(x, y, 1.0, 1.0)
+            .expect_throw("canvas_tests::pixel get_image_data")
+            .data()
+            .to_vec()
+    }
+
+    #[wasm_bindgen_test]
+    fn draw_on_canvas() {
+        let (_root, rc) = crate::test_utils::mount::<State>();
+        let canvas = super::Canvas2d::from_ws_ref(&rc.comp_instance().state().canvas)
+            .expect_throw("canvas_tests::draw_on_canvas 2d context");
+        assert_eq!(40, canvas.width());
+        assert_eq!(20, canvas.height());
+
+        canvas.fill_rect(0.0, 0.0, 10.0, 10.0, "#ff0000");
+        assert_eq!(vec![255, 0, 0, 255], pixel(&canvas, 5.0, 5.0));
+        assert_eq!(vec![0, 0, 0, 0], pixel(&canvas, 15.0, 5.0));
+
+        assert!(canvas
+            .draw_text("Spair", 10.0, 15.0, "12px sans-serif", "blue")
+            .is_ok());
+
+        canvas.clear();
+        assert_eq!(vec![0, 0, 0, 0], pixel(&canvas, 5.0, 5.0));
+    }
+}
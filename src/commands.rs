@@ -0,0 +1,533 @@
+use std::{
+    cell::{Cell, RefCell},
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+use wasm_bindgen::UnwrapThrowExt;
+
+use crate::{
+    callback::CallbackOnce,
+    component::{Checklist, Command as CommandTrait, Comp, Component},
+    timer::{Interval, Timeout},
+    CallbackArg, Command,
+};
+
+impl<C: Component> Command<C> {
+    /// Create a command that spawns the given future when executed. The output of
+    /// the future is sent to `on_done`.
+    pub fn from_future<F, T>(future: F, on_done: CallbackArg<T>) -> Self
+    where
+        T: 'static,
+        F: 'static + std::future::Future<Output = T>,
+    {
+        crate::Future::new(future).with_callback(on_done)
+    }
+
+    /// Like [`Command::from_future`], but the future is tied to the component: if the
+    /// component is unmounted (or dropped) before the future resolves, the future is
+    /// dropped and `on_done` is never called. `make_future` receives an `AbortSignal`
+    /// that is aborted at the same time, pass it to a fetch (`fetch::Request::signal`)
+    /// to also cancel the network request.
+    pub fn scoped_future<M, F, T>(make_future: M, on_done: CallbackArg<T>) -> Self
+    where
+        T: 'static,
+        F: 'static + std::future::Future<Output = T>,
+        M: 'static + FnOnce(web_sys::AbortSignal) -> F,
+    {
+        Command(Box::new(ScopedFutureCommand(Some((make_future, on_done)))))
+    }
+
+    /// Combine the given commands into one. They are executed in order.
+    pub fn batch(commands: Vec<Command<C>>) -> Self {
+        Command(Box::new(BatchCommand(
+            commands.into_iter().map(|cmd| cmd.0).collect(),
+        )))
+    }
+}
+
+struct BatchCommand<C>(Vec<Box<dyn CommandTrait<C>>>);
+
+impl<C: Component> CommandTrait<C> for BatchCommand<C> {
+    fn execute(&mut self, comp: &Comp<C>, state: &mut C) {
+        self.0.iter_mut().for_each(|cmd| cmd.execute(comp, state));
+    }
+}
+
+struct ScopedFutureCommand<M, T>(Option<(M, CallbackArg<T>)>);
+
+impl<C, M, F, T> CommandTrait<C> for ScopedFutureCommand<M, T>
+where
+    C: Component,
+    T: 'static,
+    F: 'static + std::future::Future<Output = T>,
+    M: 'static + FnOnce(web_sys::AbortSignal) -> F,
+{
+    fn execute(&mut self, comp: &Comp<C>, _state: &mut C) {
+        let (make_future, on_done) = self
+            .0
+            .take()
+            .expect_throw("Internal error: Why ScopedFutureCommand is executed twice?");
+        let comp = comp.clone();
+        // The component is being updated now, it can not be borrowed to register
+        // the handle until the future is spawned.
+        wasm_bindgen_futures::spawn_local(async move {
+            let handle = AbortHandle::new();
+            let future = Abortable {
+                future: Box::pin(make_future(handle.signal())),
+                state: handle.0.clone(),
+            };
+            if !comp.add_abort_handle(handle) {
+                return;
+            }
+            if let Some(rs) = future.await {
+                on_done.call(rs);
+            }
+        });
+    }
+}
+
+struct AbortState {
+    settled: Cell<bool>,
+    aborted: Cell<bool>,
+    waker: Cell<Option<Waker>>,
+    controller: web_sys::AbortController,
+}
+
+/// Aborts a future spawned by [`Command::scoped_future`] when it is dropped. The
+/// handles are stored in the component and are dropped when it is unmounted.
+pub(crate) struct AbortHandle(Rc<AbortState>);
+
+impl AbortHandle {
+    fn new() -> Self {
+        Self(Rc::new(AbortState {
+            settled: Cell::new(false),
+            aborted: Cell::new(false),
+            waker: Cell::new(None),
+            controller: web_sys::AbortController::new()
+                .expect_throw("commands::AbortHandle::new AbortController"),
+        }))
+    }
+
+    fn signal(&self) -> web_sys::AbortSignal {
+        self.0.controller.signal()
+    }
+
+    pub(crate) fn is_settled(&self) -> bool {
+        self.0.settled.get()
+    }
+}
+
+impl Drop for AbortHandle {
+    fn drop(&mut self) {
+        if self.0.settled.get() {
+            return;
+        }
+        self.0.aborted.set(true);
+        self.0.controller.abort();
+        // Wake the task so that the future is dropped now.
+        if let Some(waker) = self.0.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+struct Abortable<F> {
+    future: Pin<Box<F>>,
+    state: Rc<AbortState>,
+}
+
+impl<F: std::future::Future> std::future::Future for Abortable<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.aborted.get() {
+            self.state.settled.set(true);
+            return Poll::Ready(None);
+        }
+        self.state.waker.set(Some(cx.waker().clone()));
+        let rs = self.future.as_mut().poll(cx);
+        if rs.is_ready() {
+            self.state.settled.set(true);
+        }
+        rs.map(Some)
+    }
+}
+
+// The timer only needs to be kept alive, it is cleared on drop.
+type Timer = Box<dyn std::any::Any>;
+
+/// Keeps a timer that is started by a command alive. The timer is cancelled
+/// when the handle is dropped (or `.cancel()` is called).
+#[must_use = "The timer will be cancelled immediately if the handle is dropped"]
+pub struct TimerHandle(Rc<RefCell<Option<Timer>>>);
+
+impl TimerHandle {
+    fn new() -> Self {
+        Self(Rc::new(RefCell::new(None)))
+    }
+
+    fn slot(&self) -> Weak<RefCell<Option<Timer>>> {
+        Rc::downgrade(&self.0)
+    }
+
+    pub fn cancel(self) {}
+}
+
+/// Create a command that executes `f` every `duration` until the returned
+/// handle is dropped. You may want to store the handle in your component state.
+pub fn every<C, Cl, F>(duration: Duration, f: F) -> (Command<C>, TimerHandle)
+where
+    C: Component,
+    Cl: 'static + Into<Checklist<C>>,
+    F: 'static + Fn(&mut C) -> Cl,
+{
+    let handle = TimerHandle::new();
+    let cmd = IntervalCommand {
+        duration,
+        slot: handle.slot(),
+        callback: Some(f),
+    };
+    (Command(Box::new(cmd)), handle)
+}
+
+struct IntervalCommand<F> {
+    duration: Duration,
+    slot: Weak<RefCell<Option<Timer>>>,
+    callback: Option<F>,
+}
+
+impl<C, Cl, F> CommandTrait<C> for IntervalCommand<F>
+where
+    C: Component,
+    Cl: 'static + Into<Checklist<C>>,
+    F: 'static + Fn(&mut C) -> Cl,
+{
+    fn execute(&mut self, comp: &Comp<C>, _state: &mut C) {
+        let callback = self
+            .callback
+            .take()
+            .expect_throw("Internal error: Why IntervalCommand is executed twice?");
+        let slot = match self.slot.upgrade() {
+            Some(slot) => slot,
+            // The handle is already dropped, the timer is cancelled before it starts.
+            None => return,
+        };
+        let callback = comp.callback_mut(callback);
+        let interval = Interval::new(self.duration, move || callback.call_or_queue());
+        *slot.borrow_mut() = Some(Box::new(interval));
+    }
+}
+
+/// Create a command that executes `f` once after `duration`. Dropping the
+/// returned handle before that cancels it.
+pub fn delay<C, Cl, F>(duration: Duration, f: F) -> (Command<C>, TimerHandle)
+where
+    C: Component,
+    Cl: 'static + Into<Checklist<C>>,
+    F: 'static + FnOnce(&mut C) -> Cl,
+{
+    let handle = TimerHandle::new();
+    let cmd = TimeoutCommand {
+        duration,
+        slot: handle.slot(),
+        callback: Some(f),
+    };
+    (Command(Box::new(cmd)), handle)
+}
+
+struct TimeoutCommand<F> {
+    duration: Duration,
+    slot: Weak<RefCell<Option<Timer>>>,
+    callback: Option<F>,
+}
+
+impl<C, Cl, F> CommandTrait<C> for TimeoutCommand<F>
+where
+    C: Component,
+    Cl: 'static + Into<Checklist<C>>,
+    F: 'static + FnOnce(&mut C) -> Cl,
+{
+    fn execute(&mut self, comp: &Comp<C>, _state: &mut C) {
+        let callback = self
+            .callback
+            .take()
+            .expect_throw("Internal error: Why TimeoutCommand is executed twice?");
+        let slot = match self.slot.upgrade() {
+            Some(slot) => slot,
+            None => return,
+        };
+        let callback = comp.callback_once_mut(callback);
+        let timeout = Timeout::new(self.duration, move || callback.call_or_queue());
+        *slot.borrow_mut() = Some(Box::new(timeout));
+    }
+}
+
+/// Create a command that fetches `url` (with `GET`) every `interval` and passes the text
+/// of the response to `on_data`. The first fetch starts when the command is executed.
+/// On failure, the delay before the next fetch is doubled (up to 32 times `interval`),
+/// and is reset to `interval` after a successful fetch. Polling stops when the returned
+/// handle is dropped, you may want to store it in your component state.
+pub fn poll<C, Cl, F>(
+    url: impl Into<String>,
+    interval: Duration,
+    on_data: F,
+) -> (Command<C>, TimerHandle)
+where
+    C: Component,
+    Cl: 'static + Into<Checklist<C>>,
+    F: 'static + Fn(&mut C, String) -> Cl,
+{
+    let handle = TimerHandle::new();
+    let cmd = PollCommand {
+        url: url.into(),
+        interval,
+        slot: handle.slot(),
+        on_data: Some(on_data),
+    };
+    (Command(Box::new(cmd)), handle)
+}
+
+const MAX_POLL_BACKOFF_EXPONENT: u32 = 5;
+
+struct PollCommand<F> {
+    url: String,
+    interval: Duration,
+    slot: Weak<RefCell<Option<Timer>>>,
+    on_data: Option<F>,
+}
+
+impl<C, Cl, F> CommandTrait<C> for PollCommand<F>
+where
+    C: Component,
+    Cl: 'static + Into<Checklist<C>>,
+    F: 'static + Fn(&mut C, String) -> Cl,
+{
+    fn execute(&mut self, comp: &Comp<C>, _state: &mut C) {
+        let on_data = self
+            .on_data
+            .take()
+            .expect_throw("Internal error: Why PollCommand is executed twice?");
+        let poller = Rc::new(Poller {
+            url: std::mem::take(&mut self.url),
+            interval: self.interval,
+            failures: Cell::new(0),
+            slot: self.slot.clone(),
+            on_data: comp.callback_arg_mut(on_data),
+        });
+        poller.fetch();
+    }
+}
+
+struct Poller {
+    url: String,
+    interval: Duration,
+    failures: Cell<u32>,
+    slot: Weak<RefCell<Option<Timer>>>,
+    on_data: CallbackArg<String>,
+}
+
+impl Poller {
+    fn fetch(self: Rc<Self>) {
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = crate::fetch::get(self.url.as_str()).text().await;
+            let slot = match self.slot.upgrade() {
+                Some(slot) => slot,
+                // The handle is dropped while fetching
+                None => return,
+            };
+            let delay = match result {
+                Ok(text) => {
+                    self.failures.set(0);
+                    self.on_data.call_or_queue(text);
+                    self.interval
+                }
+                Err(e) => {
+                    crate::diagnostics::warn(format_args!(
+                        "Spair: polling {} failed: {}",
+                        self.url, e
+                    ));
+                    let failures = self.failures.get() + 1;
+                    self.failures.set(failures);
+                    self.interval * 2u32.pow(failures.min(MAX_POLL_BACKOFF_EXPONENT))
+                }
+            };
+            let poller = self.clone();
+            let timeout = Timeout::new(delay, move || poller.fetch());
+            *slot.borrow_mut() = Some(Box::new(timeout));
+        });
+    }
+}
+
+#[cfg(test)]
+mod commands_tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component, RcComp};
+    use crate::test_utils::{mount, sleep};
+
+    struct State {
+        ticks: u32,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, _: crate::Element<Self>) {}
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self { ticks: 0 }
+        }
+    }
+
+    fn execute(rc: &RcComp<State>, cmd: crate::Command<State>) {
+        rc.comp().callback_once_mut(move |_: &mut State| cmd).call();
+    }
+
+    fn ticks(rc: &RcComp<State>) -> u32 {
+        rc.comp_instance().state().ticks
+    }
+
+    #[wasm_bindgen_test]
+    async fn every() {
+        let (_root, rc) = mount::<State>();
+        let (cmd, handle) = super::every(Duration::from_millis(10), |state: &mut State| {
+            state.ticks += 1;
+        });
+        execute(&rc, cmd);
+
+        sleep(65).await;
+        let ticks_before_cancel = ticks(&rc);
+        assert!(ticks_before_cancel >= 2);
+
+        handle.cancel();
+        sleep(40).await;
+        assert_eq!(ticks_before_cancel, ticks(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    async fn delay() {
+        let (_root, rc) = mount::<State>();
+        let (cmd, _handle) = super::delay(Duration::from_millis(20), |state: &mut State| {
+            state.ticks += 1;
+        });
+        execute(&rc, cmd);
+        assert_eq!(0, ticks(&rc));
+        sleep(50).await;
+        assert_eq!(1, ticks(&rc));
+
+        let (cmd, handle) = super::delay(Duration::from_millis(20), |state: &mut State| {
+            state.ticks += 1;
+        });
+        execute(&rc, cmd);
+        handle.cancel();
+        sleep(50).await;
+        assert_eq!(1, ticks(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    async fn from_future() {
+        let (_root, rc) = mount::<State>();
+        let on_done = rc
+            .comp()
+            .callback_arg_mut(|state: &mut State, value: u32| state.ticks = value);
+        execute(
+            &rc,
+            crate::Command::from_future(std::future::ready(42), on_done),
+        );
+        sleep(0).await;
+        assert_eq!(42, ticks(&rc));
+    }
+
+    struct AddTicks(u32);
+    impl crate::component::Command<State> for AddTicks {
+        fn execute(&mut self, _comp: &Comp<State>, state: &mut State) {
+            state.ticks = state.ticks * 10 + self.0;
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn batch() {
+        let (_root, rc) = mount::<State>();
+        let cmd = crate::Command::batch(vec![
+            crate::Command(Box::new(AddTicks(1))),
+            crate::Command(Box::new(AddTicks(2))),
+            crate::Command(Box::new(AddTicks(3))),
+        ]);
+        execute(&rc, cmd);
+        assert_eq!(123, ticks(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    async fn poll() {
+        let (_root, rc) = mount::<State>();
+        let (cmd, handle) = super::poll(
+            "data:text/plain,7",
+            Duration::from_millis(20),
+            |state: &mut State, text| {
+                state.ticks += text.parse::<u32>().unwrap_or(100);
+            },
+        );
+        execute(&rc, cmd);
+
+        sleep(110).await;
+        let ticks_before_cancel = ticks(&rc);
+        assert!(ticks_before_cancel >= 14);
+        assert_eq!(0, ticks_before_cancel % 7);
+
+        handle.cancel();
+        sleep(60).await;
+        assert_eq!(ticks_before_cancel, ticks(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    async fn scoped_future() {
+        let (_root, rc) = mount::<State>();
+        let (_other_root, results) = mount::<State>();
+        let on_done = || {
+            results
+                .comp()
+                .callback_arg_mut(|state: &mut State, value: u32| state.ticks += value)
+        };
+        execute(
+            &rc,
+            crate::Command::scoped_future(
+                |_| async {
+                    sleep(10).await;
+                    1
+                },
+                on_done(),
+            ),
+        );
+        sleep(30).await;
+        assert_eq!(1, ticks(&results));
+
+        let signal = Rc::new(RefCell::new(None));
+        let signal_clone = signal.clone();
+        execute(
+            &rc,
+            crate::Command::scoped_future(
+                move |s| {
+                    *signal_clone.borrow_mut() = Some(s);
+                    async {
+                        sleep(20).await;
+                        10
+                    }
+                },
+                on_done(),
+            ),
+        );
+        sleep(0).await;
+        let signal = signal.take().expect_throw("The future is spawned");
+        assert!(!signal.aborted());
+
+        rc.unmount();
+        assert!(signal.aborted());
+        sleep(40).await;
+        assert_eq!(1, ticks(&results));
+    }
+}
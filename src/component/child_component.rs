@@ -1,6 +1,6 @@
 use wasm_bindgen::UnwrapThrowExt;
 
-use super::{Checklist, Comp, CompInstance, Component, RcComp};
+use super::{Checklist, Comp, CompInstance, Component, RcComp, ShouldRender};
 use crate::dom::{Element, TagName};
 
 pub type ChildComp<C> = RcComp<C>;
@@ -13,14 +13,30 @@ impl<C: Component> ChildComp<C> {
 
 pub trait AsChildComp: Sized + Component {
     const ROOT_ELEMENT_TAG: TagName;
+    /// If `true`, an open shadow root is attached to the root element and the component
+    /// is rendered into it. Styles defined in the component do not leak out of it and
+    /// styles of the page do not apply to the component's content.
+    const SHADOW_ROOT: bool = false;
     type Properties;
     fn init(comp: &Comp<Self>, props: Self::Properties) -> Self;
+
+    /// Receive new properties sent by the parent via [`ChildComp::update_props`]. The
+    /// child is rendered if this returns `ShouldRender::Yes`, hence, it should compare
+    /// with the current properties to avoid re-rendering on every render of the parent.
+    /// By default, new properties are ignored.
+    fn update_props(&mut self, _props: Self::Properties) -> Checklist<Self> {
+        ShouldRender::No.into()
+    }
+
     fn with_props(props: Self::Properties) -> ChildComp<Self> {
-        let root_element = match Self::ROOT_ELEMENT_TAG {
+        let mut root_element = match Self::ROOT_ELEMENT_TAG {
             TagName::Html(tag) => Element::new_ns(tag),
             #[cfg(feature = "svg")]
             TagName::Svg(tag) => Element::new_ns(tag),
         };
+        if Self::SHADOW_ROOT {
+            root_element.attach_shadow_root();
+        }
         let rc_comp = ChildComp::with_root(root_element);
         let comp = rc_comp.comp();
         let state = AsChildComp::init(&comp, props);
@@ -48,6 +64,19 @@ impl<C: AsChildComp + Component> ChildComp<C> {
         }
     }
 
+    /// Send new properties to `AsChildComp::update_props` of the child. This is usually
+    /// called by the parent in its `render`, the child is updated after the parent
+    /// finishes rendering.
+    pub fn update_props(&self, props: C::Properties)
+    where
+        C::Properties: 'static,
+    {
+        let cb = self
+            .comp()
+            .callback_once_mut(move |state: &mut C| AsChildComp::update_props(state, props));
+        crate::callback::CallbackOnce::call_or_queue(cb);
+    }
+
     pub fn no_updater<P>(self) -> Child<P, C, ()>
     where
         P: Component,
@@ -81,12 +110,13 @@ impl<C: Component> From<Comp<C>> for ComponentHandle<C> {
 impl<C: Component> Drop for ChildComp<C> {
     fn drop(&mut self) {
         crate::routing::remove_routing_callback::<C>();
-        self.0
+        // The rendered nodes are in the shadow root if the component has one
+        let mut instance = self
+            .0
             .try_borrow_mut()
-            .expect_throw("Why unable to borrow a child component in dropping?")
-            .root_element
-            .ws_element()
-            .set_text_content(None);
+            .expect_throw("Why unable to borrow a child component in dropping?");
+        let (ws_node, _) = instance.root_element.ws_node_and_nodes_mut();
+        ws_node.set_text_content(None);
     }
 }
 
@@ -144,4 +174,383 @@ where
     pub fn first_render(&self) {
         self.child.first_render();
     }
+
+    #[cfg(feature = "error-boundary")]
+    pub(crate) fn set_error_handler(
+        &self,
+        error_handler: crate::CallbackArg<super::ComponentError>,
+    ) {
+        self.child.set_error_handler(error_handler);
+    }
+}
+
+#[cfg(test)]
+mod child_component_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{AsChildComp, ChildComp, Comp, Component};
+    use crate::render::html::{
+        HemsForDistinctNames, HemsHandMade, HtmlTag, MethodsForHtmlElementContent,
+        UpdateHtmlElement,
+    };
+
+    struct Page {
+        child: ChildComp<Isolated>,
+    }
+
+    impl Component for Page {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element
+                .p(|p| {
+                    p.rupdate("outside");
+                })
+                .component_ref(&self.child);
+        }
+    }
+
+    impl crate::application::Application for Page {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                child: Isolated::with_props(()),
+            }
+        }
+    }
+
+    struct Isolated;
+
+    impl Component for Isolated {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element
+                .render_element("style", |s| {
+                    s.rupdate("p { color: rgb(255, 0, 0); }");
+                })
+                .p(|p| {
+                    p.rupdate("inside");
+                });
+        }
+    }
+
+    impl AsChildComp for Isolated {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("section"));
+        const SHADOW_ROOT: bool = true;
+        type Properties = ();
+        fn init(_: &Comp<Self>, _: Self::Properties) -> Self {
+            Self
+        }
+    }
+
+    fn color(element: &web_sys::Element) -> String {
+        crate::utils::window()
+            .get_computed_style(element)
+            .expect_throw("child_component_tests::color get_computed_style")
+            .expect_throw("child_component_tests::color no style")
+            .get_property_value("color")
+            .expect_throw("child_component_tests::color get_property_value")
+    }
+
+    #[wasm_bindgen_test]
+    fn render_into_shadow_root() {
+        let (root, _rc) = crate::test_utils::mount_in_body::<Page>();
+
+        let host = root
+            .ws_element()
+            .as_ref()
+            .query_selector("section")
+            .expect_throw("child_component_tests query section")
+            .expect_throw("child_component_tests no section");
+        // Nothing is rendered into the light DOM of the host
+        assert_eq!(0, host.child_element_count());
+        let shadow_root = host
+            .shadow_root()
+            .expect_throw("child_component_tests no shadow root");
+        let inside = shadow_root
+            .query_selector("p")
+            .expect_throw("child_component_tests query inside")
+            .expect_throw("child_component_tests no inside");
+        assert_eq!(Some("inside".to_string()), inside.text_content());
+        assert_eq!("rgb(255, 0, 0)", color(&inside));
+
+        // The style in the shadow root does not apply to the outside
+        let outside = root
+            .ws_element()
+            .as_ref()
+            .query_selector("p")
+            .expect_throw("child_component_tests query outside")
+            .expect_throw("child_component_tests no outside");
+        assert_eq!(Some("outside".to_string()), outside.text_content());
+        assert_ne!("rgb(255, 0, 0)", color(&outside));
+
+        root.ws_element().as_ref().remove();
+    }
+
+    #[wasm_bindgen_test]
+    fn unmount_and_drop_clear_the_shadow_root() {
+        let shadow_root = |child: &ChildComp<Isolated>| {
+            child
+                .comp_instance()
+                .root_element()
+                .ws_element()
+                .as_ref()
+                .shadow_root()
+                .expect_throw("child_component_tests no shadow root")
+        };
+
+        let child = Isolated::with_props(());
+        child.first_render();
+        let rendered = shadow_root(&child);
+        assert_eq!(2, rendered.child_nodes().length());
+        child.unmount();
+        assert_eq!(0, rendered.child_nodes().length());
+
+        let child = Isolated::with_props(());
+        child.first_render();
+        let rendered = shadow_root(&child);
+        assert_eq!(2, rendered.child_nodes().length());
+        drop(child);
+        assert_eq!(0, rendered.child_nodes().length());
+    }
+
+    struct Counters {
+        counters: Vec<ChildComp<Counter>>,
+    }
+
+    impl Component for Counters {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::html::HemsForList;
+            element.list_with_render(
+                self.counters.iter(),
+                crate::ListElementCreation::Clone,
+                "div",
+                |counter, d| {
+                    d.component_in_list(counter);
+                },
+            );
+        }
+    }
+
+    impl crate::application::Application for Counters {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                counters: (1..=3).map(Counter::with_props).collect(),
+            }
+        }
+    }
+
+    struct Counter(u32);
+
+    impl Component for Counter {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.rupdate(self.0);
+        }
+    }
+
+    impl AsChildComp for Counter {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("span"));
+        type Properties = u32;
+        fn init(_: &Comp<Self>, value: Self::Properties) -> Self {
+            Self(value)
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn components_in_list() {
+        let (root, rc) = crate::test_utils::mount::<Counters>();
+        let text = || root.ws_element().as_ref().text_content();
+        let all_mounted = || {
+            rc.comp_instance()
+                .state()
+                .counters
+                .iter()
+                .all(|c| c.comp_instance().is_mounted())
+        };
+        assert_eq!(Some("123".to_string()), text());
+        assert_eq!(
+            3,
+            root.ws_element()
+                .as_ref()
+                .query_selector_all("div > span")
+                .unwrap_throw()
+                .length()
+        );
+        assert!(all_mounted());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut Counters| {
+                state.counters.remove(0);
+            })
+            .call();
+        assert_eq!(Some("23".to_string()), text());
+        assert!(all_mounted());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut Counters| {
+                state.counters.push(Counter::with_props(4));
+            })
+            .call();
+        assert_eq!(Some("234".to_string()), text());
+        assert!(all_mounted());
+    }
+
+    struct Switcher {
+        show: bool,
+        child: ChildComp<Loader>,
+    }
+
+    impl Component for Switcher {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.match_if(|mi| match self.show {
+                true => crate::set_arm!(mi).component_ref(&self.child).done(),
+                false => crate::set_arm!(mi).rupdate("hidden").done(),
+            });
+        }
+    }
+
+    impl crate::application::Application for Switcher {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                show: true,
+                child: Loader::with_props(()),
+            }
+        }
+    }
+
+    struct Loader {
+        loaded: Option<u32>,
+    }
+
+    impl Component for Loader {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.rupdate(self.loaded.unwrap_or(0));
+        }
+    }
+
+    impl AsChildComp for Loader {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("span"));
+        type Properties = ();
+        fn init(_: &Comp<Self>, _: Self::Properties) -> Self {
+            Self { loaded: None }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn skip_work_after_unmounted() {
+        let (root, rc) = crate::test_utils::mount::<Switcher>();
+        let child = rc.comp_instance().state().child.comp();
+        // Simulate a fetch that resolves later
+        let resolve = |value: u32| {
+            if child.is_mounted() {
+                child
+                    .callback_arg_mut(|state: &mut Loader, value| state.loaded = Some(value))
+                    .call(value);
+            }
+        };
+        let loaded = || {
+            rc.comp_instance()
+                .state()
+                .child
+                .comp_instance()
+                .state()
+                .loaded
+        };
+
+        assert!(child.is_mounted());
+        resolve(1);
+        assert_eq!(Some(1), loaded());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut Switcher| state.show = false)
+            .call();
+        assert_eq!(
+            Some("hidden".to_string()),
+            root.ws_element().as_ref().text_content()
+        );
+        assert!(!child.is_mounted());
+        resolve(2);
+        assert_eq!(Some(1), loaded());
+    }
+
+    struct Scoreboard {
+        score: u32,
+        badge: ChildComp<Badge>,
+    }
+
+    impl Component for Scoreboard {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            self.badge.update_props(self.score / 10);
+            element.component_ref(&self.badge);
+        }
+    }
+
+    impl crate::application::Application for Scoreboard {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                score: 0,
+                badge: Badge::with_props(0),
+            }
+        }
+    }
+
+    struct Badge {
+        level: u32,
+    }
+
+    impl Component for Badge {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.rupdate("Level ").rupdate(self.level);
+        }
+    }
+
+    impl AsChildComp for Badge {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("span"));
+        type Properties = u32;
+        fn init(_: &Comp<Self>, level: Self::Properties) -> Self {
+            Self { level }
+        }
+
+        fn update_props(&mut self, level: Self::Properties) -> crate::Checklist<Self> {
+            if self.level == level {
+                return crate::ShouldRender::No.into();
+            }
+            self.level = level;
+            crate::ShouldRender::Yes.into()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn update_props() {
+        let (root, rc) = crate::test_utils::mount::<Scoreboard>();
+        let text = || root.ws_element().as_ref().text_content();
+        let badge_renders = || {
+            rc.comp_instance()
+                .state()
+                .badge
+                .comp_instance()
+                .render_count()
+        };
+        let set_score = |score| {
+            rc.comp()
+                .callback_once_mut(move |state: &mut Scoreboard| state.score = score)
+                .call()
+        };
+        assert_eq!(Some("Level 0".to_string()), text());
+        let first_renders = badge_renders();
+
+        set_score(25);
+        assert_eq!(Some("Level 2".to_string()), text());
+        assert_eq!(first_renders + 1, badge_renders());
+
+        // The parent is rendered, but the props of the child are unchanged
+        set_score(27);
+        assert_eq!(Some("Level 2".to_string()), text());
+        assert_eq!(first_renders + 1, badge_renders());
+    }
 }
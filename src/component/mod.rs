@@ -1,5 +1,5 @@
 use std::cell::{Cell, RefCell};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::rc::{Rc, Weak};
 use wasm_bindgen::UnwrapThrowExt;
 
@@ -21,7 +21,7 @@ struct UpdateQueue {
 thread_local! {
     static UPDATE_QUEUE: UpdateQueue = UpdateQueue {
         will_be_executed: Cell::new(false),
-        queue: RefCell::new(VecDeque::new())
+        queue: RefCell::new(VecDeque::new()),
     };
 }
 
@@ -51,6 +51,18 @@ pub fn update_component(fn_update: impl FnOnce() + 'static) {
     UPDATE_QUEUE.with(|uq| uq.add(Box::new(fn_update)));
 }
 
+/// Run `f` as a batch of updates. Callbacks that are called by `call_or_queue` (such
+/// as event handlers dispatched by `f`) and updates added by `update_component`
+/// during `f` are queued, then they are executed in order after `f` returns. This is
+/// useful for integrations (for example, a WebSocket message that affects many
+/// components) and tests. If the queue is already being executed (`batch` is called
+/// in a callback), the updates are just executed with the current queue.
+pub fn batch(f: impl FnOnce()) {
+    let promise = self::i_have_to_execute_update_queue();
+    f();
+    self::execute_update_queue(promise);
+}
+
 fn execute_update_queue(promise: bool) {
     if !promise {
         return;
@@ -58,6 +70,22 @@ fn execute_update_queue(promise: bool) {
     UPDATE_QUEUE.with(|uq| uq.execute());
 }
 
+// Callbacks that were queued while no component was updating must be executed
+// before the callback of the caller, to keep their order.
+fn execute_pending_updates(promise: bool) {
+    if !promise {
+        return;
+    }
+    UPDATE_QUEUE.with(|uq| uq.drain());
+}
+
+// The caller is unable to execute the queue, let the next caller do it.
+fn release_update_queue_promise(promise: bool) {
+    if promise {
+        UPDATE_QUEUE.with(|uq| uq.will_be_executed.set(false));
+    }
+}
+
 impl UpdateQueue {
     fn add(&self, f: Box<dyn FnOnce()>) {
         self.queue.borrow_mut().push_back(f);
@@ -67,10 +95,14 @@ impl UpdateQueue {
         self.queue.borrow_mut().pop_front()
     }
 
-    fn execute(&self) {
+    fn drain(&self) {
         while let Some(f) = self.take() {
             f();
         }
+    }
+
+    fn execute(&self) {
+        self.drain();
         self.will_be_executed.set(false);
     }
 }
@@ -104,9 +136,47 @@ pub trait Component: 'static + Sized {
     /// This method will be called before executing an update method
     fn before_update(&mut self) {}
 
+    /// This method will be called when an update of a child component (rendered by this
+    /// component) returns an error (`Err(ComponentError)`), or when the child panics
+    /// while rendering or updating. Override it to render a fallback UI instead of the
+    /// child. By default, the error is raised again as a panic in this component.
+    ///
+    /// Panics are caught with `std::panic::catch_unwind`, hence they are only caught if
+    /// panics unwind. On `wasm32-unknown-unknown`, panics abort by default, only errors
+    /// returned by updates reach this method there.
+    #[cfg(feature = "error-boundary")]
+    fn on_child_error(&mut self, error: ComponentError) -> Checklist<Self> {
+        panic!("Spair: unhandled error in a child component: {}", error)
+    }
+
+    /// Opt-in to skip rendering after updates that do not change the state. If this
+    /// returns `Some`, a snapshot is taken before each update and compared with the
+    /// snapshot after the update. If they are equal, the component is not rendered,
+    /// even if the update returns `ShouldRender::Yes` (commands are still executed).
+    /// For example: `Some(Snapshot::new(self.clone()))`.
+    fn snapshot(&self) -> Option<Snapshot> {
+        None
+    }
+
+    /// The name of the component, used in render traces (enabled by the `debug-trace`
+    /// feature). By default, it is the type name of the component.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     fn render(&self, element: crate::Element<Self>);
 }
 
+/// An optional, Elm-style way to update a component: all updates are described by
+/// a single `Message` type and handled by `update`. Messages are sent to the
+/// component with [`Comp::emit`], [`Comp::message_callback`] or
+/// [`Comp::message_callback_arg`].
+pub trait MessageComponent: Component {
+    type Message: 'static;
+
+    fn update(&mut self, message: Self::Message) -> Checklist<Self>;
+}
+
 #[must_use = "This value must be returned to the framework. Otherwise, it will be lost and the default value will be used"]
 pub enum ShouldRender {
     No,
@@ -115,10 +185,7 @@ pub enum ShouldRender {
 
 impl<C: Component> From<ShouldRender> for Checklist<C> {
     fn from(should_render: ShouldRender) -> Self {
-        Checklist {
-            should_render,
-            commands: Commands(Vec::new()),
-        }
+        Checklist::new(should_render)
     }
 }
 
@@ -130,6 +197,149 @@ pub struct CompInstance<C> {
     root_element: Element,
     mount_status: MountStatus,
     events: Vec<Box<dyn crate::events::Listener>>,
+    named_events: HashMap<&'static str, Box<dyn crate::events::Listener>>,
+    error_handler: ErrorHandler,
+    render_count: u32,
+    scoped_futures: Vec<crate::commands::AbortHandle>,
+}
+
+trait SnapshotValue {
+    fn as_any(&self) -> &dyn std::any::Any;
+    fn eq_snapshot(&self, other: &dyn SnapshotValue) -> bool;
+}
+
+impl<T: 'static + PartialEq> SnapshotValue for T {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn eq_snapshot(&self, other: &dyn SnapshotValue) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// A copy of (a part of) the state of a component. See [`Component::snapshot`].
+pub struct Snapshot(Box<dyn SnapshotValue>);
+
+impl Snapshot {
+    pub fn new<T: 'static + PartialEq>(value: T) -> Self {
+        Self(Box::new(value))
+    }
+}
+
+impl PartialEq for Snapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_snapshot(&*other.0)
+    }
+}
+
+/// An error returned by an update of a child component, or a panic caught while
+/// rendering or updating it. See [`Component::on_child_error`].
+#[cfg(feature = "error-boundary")]
+#[derive(Debug, Clone)]
+pub struct ComponentError {
+    message: String,
+}
+
+#[cfg(feature = "error-boundary")]
+impl ComponentError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    fn from_panic(payload: Box<dyn std::any::Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "unknown panic".to_string(),
+            },
+        };
+        Self { message }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(feature = "error-boundary")]
+impl std::fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// An update returns `Err` to report the error to the parent component, the
+/// component is not rendered.
+#[cfg(feature = "error-boundary")]
+impl<C: Component, Cl: Into<Checklist<C>>> From<Result<Cl, ComponentError>> for Checklist<C> {
+    fn from(result: Result<Cl, ComponentError>) -> Self {
+        match result {
+            Ok(checklist) => checklist.into(),
+            Err(error) => {
+                let mut checklist = Checklist::skip_render();
+                checklist.error = Some(error);
+                checklist
+            }
+        }
+    }
+}
+
+#[cfg(feature = "error-boundary")]
+type ErrorHandler = Option<crate::CallbackArg<ComponentError>>;
+#[cfg(not(feature = "error-boundary"))]
+type ErrorHandler = ();
+
+#[cfg(not(feature = "error-boundary"))]
+fn catch_error<T>(_: &ErrorHandler, f: impl FnOnce() -> T) -> Option<T> {
+    Some(f())
+}
+
+#[cfg(not(feature = "error-boundary"))]
+fn report_error<C: Component>(_: &ErrorHandler, _: &mut Checklist<C>) {}
+
+// An error returned by an update is reported the same way as a caught panic.
+#[cfg(feature = "error-boundary")]
+fn report_error<C: Component>(error_handler: &ErrorHandler, checklist: &mut Checklist<C>) {
+    if let Some(error) = checklist.error.take() {
+        match error_handler {
+            Some(error_handler) => error_handler.queue(error),
+            None => panic!("Spair: unhandled error in a component: {}", error),
+        }
+    }
+}
+
+// Without an error handler (e.g. the root component), a panic propagates as usual.
+#[cfg(feature = "error-boundary")]
+fn catch_error<T>(error_handler: &ErrorHandler, f: impl FnOnce() -> T) -> Option<T> {
+    let error_handler = match error_handler {
+        Some(error_handler) => error_handler,
+        None => return Some(f()),
+    };
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+        Ok(value) => Some(value),
+        Err(payload) => {
+            // The parent component may be rendering this component, so
+            // the error must be queued.
+            error_handler.queue(ComponentError::from_panic(payload));
+            None
+        }
+    }
+}
+
+#[cfg(all(feature = "error-boundary", not(panic = "unwind")))]
+fn warn_about_aborting_panics() {
+    thread_local! {
+        static WARNED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+    if !WARNED.with(|warned| warned.replace(true)) {
+        crate::diagnostics::warn(format_args!(
+            "Spair: panics abort on this target, `on_child_error` is only called for errors returned by updates"
+        ));
+    }
 }
 
 pub enum MountStatus {
@@ -146,6 +356,8 @@ pub enum MountStatus {
 pub struct Checklist<C: Component> {
     should_render: ShouldRender,
     commands: Commands<C>,
+    #[cfg(feature = "error-boundary")]
+    error: Option<ComponentError>,
 }
 
 pub(crate) struct Commands<C>(Vec<Box<dyn Command<C>>>);
@@ -167,22 +379,25 @@ impl<C: Component> From<()> for Checklist<C> {
 }
 
 impl<C: Component> Checklist<C> {
+    fn new(should_render: ShouldRender) -> Self {
+        Self {
+            should_render,
+            commands: Commands(Vec::new()),
+            #[cfg(feature = "error-boundary")]
+            error: None,
+        }
+    }
+
     pub(crate) fn into_parts(self) -> (ShouldRender, Commands<C>) {
         (self.should_render, self.commands)
     }
 
     pub fn should_render() -> Self {
-        Self {
-            should_render: ShouldRender::Yes,
-            commands: Commands(Vec::new()),
-        }
+        Self::new(ShouldRender::Yes)
     }
 
     pub fn skip_render() -> Self {
-        Self {
-            should_render: ShouldRender::No,
-            commands: Commands(Vec::new()),
-        }
+        Self::new(ShouldRender::No)
     }
 
     pub fn set_should_render(&mut self) {
@@ -202,6 +417,34 @@ impl<C: Component> Checklist<C> {
     pub fn add_command(&mut self, cmd: crate::Command<C>) {
         self.commands.0.push(cmd.0);
     }
+
+    /// Add all commands that are present, `None`s are skipped. Useful when an update
+    /// method produces a dynamic number of commands.
+    pub fn extend_commands(&mut self, cmds: impl IntoIterator<Item = crate::OptionCommand<C>>) {
+        self.commands
+            .0
+            .extend(cmds.into_iter().filter_map(|cmd| cmd.0));
+    }
+
+    pub fn rendered(mut self) -> Self {
+        self.set_should_render();
+        self
+    }
+
+    pub fn not_rendered(mut self) -> Self {
+        self.set_skip_render();
+        self
+    }
+
+    pub fn with_option_command(mut self, cmd: crate::OptionCommand<C>) -> Self {
+        self.add_option_command(cmd);
+        self
+    }
+
+    pub fn with_command(mut self, cmd: crate::Command<C>) -> Self {
+        self.add_command(cmd);
+        self
+    }
 }
 
 impl<C: Component> RcComp<C> {
@@ -214,6 +457,10 @@ impl<C: Component> RcComp<C> {
             root_element,
             mount_status,
             events: Vec::new(),
+            named_events: HashMap::new(),
+            error_handler: ErrorHandler::default(),
+            render_count: 0,
+            scoped_futures: Vec::new(),
         })))
     }
 
@@ -223,6 +470,10 @@ impl<C: Component> RcComp<C> {
             root_element,
             mount_status: MountStatus::Mounted,
             events: Vec::new(),
+            named_events: HashMap::new(),
+            error_handler: ErrorHandler::default(),
+            render_count: 0,
+            scoped_futures: Vec::new(),
         })))
     }
 }
@@ -258,9 +509,41 @@ impl<C: Component> RcComp<C> {
         self::execute_update_queue(promise);
     }
 
+    /// Drop the state (hence, all child components), window listeners and rendered nodes.
+    pub(crate) fn unmount(&self) {
+        let state = {
+            let mut instance = self
+                .0
+                .try_borrow_mut()
+                .expect_throw("Why unable to mutably borrow comp instance to unmount?");
+            instance.mount_status = MountStatus::Unmounted;
+            instance.events.clear();
+            instance.named_events.clear();
+            instance.scoped_futures.clear();
+            // The rendered nodes are in the shadow root if the component has one
+            let (ws_node, nodes) = instance.root_element.ws_node_and_nodes_mut();
+            nodes.clear_vec();
+            ws_node.set_text_content(None);
+            instance.state.take()
+        };
+        // Child components may remove their routing callbacks or borrow other
+        // components when they are dropped, so the state is dropped outside the borrow.
+        drop(state);
+    }
+
     pub fn comp(&self) -> Comp<C> {
         Comp(Rc::downgrade(&self.0))
     }
+
+    #[cfg(feature = "error-boundary")]
+    pub(crate) fn set_error_handler(&self, error_handler: crate::CallbackArg<ComponentError>) {
+        #[cfg(not(panic = "unwind"))]
+        warn_about_aborting_panics();
+        self.0
+            .try_borrow_mut()
+            .expect_throw("Why unable to mutably borrow comp instance to set error handler?")
+            .error_handler = Some(error_handler);
+    }
 }
 
 impl<C: Component> Clone for Comp<C> {
@@ -270,6 +553,20 @@ impl<C: Component> Clone for Comp<C> {
 }
 
 impl<C: Component> Comp<C> {
+    /// Return `false` if the component is dropped or removed from its parent. Async
+    /// work (such as a fetch) should check this before updating a component that may
+    /// be unmounted while the work is in flight. A component that is being updated or
+    /// rendered is considered mounted.
+    pub fn is_mounted(&self) -> bool {
+        match self.0.upgrade() {
+            Some(instance) => match instance.try_borrow() {
+                Ok(instance) => !matches!(instance.mount_status, MountStatus::Unmounted),
+                Err(_) => true,
+            },
+            None => false,
+        }
+    }
+
     pub fn window_event(&self, listener: Box<dyn crate::events::Listener>) -> &Self {
         self.0
             .upgrade()
@@ -281,6 +578,44 @@ impl<C: Component> Comp<C> {
         self
     }
 
+    /// Store the listener with the given key, the listener previously stored with the
+    /// same key is dropped (and removed from the window). Unlike `window_event`, this is
+    /// safe to call in `Component::render`. All window listeners are dropped when the
+    /// component is unmounted.
+    pub fn window_event_named(
+        &self,
+        key: &'static str,
+        listener: Box<dyn crate::events::Listener>,
+    ) -> &Self {
+        self.0
+            .upgrade()
+            .expect_throw("Comp::window_event_named: why the component dropped?")
+            .try_borrow_mut()
+            .expect_throw("Why unable to mutably borrow comp instance to store event?")
+            .named_events
+            .insert(key, listener);
+        self
+    }
+
+    /// Store the handle until the component is unmounted, the future is aborted
+    /// then. Returns `false` (the handle is dropped) if the component is already
+    /// dropped or unmounted.
+    pub(crate) fn add_abort_handle(&self, handle: crate::commands::AbortHandle) -> bool {
+        let instance = match self.0.upgrade() {
+            Some(instance) => instance,
+            None => return false,
+        };
+        let mut instance = instance
+            .try_borrow_mut()
+            .expect_throw("Why unable to mutably borrow comp instance to store abort handle?");
+        if let MountStatus::Unmounted = instance.mount_status {
+            return false;
+        }
+        instance.scoped_futures.retain(|h| !h.is_settled());
+        instance.scoped_futures.push(handle);
+        true
+    }
+
     #[cfg(feature = "queue-render")]
     pub(crate) fn upgrade(&self) -> Rc<RefCell<CompInstance<C>>> {
         // Why wrapping this around an RcComp cause a bug the clear the root element empty?
@@ -289,10 +624,21 @@ impl<C: Component> Comp<C> {
             .expect_throw("Comp::upgrade: why the component dropped?")
     }
 
+    pub(crate) fn set_mount_status_to_mounted(&self) {
+        if let Some(instance) = self.0.upgrade() {
+            if let Ok(mut instance) = instance.try_borrow_mut() {
+                instance.mount_status = MountStatus::Mounted;
+            }
+        }
+    }
+
     fn set_mount_status_to_unmounted(&self) {
         if let Some(instance) = self.0.upgrade() {
             if let Ok(mut instance) = instance.try_borrow_mut() {
                 instance.mount_status = MountStatus::Unmounted;
+                instance.events.clear();
+                instance.named_events.clear();
+                instance.scoped_futures.clear();
             }
         }
     }
@@ -302,26 +648,52 @@ impl<C: Component> Comp<C> {
         Cb: crate::callback::ExecuteCallback<C, A>,
     {
         let promise = self::i_have_to_execute_update_queue();
+        self::execute_pending_updates(promise);
         {
-            let this = self
-                .0
-                .upgrade()
-                .expect_throw("Expect the component instance alive when updating - update()");
+            let this = match self.0.upgrade() {
+                Some(this) => this,
+                None => {
+                    // A queued callback of a component that was dropped (unmounted)
+                    // before the queue is executed.
+                    log::debug!("Spair: the component is dropped, the callback is ignored");
+                    self::execute_update_queue(promise);
+                    return;
+                }
+            };
             let mut this = match this.try_borrow_mut() {
                 Ok(this) => this,
                 Err(_) => {
+                    // The component is being updated or rendered (for example, the
+                    // callback is called by another callback of the same component).
+                    // The callback is queued and will be executed after the current
+                    // update, in the order that it was called.
                     callback.queue(arg);
+                    self::release_update_queue_promise(promise);
                     return;
                 }
             };
 
+            let this = &mut *this;
             let state = this
                 .state
                 .as_mut()
                 .expect_throw("Mutable reference to state for updating");
-            C::before_update(state);
-            let (should_render, commands) = callback.execute(state, arg).into_parts();
-            this.extra_update(should_render, commands, self);
+            let checklist = self::catch_error(&this.error_handler, || {
+                C::before_update(state);
+                let snapshot = state.snapshot();
+                let mut checklist = callback.execute(state, arg);
+                if let Some(snapshot) = snapshot {
+                    if state.snapshot().as_ref() == Some(&snapshot) {
+                        checklist.set_skip_render();
+                    }
+                }
+                checklist
+            });
+            if let Some(mut checklist) = checklist {
+                self::report_error(&this.error_handler, &mut checklist);
+                let (should_render, commands) = checklist.into_parts();
+                this.extra_update(should_render, commands, self);
+            }
         }
         self::execute_update_queue(promise);
         #[cfg(feature = "queue-render")]
@@ -404,6 +776,19 @@ impl<C: Component> Comp<C> {
         }
     }
 
+    fn cb_arg<Cl: 'static, A>(
+        &self,
+        f: impl Fn(&C, A) -> Cl + 'static,
+    ) -> crate::callback::CallbackFn<C, A>
+    where
+        Cl: Into<Checklist<C>>,
+    {
+        crate::callback::CallbackFn {
+            comp: self.clone(),
+            callback: Rc::new(crate::callback::CbArg(f)),
+        }
+    }
+
     fn cb_arg_mut<Cl: 'static, A>(
         &self,
         f: impl Fn(&mut C, A) -> Cl + 'static,
@@ -417,6 +802,29 @@ impl<C: Component> Comp<C> {
         }
     }
 
+    /// Same as `callback_once_mut`, but the callback is boxed, so one-shot callbacks
+    /// of different closures can be stored in the same way.
+    pub fn callback_once<Cl: 'static>(
+        &self,
+        f: impl FnOnce(&mut C) -> Cl + 'static,
+    ) -> crate::CallbackOnce
+    where
+        Cl: Into<Checklist<C>>,
+    {
+        Box::new(self.callback_once_mut(f))
+    }
+
+    /// Same as `callback_once_arg_mut`, but the callback is boxed.
+    pub fn callback_once_arg<Cl: 'static, A: 'static>(
+        &self,
+        f: impl FnOnce(&mut C, A) -> Cl + 'static,
+    ) -> crate::CallbackOnceArg<A>
+    where
+        Cl: Into<Checklist<C>>,
+    {
+        Box::new(self.callback_once_arg_mut(f))
+    }
+
     pub fn callback<Cl: 'static>(&self, f: impl Fn(&C) -> Cl + 'static) -> crate::Callback
     where
         Cl: Into<Checklist<C>>,
@@ -431,6 +839,25 @@ impl<C: Component> Comp<C> {
         Box::new(self.cb_mut(f))
     }
 
+    /// A callback that only reads the state, such as copying a value to the clipboard
+    /// or logging it. The component is never rendered after `f` is executed.
+    pub fn read(&self, f: impl Fn(&C) + 'static) -> crate::Callback {
+        self.callback(move |state| {
+            f(state);
+            ShouldRender::No
+        })
+    }
+
+    pub fn callback_arg<Cl: 'static, A: 'static>(
+        &self,
+        f: impl Fn(&C, A) -> Cl + 'static,
+    ) -> crate::CallbackArg<A>
+    where
+        Cl: Into<Checklist<C>>,
+    {
+        Box::new(self.cb_arg(f))
+    }
+
     pub fn callback_arg_mut<Cl: 'static, A: 'static>(
         &self,
         f: impl Fn(&mut C, A) -> Cl + 'static,
@@ -441,6 +868,108 @@ impl<C: Component> Comp<C> {
         Box::new(self.cb_arg_mut(f))
     }
 
+    /// Create a callback that navigates to `route` (like `Routes::execute_routing`) every
+    /// time it is called. The argument is ignored, so it can be used for any event, such
+    /// as `.on_click(comp.navigate_callback(Route::Home))`.
+    pub fn navigate_callback<A: 'static>(&self, route: C::Routes) -> crate::CallbackArg<A>
+    where
+        C::Routes: 'static,
+    {
+        use crate::routing::Routes;
+        self.callback_arg(move |_: &C, _: A| {
+            route.execute_routing();
+            ShouldRender::No
+        })
+    }
+
+    /// Wrap `f` into a JS function, for JS APIs (such as an event of a third-party JS
+    /// library) that call it outside of spair. Calls are routed through the update
+    /// queue, like `CallbackArg::call_or_queue`. The returned `Closure` must be kept
+    /// alive (for example, in the component state) as long as JS may call it, a
+    /// call after it is dropped throws an error in JS.
+    pub fn into_js_callback<A, Cl>(
+        &self,
+        f: impl Fn(&mut C, A) -> Cl + 'static,
+    ) -> wasm_bindgen::closure::Closure<dyn FnMut(A)>
+    where
+        A: 'static + wasm_bindgen::convert::FromWasmAbi,
+        Cl: 'static + Into<Checklist<C>>,
+    {
+        use crate::callback::CallbackArg;
+        let callback = self.callback_arg_mut(f);
+        wasm_bindgen::closure::Closure::wrap(
+            Box::new(move |a: A| callback.call_or_queue(a)) as Box<dyn FnMut(A)>
+        )
+    }
+
+    /// Create a callback that spawns the future returned by `f` every time it is called.
+    /// When the future completes, its output is passed to `on_done` to update the state.
+    pub fn callback_future<A, F, Cl>(
+        &self,
+        f: impl Fn(&C, A) -> F + 'static,
+        on_done: impl Fn(&mut C, F::Output) -> Cl + 'static,
+    ) -> crate::CallbackArg<A>
+    where
+        A: 'static,
+        F: 'static + std::future::Future,
+        F::Output: 'static,
+        Cl: 'static + Into<Checklist<C>>,
+    {
+        let comp = self.clone();
+        let on_done = Rc::new(on_done);
+        self.callback_arg(move |state: &C, a| {
+            let on_done = on_done.clone();
+            let callback =
+                comp.callback_arg_mut(move |state: &mut C, output| on_done(state, output));
+            crate::spawn_local(f(state, a), callback);
+            // Nothing changes until the future completes
+            ShouldRender::No
+        })
+    }
+
+    /// Create a callback that only executes `f` after it stops being called
+    /// for `duration`. Every new call resets the timer, so a burst of calls
+    /// results in only one execution, with the argument of the last call.
+    pub fn callback_debounced<Cl: 'static, A: 'static>(
+        &self,
+        duration: std::time::Duration,
+        f: impl Fn(&mut C, A) -> Cl + 'static,
+    ) -> crate::CallbackArg<A>
+    where
+        Cl: Into<Checklist<C>>,
+    {
+        Box::new(crate::callback::CallbackDebounced {
+            duration,
+            callback: Rc::new(self.cb_arg_mut(f)),
+            pending: Rc::new(RefCell::new(None)),
+        })
+    }
+
+    /// Create a callback that executes `f` at most once per `duration`. The
+    /// first call executes immediately, calls during the interval are dropped
+    /// except the last one, which is executed when the interval ends.
+    pub fn callback_throttled<Cl: 'static, A: 'static>(
+        &self,
+        duration: std::time::Duration,
+        f: impl Fn(&mut C, A) -> Cl + 'static,
+    ) -> crate::CallbackArg<A>
+    where
+        Cl: Into<Checklist<C>>,
+    {
+        Box::new(crate::callback::CallbackThrottled {
+            duration,
+            callback: Rc::new(self.cb_arg_mut(f)),
+            state: Rc::new(RefCell::new(Default::default())),
+        })
+    }
+
+    /// Render the component without changing its state. This is useful after
+    /// some changes that are not tracked by the component state.
+    pub fn force_render(&self) {
+        let cb = self.callback_once_mut(|_: &mut C| Checklist::should_render());
+        crate::callback::CallbackOnce::call_or_queue(cb);
+    }
+
     pub fn handler<Cl: 'static, A: 'static>(
         &self,
         f: impl Fn(&C) -> Cl + 'static,
@@ -470,6 +999,70 @@ impl<C: Component> Comp<C> {
     {
         self.cb_arg_mut(f)
     }
+
+    /// Create an event handler that receives the element that the listener is attached
+    /// to (`event.current_target()`), already cast to `T`, along with the event.
+    /// The handler is not called if the element is not a `T`.
+    pub fn handler_element<Cl, E, T>(
+        &self,
+        f: impl Fn(&C, T, E) -> Cl + 'static,
+    ) -> impl crate::callback::CallbackArg<E>
+    where
+        Cl: 'static + Into<Checklist<C>>,
+        E: crate::events::DomEvent,
+        T: 'static + wasm_bindgen::JsCast,
+    {
+        self.cb_arg(move |state, event: E| -> Checklist<C> {
+            match event.current_target_as::<T>() {
+                Some(element) => f(state, element, event).into(),
+                None => ShouldRender::No.into(),
+            }
+        })
+    }
+
+    /// Same as `handler_element` but the handler can mutate the state.
+    pub fn handler_element_mut<Cl, E, T>(
+        &self,
+        f: impl Fn(&mut C, T, E) -> Cl + 'static,
+    ) -> impl crate::callback::CallbackArg<E>
+    where
+        Cl: 'static + Into<Checklist<C>>,
+        E: crate::events::DomEvent,
+        T: 'static + wasm_bindgen::JsCast,
+    {
+        self.cb_arg_mut(move |state, event: E| -> Checklist<C> {
+            match event.current_target_as::<T>() {
+                Some(element) => f(state, element, event).into(),
+                None => ShouldRender::No.into(),
+            }
+        })
+    }
+}
+
+impl<C: MessageComponent> Comp<C> {
+    /// Send the message to `MessageComponent::update`. The update is queued if
+    /// there is an update in progress.
+    pub fn emit(&self, message: C::Message) {
+        let cb = self.callback_once_mut(move |state: &mut C| state.update(message));
+        crate::callback::CallbackOnce::call_or_queue(cb);
+    }
+
+    /// Create a callback that sends a clone of `message` every time it is called.
+    pub fn message_callback(&self, message: C::Message) -> crate::Callback
+    where
+        C::Message: Clone,
+    {
+        self.callback_mut(move |state: &mut C| state.update(message.clone()))
+    }
+
+    /// Create a callback that sends the message made by `f` from the argument, such
+    /// as `comp.message_callback_arg(|event: MouseEvent| Msg::Clicked(event.client_x()))`.
+    pub fn message_callback_arg<A: 'static>(
+        &self,
+        f: impl Fn(A) -> C::Message + 'static,
+    ) -> crate::CallbackArg<A> {
+        self.callback_arg_mut(move |state: &mut C, a: A| state.update(f(a)))
+    }
 }
 
 impl<C: Component> CompInstance<C> {
@@ -483,9 +1076,16 @@ impl<C: Component> CompInstance<C> {
         } else {
             ElementStatus::Existing
         };
-        let er =
-            crate::render::base::ElementUpdater::new(comp, state, &mut self.root_element, status);
-        state.render(er.into());
+        self.render_count += 1;
+        #[cfg(feature = "debug-trace")]
+        log::debug!("Spair: render {} (#{})", state.name(), self.render_count);
+        let root_element = &mut self.root_element;
+        self::catch_error(&self.error_handler, || {
+            #[cfg(feature = "debug-trace")]
+            let _rendering = crate::dom::RenderingComponent::new(state.name());
+            let er = crate::render::base::ElementUpdater::new(comp, state, root_element, status);
+            state.render(er.into());
+        });
     }
 
     fn extra_update(
@@ -511,6 +1111,22 @@ impl<C: Component> CompInstance<C> {
             .expect_throw("Immutably borrow the state from CompInstance::state()")
     }
 
+    /// A dump of the nodes of the component that are managed by Spair (elements with
+    /// their attribute slots, texts, and the boundaries of match-if groups, lists and
+    /// child components), one node per line. Only values of attributes that are set
+    /// in update mode are included. This is useful for assertions in tests, the DOM
+    /// is not read (except for tag names).
+    pub fn debug_tree(&self) -> String {
+        let mut out = String::new();
+        self.root_element.nodes().write_debug_tree(&mut out, 0);
+        out
+    }
+
+    /// The number of times the component has been rendered.
+    pub fn render_count(&self) -> u32 {
+        self.render_count
+    }
+
     pub(crate) fn is_mounted(&self) -> bool {
         matches!(self.mount_status, MountStatus::Mounted)
     }
@@ -519,3 +1135,721 @@ impl<C: Component> CompInstance<C> {
         &self.root_element
     }
 }
+
+#[cfg(test)]
+mod component_tests {
+    use std::time::Duration;
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::callback::CallbackArg;
+    use crate::component::{Comp, Component, RcComp};
+    use crate::render::html::HtmlTag;
+    use crate::test_utils::{mount, sleep};
+
+    struct State {
+        values: Vec<u32>,
+        renders: std::cell::Cell<u32>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, _: crate::Element<Self>) {
+            self.renders.set(self.renders.get() + 1);
+        }
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                values: Vec::new(),
+                renders: std::cell::Cell::new(0),
+            }
+        }
+    }
+
+    fn values(rc: &RcComp<State>) -> Vec<u32> {
+        rc.comp_instance().state().values.clone()
+    }
+
+    #[wasm_bindgen_test]
+    async fn callback_debounced() {
+        let (_root, rc) = mount::<State>();
+        let cb = rc
+            .comp()
+            .callback_debounced(Duration::from_millis(20), |state: &mut State, value| {
+                state.values.push(value)
+            });
+        cb.call_or_queue(1);
+        cb.call_or_queue(2);
+        cb.call_or_queue(3);
+        assert!(values(&rc).is_empty());
+
+        sleep(60).await;
+        assert_eq!(vec![3], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    async fn callback_throttled() {
+        let (_root, rc) = mount::<State>();
+        let cb = rc
+            .comp()
+            .callback_throttled(Duration::from_millis(30), |state: &mut State, value| {
+                state.values.push(value)
+            });
+        for value in 0..10 {
+            cb.call_or_queue(value);
+        }
+        // The leading call executes immediately
+        assert_eq!(vec![0], values(&rc));
+
+        sleep(80).await;
+        // Only the trailing call of the burst executes after the interval
+        assert_eq!(vec![0, 9], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    fn render_count() {
+        let (_root, rc) = mount::<State>();
+        let render_count = || rc.comp_instance().render_count();
+        assert_eq!(1, render_count());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.values.push(1))
+            .call();
+        assert_eq!(2, render_count());
+
+        rc.comp()
+            .callback_once_mut(|_: &mut State| crate::ShouldRender::No)
+            .call();
+        assert_eq!(2, render_count());
+
+        rc.comp().force_render();
+        assert_eq!(3, render_count());
+    }
+
+    #[wasm_bindgen_test]
+    fn read() {
+        let (_root, rc) = mount::<State>();
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.values.push(1))
+            .call();
+        let render_count = rc.comp_instance().render_count();
+
+        let read_values = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let cb = rc.comp().read({
+            let read_values = read_values.clone();
+            move |state: &State| read_values.borrow_mut().extend_from_slice(&state.values)
+        });
+        cb.call();
+        cb.call();
+        assert_eq!(vec![1, 1], *read_values.borrow());
+        assert_eq!(render_count, rc.comp_instance().render_count());
+    }
+
+    struct PushValue(u32);
+    impl super::Command<State> for PushValue {
+        fn execute(&mut self, _comp: &Comp<State>, state: &mut State) {
+            state.values.push(self.0);
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn batch() {
+        let (_root, rc) = mount::<State>();
+        let render_count = || rc.comp_instance().render_count();
+        let comp = rc.comp();
+        let first_render = render_count();
+
+        super::batch(|| {
+            use crate::callback::CallbackArg;
+            let push = comp.callback_arg_mut(|state: &mut State, value| state.values.push(value));
+            push.call_or_queue(1);
+            push.call_or_queue(2);
+            super::update_component({
+                let comp = comp.clone();
+                move || {
+                    comp.callback_once_mut(|state: &mut State| state.values.push(3))
+                        .call()
+                }
+            });
+            // Nothing is executed until the end of the batch
+            assert!(values(&rc).is_empty());
+            assert_eq!(first_render, render_count());
+        });
+        assert_eq!(vec![1, 2, 3], values(&rc));
+        assert_eq!(first_render + 3, render_count());
+    }
+
+    #[wasm_bindgen_test]
+    fn checklist_chaining() {
+        let (_root, rc) = mount::<State>();
+        rc.comp()
+            .callback_once_mut(|_: &mut State| {
+                super::Checklist::skip_render()
+                    .with_command(crate::Command(Box::new(PushValue(1))))
+                    .with_option_command(Some(crate::Command(Box::new(PushValue(2)))).into())
+                    .with_option_command(None.into())
+                    .rendered()
+            })
+            .call();
+        assert_eq!(vec![1, 2], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    fn checklist_from_commands() {
+        let (_root, rc) = mount::<State>();
+        rc.comp()
+            .callback_once_mut(|_: &mut State| {
+                let mut checklist: super::Checklist<State> = vec![3, 4]
+                    .into_iter()
+                    .map(|value| crate::Command(Box::new(PushValue(value))))
+                    .collect();
+                checklist.extend_commands(vec![
+                    Some(crate::Command(Box::new(PushValue(5)))).into(),
+                    None.into(),
+                    Some(crate::Command(Box::new(PushValue(6)))).into(),
+                    None.into(),
+                ]);
+                checklist
+            })
+            .call();
+        assert_eq!(vec![3, 4, 5, 6], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    fn force_render() {
+        let (_root, rc) = mount::<State>();
+        let renders = || rc.comp_instance().state().renders.get();
+        let first_render = renders();
+        rc.comp().force_render();
+        assert_eq!(first_render + 1, renders());
+        assert!(values(&rc).is_empty());
+    }
+
+    struct Form {
+        value: String,
+        last_length: std::cell::Cell<usize>,
+    }
+
+    impl Component for Form {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::base::MethodsForEvents;
+            use crate::render::html::HemsForDistinctNames;
+            let comp = element.comp();
+            element.input(|i| {
+                i.on_input(
+                    comp.callback_arg_mut(|state: &mut Form, value| state.value = value)
+                        .map_arg(|event: crate::InputEvent| {
+                            event
+                                .current_target_as_input_element()
+                                .map(|input| input.value())
+                                .unwrap_or_default()
+                        }),
+                );
+            });
+        }
+    }
+
+    impl crate::application::Application for Form {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                value: String::new(),
+                last_length: std::cell::Cell::new(0),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn callback_map_arg() {
+        use wasm_bindgen::JsCast;
+        let (root, rc) = mount::<Form>();
+        let input: web_sys::HtmlInputElement = root
+            .ws_element()
+            .as_ref()
+            .first_element_child()
+            .expect_throw("component_tests::callback_map_arg input")
+            .unchecked_into();
+        input.set_value("spair");
+        let event = web_sys::InputEvent::new("input")
+            .expect_throw("component_tests::callback_map_arg new input event");
+        input
+            .dispatch_event(&event)
+            .expect_throw("component_tests::callback_map_arg dispatch");
+        assert_eq!("spair", rc.comp_instance().state().value);
+
+        let cb = rc
+            .comp()
+            .callback_arg(|state: &Form, length| state.last_length.set(length))
+            .map_arg(|text: &str| text.len());
+        cb.call_or_queue("hello");
+        assert_eq!(5, rc.comp_instance().state().last_length.get());
+    }
+
+    struct Search {
+        query: String,
+        event_type: String,
+    }
+
+    impl Component for Search {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::base::MethodsForEvents;
+            use crate::render::html::HemsForDistinctNames;
+            let comp = element.comp();
+            element.input(|i| {
+                i.on_input(comp.handler_element_mut(
+                    |state: &mut Search,
+                     input: web_sys::HtmlInputElement,
+                     event: crate::InputEvent| {
+                        state.query = input.value();
+                        state.event_type = event.raw().type_();
+                    },
+                ));
+            });
+        }
+    }
+
+    impl crate::application::Application for Search {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                query: String::new(),
+                event_type: String::new(),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn handler_element() {
+        use wasm_bindgen::JsCast;
+        let (root, rc) = mount::<Search>();
+        let input: web_sys::HtmlInputElement = root
+            .ws_element()
+            .as_ref()
+            .first_element_child()
+            .expect_throw("component_tests::handler_element input")
+            .unchecked_into();
+        input.set_value("spair");
+        let event = web_sys::InputEvent::new("input")
+            .expect_throw("component_tests::handler_element new input event");
+        input
+            .dispatch_event(&event)
+            .expect_throw("component_tests::handler_element dispatch");
+        assert_eq!("spair", rc.comp_instance().state().query);
+        assert_eq!("input", rc.comp_instance().state().event_type);
+    }
+
+    struct Loader {
+        loaded: Option<u32>,
+    }
+
+    impl Component for Loader {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::base::MethodsForEvents;
+            use crate::render::html::{HemsForDistinctNames, MethodsForHtmlElementContent};
+            let comp = element.comp();
+            element
+                .button(|b| {
+                    b.on_click(comp.callback_future(
+                        |_: &Loader, _: crate::MouseEvent| async {
+                            sleep(10).await;
+                            42
+                        },
+                        |state: &mut Loader, value| state.loaded = Some(value),
+                    ))
+                    .rupdate("Load");
+                })
+                .rupdate(
+                    self.loaded
+                        .map(|value| value.to_string())
+                        .unwrap_or_default(),
+                );
+        }
+    }
+
+    impl crate::application::Application for Loader {
+        fn init(_: &Comp<Self>) -> Self {
+            Self { loaded: None }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn callback_future() {
+        use wasm_bindgen::JsCast;
+        let (root, rc) = mount::<Loader>();
+        let button: web_sys::HtmlElement = root
+            .ws_element()
+            .as_ref()
+            .first_element_child()
+            .expect_throw("component_tests::callback_future button")
+            .unchecked_into();
+
+        button.click();
+        assert_eq!(None, rc.comp_instance().state().loaded);
+
+        sleep(50).await;
+        assert_eq!(Some(42), rc.comp_instance().state().loaded);
+        assert_eq!(
+            Some("Load42".to_string()),
+            root.ws_element().as_ref().text_content()
+        );
+    }
+
+    #[cfg(feature = "error-boundary")]
+    mod child_error {
+        use super::*;
+        use crate::component::{AsChildComp, ComponentError, ShouldRender};
+
+        // The child panics in its render if its property is `true`
+        struct Faulty(bool);
+
+        impl Faulty {
+            fn load(&mut self) -> Result<ShouldRender, ComponentError> {
+                Err(ComponentError::new("faulty update"))
+            }
+        }
+
+        impl Component for Faulty {
+            type Routes = ();
+            fn render(&self, element: crate::Element<Self>) {
+                if self.0 {
+                    panic!("faulty render");
+                }
+                use crate::render::html::MethodsForHtmlElementContent;
+                element.rupdate("child");
+            }
+        }
+
+        impl AsChildComp for Faulty {
+            const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("div"));
+            type Properties = bool;
+            fn init(_: &Comp<Self>, panic_in_render: Self::Properties) -> Self {
+                Faulty(panic_in_render)
+            }
+        }
+
+        struct Parent<const PANIC_IN_RENDER: bool> {
+            child: crate::component::ChildComp<Faulty>,
+            error: Option<String>,
+        }
+
+        impl<const PANIC_IN_RENDER: bool> Component for Parent<PANIC_IN_RENDER> {
+            type Routes = ();
+            fn render(&self, element: crate::Element<Self>) {
+                use crate::render::html::HemsHandMade;
+                element.match_if(|mi| match self.error.as_ref() {
+                    None => crate::set_arm!(mi).component_ref(&self.child).done(),
+                    Some(error) => crate::set_arm!(mi).rupdate(error.as_str()).done(),
+                });
+            }
+
+            fn on_child_error(
+                &mut self,
+                error: ComponentError,
+            ) -> crate::component::Checklist<Self> {
+                self.error = Some(error.message().to_string());
+                crate::component::Checklist::should_render()
+            }
+        }
+
+        impl<const PANIC_IN_RENDER: bool> crate::application::Application for Parent<PANIC_IN_RENDER> {
+            fn init(_: &Comp<Self>) -> Self {
+                Self {
+                    child: Faulty::with_props(PANIC_IN_RENDER),
+                    error: None,
+                }
+            }
+        }
+
+        #[wasm_bindgen_test]
+        fn parent_renders_fallback_when_child_update_fails() {
+            let (root, rc) = mount::<Parent<false>>();
+            let text = || root.ws_element().as_ref().text_content();
+            assert_eq!(Some("child".to_string()), text());
+
+            let child = rc.comp_instance().state().child.comp();
+            child.callback_mut(Faulty::load).call();
+            assert_eq!(
+                Some("faulty update"),
+                rc.comp_instance().state().error.as_deref()
+            );
+            assert_eq!(Some("faulty update".to_string()), text());
+        }
+
+        // catch_unwind can not catch anything if panics abort
+        #[cfg(panic = "unwind")]
+        #[wasm_bindgen_test]
+        fn parent_renders_fallback_when_child_panics() {
+            let (root, rc) = mount::<Parent<true>>();
+            assert_eq!(
+                Some("faulty render"),
+                rc.comp_instance().state().error.as_deref()
+            );
+            assert_eq!(
+                Some("faulty render".to_string()),
+                root.ws_element().as_ref().text_content()
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn window_event_named() {
+        use crate::events::Change;
+        let (_root, rc) = mount::<State>();
+        let comp = rc.comp();
+        let push = |value| {
+            Change::on_window(comp.handler_mut(move |state: &mut State| state.values.push(value)))
+        };
+        comp.window_event_named("change", push(1));
+        comp.window_event_named("change", push(2));
+        assert_eq!(1, rc.comp_instance().named_events.len());
+
+        let event = web_sys::Event::new("change")
+            .expect_throw("component_tests::window_event_named new event");
+        crate::utils::window()
+            .dispatch_event(&event)
+            .expect_throw("component_tests::window_event_named dispatch");
+        assert_eq!(vec![2], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    fn boxed_callback_once() {
+        use crate::callback::{CallbackOnce, CallbackOnceArg};
+        let (_root, rc) = mount::<State>();
+        let comp = rc.comp();
+        let owned = vec![2, 3];
+        let pending: Vec<crate::CallbackOnce> = vec![
+            comp.callback_once(|state: &mut State| state.values.push(1)),
+            // Values can be moved out of the closure because it is called only once
+            comp.callback_once(move |state: &mut State| state.values.extend(owned)),
+        ];
+        for callback in pending {
+            callback.call_or_queue();
+        }
+        assert_eq!(vec![1, 2, 3], values(&rc));
+
+        let confirm: crate::CallbackOnceArg<u32> =
+            comp.callback_once_arg(|state: &mut State, value| state.values.push(value));
+        confirm.call_or_queue(4);
+        assert_eq!(vec![1, 2, 3, 4], values(&rc));
+    }
+
+    #[derive(Clone, PartialEq)]
+    struct Toggle {
+        on: bool,
+    }
+
+    impl Component for Toggle {
+        type Routes = ();
+        fn snapshot(&self) -> Option<super::Snapshot> {
+            Some(super::Snapshot::new(self.clone()))
+        }
+        fn render(&self, _: crate::Element<Self>) {}
+    }
+
+    impl crate::application::Application for Toggle {
+        fn init(_: &Comp<Self>) -> Self {
+            Self { on: false }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn skip_render_if_snapshot_unchanged() {
+        let (_root, rc) = mount::<Toggle>();
+        let render_count = || rc.comp_instance().render_count();
+        let set_on = |on| {
+            rc.comp()
+                .callback_once_mut(move |state: &mut Toggle| state.on = on)
+                .call()
+        };
+        assert_eq!(1, render_count());
+
+        set_on(true);
+        assert_eq!(2, render_count());
+
+        set_on(true);
+        assert_eq!(2, render_count());
+
+        set_on(false);
+        assert_eq!(3, render_count());
+    }
+
+    #[wasm_bindgen_test]
+    fn reentrant_callbacks_run_in_order() {
+        let (_root, rc) = mount::<State>();
+        let comp = rc.comp();
+        rc.comp()
+            .callback_once_mut(move |state: &mut State| {
+                // The component is being updated, these are queued.
+                comp.callback_once_mut(|state: &mut State| state.values.push(2))
+                    .call();
+                comp.callback_once_mut(|state: &mut State| state.values.push(3))
+                    .call();
+                state.values.push(1);
+            })
+            .call();
+        assert_eq!(vec![1, 2, 3], values(&rc));
+
+        // Queued while the component is borrowed outside of an update
+        let borrowed = rc.comp_instance();
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.values.push(4))
+            .call();
+        drop(borrowed);
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.values.push(5))
+            .call();
+        assert_eq!(vec![1, 2, 3, 4, 5], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    fn queued_callback_of_dropped_component() {
+        let (_root, rc) = mount::<State>();
+        let (_other_root, dropped) = mount::<State>();
+        let dropped_comp = dropped.comp();
+        rc.comp()
+            .callback_once_mut(move |state: &mut State| {
+                dropped_comp
+                    .callback_once_mut(|state: &mut State| state.values.push(1))
+                    .queue();
+                drop(dropped);
+                state.values.push(2);
+            })
+            .call();
+        assert_eq!(vec![2], values(&rc));
+
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.values.push(3))
+            .call();
+        assert_eq!(vec![2, 3], values(&rc));
+    }
+
+    #[wasm_bindgen_test]
+    fn into_js_callback() {
+        let (_root, rc) = mount::<State>();
+        let closure =
+            rc.comp()
+                .into_js_callback(|state: &mut State, value: wasm_bindgen::JsValue| {
+                    state.values.extend(value.as_f64().map(|v| v as u32))
+                });
+        let call_twice = js_sys::Function::new_with_args("f", "f(7); f(8);");
+        call_twice
+            .call1(&wasm_bindgen::JsValue::NULL, closure.as_ref())
+            .expect_throw("component_tests::into_js_callback call");
+        assert_eq!(vec![7, 8], values(&rc));
+    }
+
+    #[derive(Clone)]
+    enum Msg {
+        Add(u32),
+        Reset,
+    }
+
+    struct Counter {
+        total: u32,
+        history: Vec<u32>,
+    }
+
+    impl Component for Counter {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::html::MethodsForHtmlElementContent;
+            element.rupdate(self.total);
+        }
+    }
+
+    impl super::MessageComponent for Counter {
+        type Message = Msg;
+        fn update(&mut self, message: Msg) -> super::Checklist<Self> {
+            match message {
+                Msg::Add(value) => self.total += value,
+                Msg::Reset => self.total = 0,
+            }
+            self.history.push(self.total);
+            super::Checklist::should_render()
+        }
+    }
+
+    impl crate::application::Application for Counter {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                total: 0,
+                history: Vec::new(),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn message_component() {
+        let (root, rc) = mount::<Counter>();
+        let text = || root.ws_element().as_ref().text_content();
+        assert_eq!(Some("0".to_string()), text());
+
+        rc.comp().emit(Msg::Add(2));
+        rc.comp().message_callback_arg(Msg::Add).call(3);
+        assert_eq!(Some("5".to_string()), text());
+
+        rc.comp().message_callback(Msg::Reset).call();
+        assert_eq!(Some("0".to_string()), text());
+        assert_eq!(vec![2, 5, 0], rc.comp_instance().state().history);
+    }
+
+    struct Card {
+        count: u32,
+        show: bool,
+    }
+
+    impl Component for Card {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::prelude::*;
+            let comp = element.comp();
+            element
+                .div(|d| {
+                    d.set_attribute_str("data-name", "card")
+                        .on_click(comp.handler_mut(|state: &mut Card| {
+                            state.count += 1;
+                            state.show = !state.show;
+                        }))
+                        .rupdate(self.count);
+                })
+                .match_if(|mi| match self.show {
+                    true => mi.render_on_arm_index(0).rstatic("shown").done(),
+                    false => mi.render_on_arm_index(1).done(),
+                });
+        }
+    }
+
+    impl crate::application::Application for Card {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                count: 1,
+                show: true,
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn debug_tree() {
+        use wasm_bindgen::JsCast;
+        let (root, rc) = mount::<Card>();
+        assert_eq!(
+            "<div data-name=\"card\" EventListener(...)>\n  \"1\"\ngroup arm=0\n  \"shown\"\n",
+            rc.comp_instance().debug_tree()
+        );
+
+        root.ws_element()
+            .as_ref()
+            .query_selector("div")
+            .expect_throw("component_tests::debug_tree query")
+            .expect_throw("component_tests::debug_tree no div")
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+        assert_eq!(
+            "<div data-name=\"card\" EventListener(...)>\n  \"2\"\ngroup arm=1\n",
+            rc.comp_instance().debug_tree()
+        );
+    }
+}
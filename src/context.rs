@@ -0,0 +1,119 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+thread_local! {
+    static CONTEXTS: RefCell<HashMap<TypeId, Rc<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// Provide a value that is accessible by all components via [`use_context`], such
+/// as the theme or the current user. There is only one value per type, providing a
+/// value of the same type again replaces the previous one. This is usually done in
+/// `Application::init`.
+pub fn provide_context<T: 'static>(value: T) {
+    CONTEXTS.with(|contexts| {
+        contexts
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Rc::new(value));
+    });
+}
+
+/// Get the value provided by [`provide_context`]. Returns `None` if there is no
+/// value of type `T`.
+pub fn use_context<T: 'static>() -> Option<Rc<T>> {
+    CONTEXTS.with(|contexts| {
+        contexts
+            .borrow()
+            .get(&TypeId::of::<T>())
+            .cloned()
+            .and_then(|value| value.downcast::<T>().ok())
+    })
+}
+
+#[cfg(test)]
+mod context_tests {
+    use wasm_bindgen_test::*;
+
+    use crate::component::{AsChildComp, ChildComp, Comp, Component};
+    use crate::render::html::{HemsHandMade, HtmlTag, MethodsForHtmlElementContent};
+
+    struct Theme(&'static str);
+
+    struct Root {
+        child: ChildComp<Middle>,
+    }
+
+    impl Component for Root {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.component_ref(&self.child);
+        }
+    }
+
+    impl crate::application::Application for Root {
+        fn init(_: &Comp<Self>) -> Self {
+            super::provide_context(Theme("dark"));
+            Self {
+                child: Middle::with_props(()),
+            }
+        }
+    }
+
+    struct Middle {
+        child: ChildComp<Leaf>,
+    }
+
+    impl Component for Middle {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.component_ref(&self.child);
+        }
+    }
+
+    impl AsChildComp for Middle {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("div"));
+        type Properties = ();
+        fn init(_: &Comp<Self>, _: Self::Properties) -> Self {
+            Self {
+                child: Leaf::with_props(()),
+            }
+        }
+    }
+
+    struct Leaf;
+
+    impl Component for Leaf {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            let theme = super::use_context::<Theme>();
+            element.rupdate(theme.map(|theme| theme.0).unwrap_or("no theme"));
+        }
+    }
+
+    impl AsChildComp for Leaf {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(HtmlTag("span"));
+        type Properties = ();
+        fn init(_: &Comp<Self>, _: Self::Properties) -> Self {
+            Self
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn nested_child_reads_context() {
+        let (root, _rc) = crate::test_utils::mount::<Root>();
+        assert_eq!(
+            Some("dark".to_string()),
+            root.ws_element().as_ref().text_content()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn replace_context() {
+        struct Count(u32);
+        assert!(super::use_context::<Count>().is_none());
+        super::provide_context(Count(1));
+        super::provide_context(Count(2));
+        assert_eq!(2, super::use_context::<Count>().map(|c| c.0).unwrap());
+    }
+}
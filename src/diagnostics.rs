@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub use log::Level;
+
+type Handler = Rc<dyn Fn(Level, &str)>;
+
+thread_local! {
+    static HANDLER: RefCell<Option<Handler>> = RefCell::new(None);
+}
+
+/// Route the warnings and errors of Spair (such as `.checked()` on an element that
+/// is not an `<input>`) to `handler`, instead of logging them with the `log` crate.
+/// For example, panic in debug builds or send them to telemetry in production. The
+/// handler replaces the previously set one.
+pub fn set_diagnostic_handler(handler: impl Fn(Level, &str) + 'static) {
+    HANDLER.with(|h| *h.borrow_mut() = Some(Rc::new(handler)));
+}
+
+/// Remove the handler set by [`set_diagnostic_handler`], the warnings and errors are
+/// logged with the `log` crate again.
+pub fn remove_diagnostic_handler() {
+    HANDLER.with(|h| *h.borrow_mut() = None);
+}
+
+pub(crate) fn warn(args: std::fmt::Arguments) {
+    report(Level::Warn, args);
+}
+
+#[cfg(any(feature = "queue-render", feature = "debug-trace"))]
+pub(crate) fn error(args: std::fmt::Arguments) {
+    report(Level::Error, args);
+}
+
+fn report(level: Level, args: std::fmt::Arguments) {
+    // Clone the handler out of the cell, it may set another handler.
+    match HANDLER.with(|h| h.borrow().clone()) {
+        Some(handler) => handler(level, &args.to_string()),
+        None => log::log!(level, "{}", args),
+    }
+}
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::render::html::{HamsHandMade, HemsForDistinctNames};
+
+    struct State;
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.div(|d| {
+                d.checked(true);
+            });
+        }
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn checked_on_a_div() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = received.clone();
+        super::set_diagnostic_handler(move |level, message| {
+            received_clone
+                .borrow_mut()
+                .push((level, message.to_string()))
+        });
+
+        let (_root, _rc) = crate::test_utils::mount::<State>();
+        super::remove_diagnostic_handler();
+
+        assert_eq!(
+            vec![(
+                super::Level::Warn,
+                ".checked() is called on an element that is not an <input>".to_string()
+            )],
+            *received.borrow()
+        );
+    }
+}
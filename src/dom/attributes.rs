@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use super::WsElement;
+
 enum AttributeValue {
     EventListener(Option<Box<dyn crate::events::Listener>>),
     String(String),
@@ -6,6 +11,7 @@ enum AttributeValue {
     I32(i32),
     U32(u32),
     F64(f64),
+    JsValue(wasm_bindgen::JsValue),
 }
 
 impl Clone for AttributeValue {
@@ -18,6 +24,7 @@ impl Clone for AttributeValue {
             Self::I32(v) => Self::I32(*v),
             Self::U32(v) => Self::U32(*v),
             Self::F64(v) => Self::F64(*v),
+            Self::JsValue(v) => Self::JsValue(v.clone()),
         }
     }
 }
@@ -32,14 +39,268 @@ impl std::fmt::Debug for AttributeValue {
             Self::I32(value) => value.fmt(f),
             Self::U32(value) => value.fmt(f),
             Self::F64(value) => value.fmt(f),
+            Self::JsValue(value) => value.fmt(f),
+        }
+    }
+}
+
+// The attribute at an index may change its type if the attribute calls do not
+// always happen in the same order between renders. Just store the new value.
+fn replace_mismatched_value(old: &mut AttributeValue, new: AttributeValue, expected: &str) {
+    log::debug!(
+        "Spair: expected an AttributeValue::{} but found {:?}, it will be replaced by {:?}",
+        expected,
+        old,
+        new
+    );
+    *old = new;
+}
+
+// NaN is considered equal to NaN (it is the same value for an attribute), and the
+// epsilon is scaled by the magnitude of the values.
+fn f64_eq(a: f64, b: f64) -> bool {
+    if a.is_nan() || b.is_nan() {
+        return a.is_nan() && b.is_nan();
+    }
+    if a == b {
+        return true;
+    }
+    (a - b).abs() <= f64::EPSILON * a.abs().max(b.abs()).max(1.0)
+}
+
+/// The name of an attribute or a property on a slot. Most attribute methods have their
+/// names as literals, only a name that is given at runtime (such as the name of
+/// `set_attribute_str`) is copied into the slot.
+#[derive(Debug, Clone, Copy)]
+pub enum SlotName<'a> {
+    Static(&'static str),
+    Runtime(&'a str),
+}
+
+impl SlotName<'_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Static(name) => name,
+            Self::Runtime(name) => name,
         }
     }
+
+    fn to_stored(self) -> Cow<'static, str> {
+        match self {
+            Self::Static(name) => Cow::Borrowed(name),
+            Self::Runtime(name) => Cow::Owned(name.to_string()),
+        }
+    }
+}
+
+/// What a slot is used for by the render. It tells what must be undone on the element
+/// when the slot is not set by a render (the call of the method is omitted).
+#[derive(Debug, Clone, Copy)]
+pub enum Slot<'a> {
+    /// An event listener (it is removed with the slot), or an action such as `focus`
+    /// that has nothing to undo.
+    Plain,
+    /// The attribute is removed.
+    Attribute(SlotName<'a>),
+    /// The classes in the value of the slot (a `String` of class names) are removed.
+    Classes,
+    /// The property is set to `null` (an empty `value`, an unchecked `checked`).
+    Property(SlotName<'a>),
+}
+
+#[derive(Debug, Clone)]
+enum SlotUndo {
+    Nothing,
+    RemoveAttribute(Cow<'static, str>),
+    RemoveClasses,
+    UnsetProperty(Cow<'static, str>),
+}
+
+impl SlotUndo {
+    fn is(&self, slot: &Slot) -> bool {
+        match (self, slot) {
+            (Self::Nothing, Slot::Plain) | (Self::RemoveClasses, Slot::Classes) => true,
+            (Self::RemoveAttribute(name), Slot::Attribute(new_name))
+            | (Self::UnsetProperty(name), Slot::Property(new_name)) => name == new_name.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl From<Slot<'_>> for SlotUndo {
+    fn from(slot: Slot) -> Self {
+        match slot {
+            Slot::Plain => Self::Nothing,
+            Slot::Attribute(name) => Self::RemoveAttribute(name.to_stored()),
+            Slot::Classes => Self::RemoveClasses,
+            Slot::Property(name) => Self::UnsetProperty(name.to_stored()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SlotState {
+    undo: SlotUndo,
+    seen: bool,
+}
+
+// An undo of a slot that is not set (or set to something else) by the current render.
+// It is done at the end of the render, unless another slot sets the same attribute,
+// class or property.
+#[derive(Debug, Clone)]
+enum PendingUndo {
+    RemoveAttribute(Cow<'static, str>),
+    RemoveClasses(String),
+    UnsetProperty(Cow<'static, str>),
+}
+
+#[derive(Default, Debug, Clone)]
+struct Slots {
+    states: Vec<SlotState>,
+    pending: Vec<PendingUndo>,
+}
+
+impl Slots {
+    fn queue_undo(&mut self, undo: SlotUndo, value: Option<&AttributeValue>) {
+        let pending = match undo {
+            SlotUndo::Nothing => return,
+            SlotUndo::RemoveAttribute(name) => PendingUndo::RemoveAttribute(name),
+            SlotUndo::RemoveClasses => match value {
+                Some(AttributeValue::String(class_names)) if !class_names.is_empty() => {
+                    PendingUndo::RemoveClasses(class_names.clone())
+                }
+                _ => return,
+            },
+            SlotUndo::UnsetProperty(name) => PendingUndo::UnsetProperty(name),
+        };
+        self.pending.push(pending);
+    }
 }
 
+/// Only values of attributes that are set in update mode are stored here (and
+/// event listeners, which must be kept alive). Attributes that are set in static
+/// mode are never checked again, hence they do not occupy a slot in the list.
 #[derive(Default, Debug, Clone)]
-pub struct AttributeValueList(Vec<AttributeValue>);
+pub struct AttributeValueList(Vec<AttributeValue>, Slots);
 
 impl AttributeValueList {
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[cfg(test)]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Set what the slot at `index` is used for, and mark it as seen by the current
+    /// render. Returns `true` if the slot was used for something else in the last
+    /// render, so the value must be set even if it does not change.
+    pub fn set_slot(&mut self, index: usize, slot: Slot) -> bool {
+        let slots = &mut self.1;
+        let state = match slots.states.get_mut(index) {
+            Some(state) => state,
+            None => {
+                slots.states.push(SlotState {
+                    undo: slot.into(),
+                    seen: true,
+                });
+                return !matches!(slot, Slot::Plain);
+            }
+        };
+        state.seen = true;
+        if state.undo.is(&slot) {
+            return false;
+        }
+        let old_undo = std::mem::replace(&mut state.undo, slot.into());
+        slots.queue_undo(old_undo, self.0.get(index));
+        true
+    }
+
+    /// Mark the slot at `index` as seen by the current render without changing it (a
+    /// static event listener of an existing element is not set again).
+    pub fn mark_seen(&mut self, index: usize) {
+        if let Some(state) = self.1.states.get_mut(index) {
+            state.seen = true;
+        }
+    }
+
+    /// Called at the end of a render of the element, `len` is the number of slots that
+    /// are used by the render. Slots that are not seen by the render are dropped (with
+    /// their event listeners), and what they set on `ws_element` is undone.
+    pub fn clear_unset_slots(&mut self, len: usize, ws_element: &WsElement) {
+        let Self(values, slots) = self;
+        for index in 0..slots.states.len() {
+            let state = &mut slots.states[index];
+            if !std::mem::replace(&mut state.seen, false) {
+                let undo = std::mem::replace(&mut state.undo, SlotUndo::Nothing);
+                slots.queue_undo(undo, values.get(index));
+            }
+        }
+        slots.states.truncate(len);
+        values.truncate(len);
+        if slots.pending.is_empty() {
+            return;
+        }
+
+        let mut attributes = HashSet::new();
+        let mut properties = HashSet::new();
+        let mut classes = HashSet::new();
+        for (state, value) in slots.states.iter().zip(values.iter()) {
+            match (&state.undo, value) {
+                (SlotUndo::RemoveAttribute(name), _) => {
+                    attributes.insert(name.as_ref());
+                }
+                (SlotUndo::UnsetProperty(name), _) => {
+                    properties.insert(name.as_ref());
+                }
+                (SlotUndo::RemoveClasses, AttributeValue::String(class_names)) => {
+                    classes.extend(class_names.split_whitespace());
+                }
+                _ => {}
+            }
+        }
+        for pending in slots.pending.drain(..) {
+            match pending {
+                PendingUndo::RemoveAttribute(name) => {
+                    if !attributes.contains(name.as_ref()) {
+                        ws_element.remove_attribute(&name);
+                    }
+                }
+                PendingUndo::RemoveClasses(class_names) => {
+                    let stale: Vec<&str> = class_names
+                        .split_whitespace()
+                        .filter(|name| !classes.contains(name))
+                        .collect();
+                    if !stale.is_empty() {
+                        ws_element.remove_classes(&stale);
+                    }
+                }
+                PendingUndo::UnsetProperty(name) => {
+                    if !properties.contains(name.as_ref()) {
+                        ws_element.set_property(&name, &wasm_bindgen::JsValue::NULL);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The values of the slots for `debug_tree`, in the form of ` name=value` (or
+    /// ` value` if the slot is not an attribute).
+    pub fn debug_string(&self) -> String {
+        use std::fmt::Write;
+        let mut s = String::new();
+        for (index, value) in self.0.iter().enumerate() {
+            // Writing to a `String` never fails.
+            let _ = match self.1.states.get(index).map(|state| &state.undo) {
+                Some(SlotUndo::RemoveAttribute(name)) => write!(s, " {}={:?}", name, value),
+                _ => write!(s, " {:?}", value),
+            };
+        }
+        s
+    }
+
     pub fn store_listener(&mut self, index: usize, listener: Box<dyn crate::events::Listener>) {
         if index < self.0.len() {
             self.0[index] = AttributeValue::EventListener(Some(listener));
@@ -60,7 +321,10 @@ impl AttributeValueList {
                     *old_value = value;
                     true
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::Bool?"),
+                a => {
+                    replace_mismatched_value(a, AttributeValue::Bool(value), "Bool");
+                    true
+                }
             },
         }
     }
@@ -77,7 +341,10 @@ impl AttributeValueList {
                     *old_value = value;
                     true
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::I32?"),
+                a => {
+                    replace_mismatched_value(a, AttributeValue::I32(value), "I32");
+                    true
+                }
             },
         }
     }
@@ -94,7 +361,10 @@ impl AttributeValueList {
                     *old_value = value;
                     true
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::U32?"),
+                a => {
+                    replace_mismatched_value(a, AttributeValue::U32(value), "U32");
+                    true
+                }
             },
         }
     }
@@ -106,16 +376,36 @@ impl AttributeValueList {
                 true
             }
             Some(a) => match a {
-                AttributeValue::F64(old_value)
-                    if (value - *old_value).abs() < std::f64::EPSILON =>
-                {
-                    false
-                }
+                AttributeValue::F64(old_value) if f64_eq(value, *old_value) => false,
                 AttributeValue::F64(old_value) => {
                     *old_value = value;
                     true
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::F64?"),
+                a => {
+                    replace_mismatched_value(a, AttributeValue::F64(value), "F64");
+                    true
+                }
+            },
+        }
+    }
+
+    /// Values are compared with `Object.is`, so a `NaN` is equal to another `NaN`.
+    pub fn check_js_value(&mut self, index: usize, value: &wasm_bindgen::JsValue) -> bool {
+        match self.0.get_mut(index) {
+            None => {
+                self.0.push(AttributeValue::JsValue(value.clone()));
+                true
+            }
+            Some(a) => match a {
+                AttributeValue::JsValue(old_value) if js_sys::Object::is(value, old_value) => false,
+                AttributeValue::JsValue(old_value) => {
+                    *old_value = value.clone();
+                    true
+                }
+                a => {
+                    replace_mismatched_value(a, AttributeValue::JsValue(value.clone()), "JsValue");
+                    true
+                }
             },
         }
     }
@@ -129,10 +419,19 @@ impl AttributeValueList {
             Some(a) => match a {
                 AttributeValue::String(old_value) if value == *old_value => false,
                 AttributeValue::String(old_value) => {
-                    *old_value = value.to_string();
+                    // Reuse the buffer of the old value
+                    old_value.clear();
+                    old_value.push_str(value);
+                    true
+                }
+                a => {
+                    replace_mismatched_value(
+                        a,
+                        AttributeValue::String(value.to_string()),
+                        "String",
+                    );
                     true
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::String?"),
             },
         }
     }
@@ -148,10 +447,23 @@ impl AttributeValueList {
             Some(a) => match a {
                 AttributeValue::SelectedValue(old_value) if value == old_value.as_deref() => false,
                 AttributeValue::SelectedValue(old_value) => {
-                    *old_value = value.map(ToString::to_string);
+                    match (old_value, value) {
+                        (Some(old_value), Some(value)) => {
+                            old_value.clear();
+                            old_value.push_str(value);
+                        }
+                        (old_value, value) => *old_value = value.map(ToString::to_string),
+                    }
+                    true
+                }
+                a => {
+                    replace_mismatched_value(
+                        a,
+                        AttributeValue::SelectedValue(value.map(ToString::to_string)),
+                        "SelectedValue",
+                    );
                     true
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::SelectedValue?"),
             },
         }
     }
@@ -173,8 +485,214 @@ impl AttributeValueList {
                     std::mem::swap(&mut value, old_value);
                     (true, Some(value))
                 }
-                _ => panic!("Spair's internal error, expected an AttributeValue::String"),
+                a => {
+                    replace_mismatched_value(
+                        a,
+                        AttributeValue::String(value.to_string()),
+                        "String",
+                    );
+                    (true, None)
+                }
             },
         }
     }
+
+    /// Same as `check_str_attribute_and_return_old_value` with `class_names.join(" ")`,
+    /// but the names are only joined if they are different from the stored value.
+    pub fn check_class_names_and_return_old_value(
+        &mut self,
+        index: usize,
+        class_names: &[&str],
+    ) -> (bool, Option<String>) {
+        match self.0.get_mut(index) {
+            Some(AttributeValue::String(old_value))
+                if is_joined_by_space(old_value, class_names) =>
+            {
+                (false, None)
+            }
+            _ => self.check_str_attribute_and_return_old_value(index, &class_names.join(" ")),
+        }
+    }
+}
+
+// Whether `value == names.join(" ")`, without joining.
+fn is_joined_by_space(value: &str, names: &[&str]) -> bool {
+    let mut rest = value;
+    for (index, name) in names.iter().enumerate() {
+        if index > 0 {
+            match rest.strip_prefix(' ') {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        }
+        match rest.strip_prefix(name) {
+            Some(r) => rest = r,
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod attribute_value_list_tests {
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn attribute_type_changes_between_renders() {
+        let mut list = super::AttributeValueList::default();
+        assert!(list.check_bool_attribute(0, true));
+        assert!(list.check_str_attribute(1, "first"));
+
+        // The order of attributes changes: index 0 is now a string, index 1 is a bool.
+        assert!(list.check_str_attribute(0, "first"));
+        assert!(list.check_bool_attribute(1, true));
+        assert!(!list.check_str_attribute(0, "first"));
+        assert!(!list.check_bool_attribute(1, true));
+
+        assert!(list.check_f64_attribute(0, 1.5));
+        assert!(list.check_optional_str_attribute(1, Some("value")));
+        assert_eq!(
+            (true, None),
+            list.check_str_attribute_and_return_old_value(0, "class")
+        );
+        assert!(list.check_i32_attribute(1, 5));
+        assert!(list.check_u32_attribute(0, 5));
+        assert!(!list.check_u32_attribute(0, 5));
+        assert!(!list.check_i32_attribute(1, 5));
+    }
+
+    #[wasm_bindgen_test]
+    fn class_names_attribute() {
+        let mut list = super::AttributeValueList::default();
+        assert_eq!(
+            (true, None),
+            list.check_class_names_and_return_old_value(0, &["a", "b"])
+        );
+        assert_eq!(
+            (false, None),
+            list.check_class_names_and_return_old_value(0, &["a", "b"])
+        );
+        assert_eq!(
+            (false, None),
+            list.check_str_attribute_and_return_old_value(0, "a b")
+        );
+        assert_eq!(
+            (true, Some("a b".to_string())),
+            list.check_class_names_and_return_old_value(0, &["a", "bc"])
+        );
+        assert_eq!(
+            (true, Some("a bc".to_string())),
+            list.check_class_names_and_return_old_value(0, &["a"])
+        );
+        assert_eq!(
+            (true, Some("a".to_string())),
+            list.check_class_names_and_return_old_value(0, &[])
+        );
+        assert_eq!(
+            (false, None),
+            list.check_class_names_and_return_old_value(0, &[])
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn f64_attribute_with_large_values() {
+        let mut list = super::AttributeValueList::default();
+        assert!(list.check_f64_attribute(0, 1.0e10));
+        assert!(!list.check_f64_attribute(0, 1.0e10 + 1.0e-6));
+        assert!(list.check_f64_attribute(0, 1.0e10 + 1.0));
+        assert!(!list.check_f64_attribute(0, 1.0e10 + 1.0));
+        assert!(list.check_f64_attribute(0, 0.5));
+        assert!(!list.check_f64_attribute(0, 0.5));
+    }
+
+    #[wasm_bindgen_test]
+    fn f64_attribute_with_nan() {
+        let mut list = super::AttributeValueList::default();
+        assert!(list.check_f64_attribute(0, f64::NAN));
+        assert!(!list.check_f64_attribute(0, f64::NAN));
+        assert!(list.check_f64_attribute(0, 1.0));
+        assert!(list.check_f64_attribute(0, f64::NAN));
+        assert!(list.check_f64_attribute(0, f64::INFINITY));
+        assert!(!list.check_f64_attribute(0, f64::INFINITY));
+    }
+
+    #[wasm_bindgen_test]
+    fn only_dynamic_attributes_are_stored() {
+        use crate::render::html::{HamsForDistinctNames, HamsHandMade};
+
+        let mut te = crate::test_utils::TestElement::new("div");
+        for (status, title) in [
+            (crate::dom::ElementStatus::JustCreated, "first"),
+            (crate::dom::ElementStatus::Existing, "second"),
+        ] {
+            te.updater(status)
+                .title(title)
+                .col_span(2)
+                .static_attributes()
+                .id("static-id")
+                .class("static-class")
+                .href_str("/static")
+                .done();
+            assert_eq!(2, te.element.attribute_list_mut().len());
+            assert_eq!(Some(title), te.attribute("title").as_deref());
+            assert_eq!(Some("static-id"), te.attribute("id").as_deref());
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn js_value_attribute() {
+        use wasm_bindgen::JsValue;
+        let mut list = super::AttributeValueList::default();
+        assert!(list.check_js_value(0, &JsValue::from_f64(0.5)));
+        assert!(!list.check_js_value(0, &JsValue::from_f64(0.5)));
+        assert!(list.check_js_value(0, &JsValue::from_str("0.5")));
+        assert!(list.check_js_value(0, &JsValue::from_f64(f64::NAN)));
+        assert!(!list.check_js_value(0, &JsValue::from_f64(f64::NAN)));
+    }
+
+    fn eight_attributes<T: crate::render::html::HamsHandMade<crate::test_utils::Unit>>(
+        attributes: T,
+    ) -> T {
+        attributes
+            .id("id")
+            .class("class")
+            .title("title")
+            .href_str("/href")
+            .alt("alt")
+            .name("name")
+            .placeholder("placeholder")
+            .col_span(2)
+    }
+
+    // Measure the storage of an element with 8 attributes, all of them are dynamic
+    // (the storage before static attributes were skipped) vs all of them are static.
+    #[wasm_bindgen_test]
+    fn storage_of_a_static_heavy_element() {
+        use crate::dom::ElementStatus;
+        use crate::render::html::HamsHandMade;
+        use crate::test_utils::TestElement;
+
+        let mut dynamic_element = TestElement::new("div");
+        eight_attributes(dynamic_element.updater(ElementStatus::JustCreated)).done();
+        let dynamic_list = dynamic_element.element.attribute_list_mut();
+        assert_eq!(8, dynamic_list.len());
+        assert!(dynamic_list.capacity() >= 8);
+
+        let mut static_element = TestElement::new("div");
+        eight_attributes(
+            static_element
+                .updater(ElementStatus::JustCreated)
+                .static_attributes(),
+        )
+        .done();
+        let static_list = static_element.element.attribute_list_mut();
+        assert_eq!(0, static_list.len());
+        // Nothing is allocated for the list
+        assert_eq!(0, static_list.capacity());
+
+        assert_eq!(
+            dynamic_element.ws_element().outer_html(),
+            static_element.ws_element().outer_html()
+        );
+    }
 }
@@ -9,6 +9,8 @@ pub struct Element {
     ws_element: WsElement,
     attributes: AttributeValueList,
     nodes: Nodes,
+    // If this is Some, `nodes` are rendered into it instead of `ws_element`
+    shadow_root: Option<web_sys::ShadowRoot>,
     #[cfg(feature = "queue-render")]
     unmounted: Rc<Cell<bool>>,
 }
@@ -24,11 +26,19 @@ impl Clone for Element {
     fn clone(&self) -> Self {
         let ws_element = self.ws_element.shadow_clone();
         let nodes = self.nodes.clone();
-        nodes.append_to(ws_element.ws_node());
+        let shadow_root = self
+            .shadow_root
+            .as_ref()
+            .map(|_| ws_element.attach_shadow_root());
+        match shadow_root.as_ref() {
+            Some(shadow_root) => nodes.append_to(shadow_root.as_ref()),
+            None => nodes.append_to(ws_element.ws_node()),
+        }
 
         Self {
             ws_element,
             nodes,
+            shadow_root,
             attributes: self.attributes.clone(),
             #[cfg(feature = "queue-render")]
             unmounted: Rc::new(Cell::new(false)),
@@ -48,6 +58,7 @@ impl Element {
             ws_element: WsElement::new(E::NAMESPACE, tag.tag_name()),
             attributes: Default::default(),
             nodes: Default::default(),
+            shadow_root: None,
             #[cfg(feature = "queue-render")]
             unmounted: Rc::new(Cell::new(false)),
         }
@@ -61,11 +72,23 @@ impl Element {
             },
             attributes: Default::default(),
             nodes: Default::default(),
+            shadow_root: None,
             #[cfg(feature = "queue-render")]
             unmounted: Rc::new(Cell::new(false)),
         }
     }
 
+    /// Create an element by deeply cloning the given `template`. Only the `web_sys`
+    /// content is cloned, spair does not know about the attributes and children of
+    /// the template.
+    pub fn from_template(template: &web_sys::Element) -> Self {
+        let ws_element = template
+            .clone_node_with_deep(true)
+            .expect_throw("dom::element::Element::from_template clone_node_with_deep")
+            .unchecked_into();
+        Self::from_ws_element(ws_element)
+    }
+
     #[cfg(feature = "queue-render")]
     pub fn unmounted(&self) -> Rc<Cell<bool>> {
         self.unmounted.clone()
@@ -85,8 +108,23 @@ impl Element {
         &self.ws_element
     }
 
+    /// Attach an open shadow root to the element, then its content will be rendered
+    /// into the shadow root. This must be called before rendering any content.
+    pub fn attach_shadow_root(&mut self) {
+        if self.shadow_root.is_none() {
+            self.shadow_root = Some(self.ws_element.attach_shadow_root());
+        }
+    }
+
+    pub fn shadow_root(&self) -> Option<&web_sys::ShadowRoot> {
+        self.shadow_root.as_ref()
+    }
+
     pub fn ws_node_and_nodes_mut(&mut self) -> (&web_sys::Node, &mut Nodes) {
-        (self.ws_element.as_ref(), &mut self.nodes)
+        match self.shadow_root.as_ref() {
+            Some(shadow_root) => (shadow_root.as_ref(), &mut self.nodes),
+            None => (self.ws_element.as_ref(), &mut self.nodes),
+        }
     }
 
     pub fn ws_html_element(&self) -> &web_sys::HtmlElement {
@@ -101,7 +139,26 @@ impl Element {
         &mut self.attributes
     }
 
-    #[cfg(test)]
+    /// Undo attributes, classes and properties that were set by the last render but not
+    /// by the current one, `len` is the number of slots that are used by the current
+    /// render.
+    pub fn clear_unset_slots(&mut self, len: usize) {
+        self.attributes.clear_unset_slots(len, &self.ws_element);
+    }
+
+    pub fn write_debug_tree(&self, out: &mut String, depth: usize) {
+        super::write_debug_line(
+            out,
+            depth,
+            format_args!(
+                "<{}{}>",
+                self.ws_element.ws_element.local_name(),
+                self.attributes.debug_string()
+            ),
+        );
+        self.nodes.write_debug_tree(out, depth + 1);
+    }
+
     pub fn nodes(&self) -> &Nodes {
         &self.nodes
     }
@@ -197,6 +254,12 @@ impl WsElement {
         self.ws_element.set_text_content(text);
     }
 
+    fn attach_shadow_root(&self) -> web_sys::ShadowRoot {
+        self.ws_element
+            .attach_shadow(&web_sys::ShadowRootInit::new(web_sys::ShadowRootMode::Open))
+            .expect_throw("dom::element::WsElement::attach_shadow_root")
+    }
+
     pub fn set_str_attribute(&self, attribute_name: &str, attribute_value: &str) {
         self.ws_element
             .set_attribute(attribute_name, attribute_value)
@@ -217,6 +280,11 @@ impl WsElement {
         self.set_str_attribute(attribute_name, &attribute_value.to_string());
     }
 
+    pub fn set_property(&self, name: &str, value: &wasm_bindgen::JsValue) {
+        js_sys::Reflect::set(self.ws_element.as_ref(), &name.into(), value)
+            .expect_throw("dom::element::WsElement::set_property");
+    }
+
     pub fn set_bool_attribute(&self, name: &str, value: bool) {
         if value {
             self.set_str_attribute(name, "");
@@ -239,6 +307,28 @@ impl WsElement {
             .expect_throw("dom::element::WsElement::remove_class");
     }
 
+    pub fn add_classes(&self, class_names: &[&str]) {
+        let class_names: js_sys::Array = class_names
+            .iter()
+            .map(|name| wasm_bindgen::JsValue::from_str(name))
+            .collect();
+        self.ws_element
+            .class_list()
+            .add(&class_names)
+            .expect_throw("dom::element::WsElement::add_classes");
+    }
+
+    pub fn remove_classes(&self, class_names: &[&str]) {
+        let class_names: js_sys::Array = class_names
+            .iter()
+            .map(|name| wasm_bindgen::JsValue::from_str(name))
+            .collect();
+        self.ws_element
+            .class_list()
+            .remove(&class_names)
+            .expect_throw("dom::element::WsElement::remove_classes");
+    }
+
     pub fn add_class_optional(&self, class_name: Option<&str>) {
         if let Some(class_name) = class_name {
             self.add_class(class_name);
@@ -261,6 +351,9 @@ impl WsElement {
             ElementType::Input => {
                 let input = self.ws_element.unchecked_ref::<web_sys::HtmlInputElement>();
                 input.set_value(value);
+                // Also set the `value` content attribute, so that a form reset
+                // reverts the input to this value.
+                input.set_default_value(value);
             }
             ElementType::Select => {
                 if queue_render {
@@ -284,9 +377,9 @@ impl WsElement {
                 option.set_value(value);
             }
             ElementType::Other => {
-                log::warn!(
+                crate::diagnostics::warn(format_args!(
                     ".value() is called on an element that is not <input>, <select>, <option>, <textarea>"
-                );
+                ));
             }
         }
         false
@@ -315,7 +408,9 @@ impl WsElement {
                 select.set_selected_index(index);
             }
             _ => {
-                log::warn!(".set_selected_index() is called on an element that is not a <select>");
+                crate::diagnostics::warn(format_args!(
+                    ".set_selected_index() is called on an element that is not a <select>"
+                ));
             }
         }
     }
@@ -339,9 +434,27 @@ impl WsElement {
         if self.element_type == ElementType::Input {
             let input = self.ws_element.unchecked_ref::<web_sys::HtmlInputElement>();
             input.set_checked(value);
+            // Also set the `checked` content attribute, so that a form reset
+            // reverts the input to this state.
+            input.set_default_checked(value);
         } else {
-            log::warn!(".checked() is called on an element that is not an <input>");
+            crate::diagnostics::warn(format_args!(
+                ".checked() is called on an element that is not an <input>"
+            ));
+        }
+    }
+
+    pub fn set_value_as_number(&self, value: f64) {
+        if self.element_type == ElementType::Input {
+            let input = self.ws_element.unchecked_ref::<web_sys::HtmlInputElement>();
+            if matches!(input.type_().as_str(), "number" | "range") {
+                input.set_value_as_number(value);
+                return;
+            }
         }
+        crate::diagnostics::warn(format_args!(
+            ".value_number() is called on an element that is not an <input type=\"number\"> or <input type=\"range\">"
+        ));
     }
 
     pub fn enabled_ref(&self, value: &bool) {
@@ -364,6 +477,22 @@ impl WsElement {
         }
     }
 
+    pub fn select_text(&self) {
+        match self.element_type {
+            ElementType::Input => self
+                .ws_element
+                .unchecked_ref::<web_sys::HtmlInputElement>()
+                .select(),
+            ElementType::TextArea => self
+                .ws_element
+                .unchecked_ref::<web_sys::HtmlTextAreaElement>()
+                .select(),
+            _ => crate::diagnostics::warn(format_args!(
+                ".select_text() is called on an element that is not <input>, <textarea>"
+            )),
+        }
+    }
+
     pub fn scroll_to_view_with_bool(&self, align_to_top: bool) {
         self.ws_element.scroll_into_view_with_bool(align_to_top);
     }
@@ -373,3 +502,61 @@ impl WsElement {
             .scroll_into_view_with_scroll_into_view_options(options);
     }
 }
+
+#[cfg(test)]
+mod ws_element_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::dom::{AChildNode, Element};
+    use crate::render::html::HtmlTag;
+
+    fn form_with_input() -> (Element, Element) {
+        let form = Element::new_ns(HtmlTag("form"));
+        let input = Element::new_ns(HtmlTag("input"));
+        form.ws_node()
+            .append_child(input.ws_node())
+            .expect_throw("Append the input to the form");
+        (form, input)
+    }
+
+    fn reset(form: &Element) {
+        form.ws_element()
+            .unchecked_ref::<web_sys::HtmlFormElement>()
+            .reset();
+    }
+
+    #[wasm_bindgen_test]
+    fn checked_survives_form_reset() {
+        let (form, input) = form_with_input();
+        let ws_input = input
+            .ws_element()
+            .unchecked_ref::<web_sys::HtmlInputElement>();
+        ws_input.set_type("checkbox");
+
+        input.ws_element().checked(true);
+        // The user unchecks the checkbox
+        ws_input.set_checked(false);
+        reset(&form);
+        assert!(ws_input.checked());
+
+        input.ws_element().checked(false);
+        ws_input.set_checked(true);
+        reset(&form);
+        assert!(!ws_input.checked());
+    }
+
+    #[wasm_bindgen_test]
+    fn value_survives_form_reset() {
+        let (form, input) = form_with_input();
+        let ws_input = input
+            .ws_element()
+            .unchecked_ref::<web_sys::HtmlInputElement>();
+
+        let _ = input.ws_element().set_value("spair", false);
+        // The user edits the input
+        ws_input.set_value("something else");
+        reset(&form);
+        assert_eq!("spair", ws_input.value());
+    }
+}
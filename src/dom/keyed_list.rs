@@ -1,5 +1,6 @@
-use super::{AChildNode, Element};
+use super::{AChildNode, Element, ElementStatus};
 use std::collections::HashMap;
+#[cfg(feature = "keyed-list-uuid")]
 use uuid::Uuid;
 use wasm_bindgen::UnwrapThrowExt;
 
@@ -21,7 +22,7 @@ where
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub enum ListItemKey {
     String(String),
     ISize(isize),
@@ -30,7 +31,19 @@ pub enum ListItemKey {
     U64(u64),
     I32(i32),
     U32(u32),
+    #[cfg(feature = "keyed-list-uuid")]
     Uuid(Uuid),
+    /// A key made of multiple fields, such as `(group_id, item_id)`. It is created
+    /// from a tuple of keys. An enum can be used as a key by implementing
+    /// `From<&YourEnum>` for `ListItemKey` (and `PartialEq<ListItemKey>` for `YourEnum`),
+    /// for example, by mapping it to a `Composite` of its discriminant and fields.
+    Composite(Vec<ListItemKey>),
+}
+
+impl From<&ListItemKey> for ListItemKey {
+    fn from(value: &ListItemKey) -> Self {
+        value.clone()
+    }
 }
 
 impl From<&String> for ListItemKey {
@@ -90,9 +103,63 @@ impl_from_and_partial_eq_for_key_type! {
     u64 U64
     i32 I32
     u32 U32
+}
+
+#[cfg(feature = "keyed-list-uuid")]
+impl_from_and_partial_eq_for_key_type! {
     Uuid Uuid
 }
 
+/// A type that can be a field of a tuple key, such as `(group_id, item_id)`.
+pub trait KeyField: PartialEq<ListItemKey> {
+    fn to_list_item_key(&self) -> ListItemKey;
+}
+
+macro_rules! impl_key_field {
+    ($($key_type:ty)+) => {
+        $(
+            impl KeyField for $key_type {
+                fn to_list_item_key(&self) -> ListItemKey {
+                    ListItemKey::from(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_key_field! { String &str isize usize i64 u64 i32 u32 }
+
+#[cfg(feature = "keyed-list-uuid")]
+impl_key_field! { Uuid }
+
+macro_rules! impl_from_and_partial_eq_for_tuple_key {
+    ($(($($T:ident $index:tt),+) $len:literal)+) => {
+        $(
+            impl<$($T: KeyField),+> From<&($($T,)+)> for ListItemKey {
+                fn from(value: &($($T,)+)) -> Self {
+                    ListItemKey::Composite(vec![$(value.$index.to_list_item_key()),+])
+                }
+            }
+            impl<$($T: KeyField),+> PartialEq<ListItemKey> for ($($T,)+) {
+                fn eq(&self, other: &ListItemKey) -> bool {
+                    match other {
+                        ListItemKey::Composite(keys) if keys.len() == $len => {
+                            $(self.$index == keys[$index])&&+
+                        }
+                        _ => false,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_and_partial_eq_for_tuple_key! {
+    (A 0, B 1) 2
+    (A 0, B 1, C 2) 3
+    (A 0, B 1, C 2, D 3) 4
+}
+
 #[derive(Debug)]
 pub struct OldElement {
     pub index: usize,
@@ -113,6 +180,11 @@ impl KeyedElement {
 pub struct ListItemTemplate {
     pub rendered: bool,
     pub element: Element,
+    /// The status of the elements that are cloned from this template. A template that
+    /// is supplied by the user has no spair nodes, its clones must be rendered as new.
+    pub clone_status: ElementStatus,
+    /// The element supplied by the user that `element` is cloned from.
+    pub source: Option<web_sys::Element>,
 }
 
 #[derive(Default)]
@@ -145,6 +217,25 @@ impl KeyedList {
         &self.active
     }
 
+    /// The first key that is used by more than one item of the list, if any.
+    #[cfg(feature = "debug-trace")]
+    pub fn duplicated_key(&self) -> Option<&ListItemKey> {
+        let mut keys = std::collections::HashSet::with_capacity(self.active.len());
+        self.active
+            .iter()
+            .flatten()
+            .map(|item| &item.key)
+            .find(|key| !keys.insert(*key))
+    }
+
+    pub fn write_debug_tree(&self, out: &mut String, depth: usize) {
+        super::write_debug_line(out, depth, format_args!("keyed-list"));
+        for item in self.active.iter().flatten() {
+            super::write_debug_line(out, depth + 1, format_args!("key={:?}", item.key));
+            item.element.write_debug_tree(out, depth + 2);
+        }
+    }
+
     pub fn get_first_element(&self) -> Option<&Element> {
         self.active
             .first()
@@ -159,14 +250,37 @@ impl KeyedList {
 
     pub fn require_init_template(&mut self, f: impl FnOnce() -> Element) -> bool {
         match self.template.as_mut() {
-            None => {
+            Some(t) if t.source.is_none() => !t.rendered,
+            // No template yet, or the list used to be rendered with a template from the user
+            _ => {
                 self.template = Some(ListItemTemplate {
                     rendered: false,
                     element: f(),
+                    clone_status: ElementStatus::JustCloned,
+                    source: None,
                 });
                 true
             }
-            Some(t) => !t.rendered,
+        }
+    }
+
+    /// Set a template from the user that is ready to be cloned (no item is required
+    /// to be rendered to make it). Like a non-keyed list, new items are cloned from
+    /// the `template` of the current render: the stored template is replaced if
+    /// `template` is a different element from the last render.
+    pub fn set_template(&mut self, template: &web_sys::Element) {
+        let is_same_source = self
+            .template
+            .as_ref()
+            .and_then(|t| t.source.as_ref())
+            .is_some_and(|source| source.is_same_node(Some(template)));
+        if !is_same_source {
+            self.template = Some(ListItemTemplate {
+                rendered: true,
+                element: Element::from_template(template),
+                clone_status: ElementStatus::JustCreated,
+                source: Some(template.clone()),
+            });
         }
     }
 
@@ -187,6 +301,22 @@ impl KeyedList {
         )
     }
 
+    /// Put back the items that a queue-render keyed list keeps in its own `Nodes`
+    /// between updates, they are the old items of the next update.
+    #[cfg(feature = "queue-render")]
+    pub fn set_active_items(&mut self, items: impl Iterator<Item = KeyedElement>) {
+        self.active.clear();
+        self.active.extend(items.map(Some));
+    }
+
+    /// Take the items out of the list after an update.
+    #[cfg(feature = "queue-render")]
+    pub fn take_active_items(&mut self) -> impl Iterator<Item = KeyedElement> + '_ {
+        self.active
+            .drain(..)
+            .map(|item| item.expect_throw("dom::keyed_list::KeyedList::take_active_items"))
+    }
+
     // better name?
     pub fn pre_update(&mut self, count: usize) {
         self.old_elements_map.reserve(count);
@@ -86,10 +86,13 @@ pub trait MaybeAChildNode {
     }
 }
 
+/// The status of an element when it is being rendered.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum ElementStatus {
     JustCreated,
     Existing,
+    /// The element is cloned from another element (for example, a list item
+    /// cloned from the first item of the list).
     JustCloned,
 }
 
@@ -6,7 +6,9 @@ use crate::component::{ChildComp, Component, ComponentHandle};
 use super::KeyedList;
 #[cfg(feature = "queue-render")]
 use super::MaybeAChildNode;
-use super::{AChildNode, Element, ElementStatus, GroupedNodes, TextNode};
+use super::{
+    AChildNode, AnyComponentHandle, Element, ElementStatus, GroupedNodes, Nodes, TextNode,
+};
 #[cfg(feature = "queue-render")]
 use crate::queue_render::dom::QrNode;
 
@@ -19,6 +21,8 @@ pub enum Node {
     KeyedList(KeyedList),
     RefComponent(RefComponent),
     OwnedComponent(OwnedComponent),
+    ListComponent(ListComponent),
+    Portal(Portal),
     #[cfg(feature = "queue-render")]
     QrNode(QrNode),
 }
@@ -34,6 +38,8 @@ impl std::fmt::Debug for Node {
             // This is actually never reachable?
             Self::RefComponent(_) => "Node::RefComponent",
             Self::OwnedComponent(_) => "Node::OwnedComponent",
+            Self::ListComponent(_) => "Node::ListComponent",
+            Self::Portal(_) => "Node::Portal",
             #[cfg(feature = "queue-render")]
             Self::QrNode(_) => "Node::QrNode",
         };
@@ -114,7 +120,167 @@ impl OwnedComponent {
     }
 }
 
+/// A child component that is rendered in a list item. Unlike `RefComponent`, each
+/// item has its own slot, and the component in a slot can be replaced by another
+/// one when the items of the list change.
+pub struct ListComponent {
+    handle: Option<AnyComponentHandle>,
+    root_node: Option<web_sys::Node>,
+    parent: Option<web_sys::Node>,
+}
+
+impl Clone for ListComponent {
+    fn clone(&self) -> Self {
+        // The component can not be shared between list items, the clone
+        // starts empty and will be given its own component on render.
+        Self {
+            handle: None,
+            root_node: None,
+            parent: None,
+        }
+    }
+}
+
+// A non-keyed list may move a component to an earlier item before the slot
+// that it was in is updated or removed. In that case, the component must
+// still be mounted.
+impl Drop for ListComponent {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let attached = match self.parent.as_ref() {
+                Some(parent) => self.is_attached_to(parent),
+                None => true,
+            };
+            if attached {
+                drop(handle);
+            } else {
+                handle.release();
+            }
+        }
+    }
+}
+
+impl ListComponent {
+    pub fn new<C: Component>(comp: &ChildComp<C>, parent: &web_sys::Node) -> Self {
+        let v = comp.comp_instance();
+        let root_node: &web_sys::Node = v.root_element().ws_element().ws_node();
+        Self {
+            handle: Some(AnyComponentHandle::from(comp.comp())),
+            root_node: Some(root_node.clone()),
+            parent: Some(parent.clone()),
+        }
+    }
+
+    pub fn root_node(&self) -> Option<&web_sys::Node> {
+        self.root_node.as_ref()
+    }
+
+    pub fn is_attached_to(&self, parent: &web_sys::Node) -> bool {
+        self.root_node
+            .as_ref()
+            .and_then(|root_node| root_node.parent_node())
+            .as_ref()
+            == Some(parent)
+    }
+
+    pub fn insert_before_a_sibling(
+        &self,
+        parent: &web_sys::Node,
+        next_sibling: Option<&web_sys::Node>,
+    ) {
+        if let Some(node) = self.root_node.as_ref() {
+            node.insert_before_a_sibling(parent, next_sibling);
+        }
+    }
+
+    /// Unmount the component and remove it from `parent`. Nothing is done if the
+    /// component was moved to another parent.
+    pub fn remove_from(mut self, parent: &web_sys::Node) {
+        if self.is_attached_to(parent) {
+            self.handle = None;
+            if let Some(root_node) = self.root_node.as_ref() {
+                root_node.remove_from(parent);
+            }
+        }
+    }
+}
+
+/// Nodes that are rendered into a target element outside of the parent element.
+/// The nodes are removed from the target when the portal is dropped.
+pub struct Portal {
+    target: web_sys::Node,
+    nodes: Nodes,
+}
+
+impl Clone for Portal {
+    fn clone(&self) -> Self {
+        // The nodes are in the target, not in the (template) element that is
+        // being cloned, hence the clone starts empty and will be rendered again.
+        Self::new(self.target.clone())
+    }
+}
+
+impl Drop for Portal {
+    fn drop(&mut self) {
+        self.nodes.clear_and_remove_child_from_dom(&self.target);
+    }
+}
+
+impl Portal {
+    pub fn new(target: web_sys::Node) -> Self {
+        Self {
+            target,
+            nodes: Nodes::default(),
+        }
+    }
+
+    /// Remove the current nodes if the target changes.
+    pub fn set_target(&mut self, target: &web_sys::Node) {
+        if &self.target != target {
+            self.nodes.clear_and_remove_child_from_dom(&self.target);
+            self.target = target.clone();
+        }
+    }
+
+    pub fn target_and_nodes_mut(&mut self) -> (&web_sys::Node, &mut Nodes) {
+        (&self.target, &mut self.nodes)
+    }
+}
+
+// Each level of `debug_tree` is indented by two spaces.
+pub fn write_debug_line(out: &mut String, depth: usize, line: std::fmt::Arguments) {
+    use std::fmt::Write;
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    // Writing to a `String` never fails.
+    let _ = out.write_fmt(line);
+    out.push('\n');
+}
+
 impl Node {
+    pub fn write_debug_tree(&self, out: &mut String, depth: usize) {
+        match self {
+            Self::Element(element) => element.write_debug_tree(out, depth),
+            Self::Text(text) => write_debug_line(out, depth, format_args!("{:?}", text.text())),
+            Self::GroupedNodes(gn) => gn.write_debug_tree(out, depth),
+            #[cfg(feature = "keyed-list")]
+            Self::KeyedList(list) => list.write_debug_tree(out, depth),
+            // The nodes of a child component are managed by its own `CompInstance`.
+            Self::RefComponent(_) => write_debug_line(out, depth, format_args!("component")),
+            Self::OwnedComponent(_) => {
+                write_debug_line(out, depth, format_args!("owned-component"))
+            }
+            Self::ListComponent(_) => write_debug_line(out, depth, format_args!("list-component")),
+            Self::Portal(portal) => {
+                write_debug_line(out, depth, format_args!("portal"));
+                portal.nodes.write_debug_tree(out, depth + 1);
+            }
+            #[cfg(feature = "queue-render")]
+            Self::QrNode(_) => write_debug_line(out, depth, format_args!("qr-node")),
+        }
+    }
+
     pub fn remove_from_dom(self, parent: &web_sys::Node) {
         match self {
             Self::Element(element) => {
@@ -137,6 +303,9 @@ impl Node {
                     wsn.remove_from(parent);
                 }
             }
+            Self::ListComponent(lc) => lc.remove_from(parent),
+            // The nodes of the portal are removed from its target when it is dropped
+            Self::Portal(_) => {}
             #[cfg(feature = "queue-render")]
             Self::QrNode(qr) => qr.remove_from(parent),
         }
@@ -156,6 +325,13 @@ impl Node {
                     wsn.append_to(parent);
                 }
             }
+            Self::ListComponent(lc) => {
+                if let Some(wsn) = lc.root_node.as_ref() {
+                    wsn.append_to(parent);
+                }
+            }
+            // The nodes of the portal are in its target, not in `parent`
+            Self::Portal(_) => {}
             #[cfg(feature = "queue-render")]
             Self::QrNode(qr) => qr.append_to(parent),
         }
@@ -171,6 +347,8 @@ impl Node {
             // Should this return the RefComponent::root_node (wrapped in dom::Element)?
             Self::RefComponent(_) => None,
             Self::OwnedComponent(_) => None,
+            Self::ListComponent(_) => None,
+            Self::Portal(_) => None,
             #[cfg(feature = "queue-render")]
             Self::QrNode(qr) => qr.get_first_element(),
         }
@@ -186,8 +364,84 @@ impl Node {
             // Should this return the RefComponent::root_node (wrapped in dom::Element)?
             Self::RefComponent(_) => None,
             Self::OwnedComponent(_) => None,
+            Self::ListComponent(_) => None,
+            Self::Portal(_) => None,
             #[cfg(feature = "queue-render")]
             Self::QrNode(qr) => qr.get_last_element(),
         }
     }
 }
+
+#[cfg(test)]
+mod portal_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component, RcComp};
+    use crate::render::html::{HemsHandMade, MethodsForHtmlElementContent};
+
+    struct State {
+        target: web_sys::Element,
+        show: bool,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.rupdate("local").match_if(|mi| match self.show {
+                true => crate::set_arm!(mi)
+                    .portal(self.target.clone(), |nodes| {
+                        nodes.rupdate("remote");
+                    })
+                    .done(),
+                false => crate::set_arm!(mi).done(),
+            });
+        }
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                target: crate::utils::document()
+                    .create_element("div")
+                    .expect_throw("Create the portal target"),
+                show: true,
+            }
+        }
+    }
+
+    fn mount() -> (crate::dom::Element, RcComp<State>, web_sys::Element) {
+        let (root, rc) = crate::test_utils::mount::<State>();
+        let target = rc.comp_instance().state().target.clone();
+        (root, rc, target)
+    }
+
+    #[wasm_bindgen_test]
+    fn portal_is_cleaned_up_on_component_drop() {
+        let (root, rc, target) = mount();
+        assert_eq!(
+            Some("local".to_string()),
+            root.ws_element().as_ref().text_content()
+        );
+        assert_eq!(Some("remote".to_string()), target.text_content());
+
+        drop(rc);
+        assert_eq!(0, target.child_nodes().length());
+    }
+
+    #[wasm_bindgen_test]
+    fn portal_is_cleaned_up_when_removed() {
+        let (_root, rc, target) = mount();
+        assert_eq!(Some("remote".to_string()), target.text_content());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.show = false)
+            .call();
+        assert_eq!(0, target.child_nodes().length());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.show = true)
+            .call();
+        assert_eq!(Some("remote".to_string()), target.text_content());
+    }
+}
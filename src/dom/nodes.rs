@@ -1,13 +1,68 @@
 #[cfg(feature = "keyed-list")]
 use super::KeyedList;
 use super::{
-    AChildNode, Element, ElementStatus, ElementTag, Node, OwnedComponent, RefComponent, TextNode,
+    AChildNode, Element, ElementStatus, ElementTag, ListComponent, Node, OwnedComponent, Portal,
+    RefComponent, TextNode,
 };
 use crate::component::{Comp, Component, ComponentHandle};
 #[cfg(feature = "queue-render")]
 use crate::queue_render::dom::QrNode;
+use crate::render::ListElementCreation;
 use wasm_bindgen::UnwrapThrowExt;
 
+thread_local! {
+    // Reused by `Nodes::render_display` to avoid allocating on every render.
+    static TEXT_BUFFER: std::cell::RefCell<String> = const { std::cell::RefCell::new(String::new()) };
+}
+
+#[cfg(feature = "debug-trace")]
+thread_local! {
+    static RENDERING_COMPONENTS: std::cell::RefCell<Vec<&'static str>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Remember the name of the component that is being rendered, so that a structure
+/// mismatch in its nodes can be reported with the component name.
+#[cfg(feature = "debug-trace")]
+pub(crate) struct RenderingComponent;
+
+#[cfg(feature = "debug-trace")]
+impl RenderingComponent {
+    pub(crate) fn new(name: &'static str) -> Self {
+        RENDERING_COMPONENTS.with(|names| names.borrow_mut().push(name));
+        Self
+    }
+}
+
+#[cfg(feature = "debug-trace")]
+impl Drop for RenderingComponent {
+    fn drop(&mut self) {
+        RENDERING_COMPONENTS.with(|names| names.borrow_mut().pop());
+    }
+}
+
+// Rendering is index-based, if a render emits a different kind of node at the same
+// position as the previous render, the node found at the index is not the expected one.
+#[cold]
+fn node_mismatch(method: &str, index: usize, expected: &str, found: &Node) -> ! {
+    let message = mismatch_message(method, index, expected, found);
+    #[cfg(feature = "debug-trace")]
+    crate::diagnostics::error(format_args!("Spair: {}", message));
+    panic!("{}", message);
+}
+
+fn mismatch_message(method: &str, index: usize, expected: &str, found: &Node) -> String {
+    let message = format!(
+        "dom::nodes::Nodes::{}: expected {} at index {}, found {:?}",
+        method, expected, index, found
+    );
+    #[cfg(feature = "debug-trace")]
+    let message = match RENDERING_COMPONENTS.with(|names| names.borrow().last().copied()) {
+        Some(name) => format!("{} (rendering {})", message, name),
+        None => message,
+    };
+    message
+}
+
 #[derive(Default, Clone)]
 pub struct Nodes(Vec<Node>);
 
@@ -23,6 +78,14 @@ impl Nodes {
         &self.0
     }
 
+    /// Write the nodes (and their descendants) that are managed by Spair, one node
+    /// per line, indented by `depth`. The DOM is not read, except for tag names.
+    pub fn write_debug_tree(&self, out: &mut String, depth: usize) {
+        self.0
+            .iter()
+            .for_each(|node| node.write_debug_tree(out, depth));
+    }
+
     pub fn count(&self) -> usize {
         self.0.len()
     }
@@ -71,7 +134,7 @@ impl Nodes {
     pub fn remove_element_at(&mut self, index: usize) -> Element {
         match self.0.remove(index) {
             Node::Element(e) => e,
-            _ => panic!("remove_element_at should be an Element"),
+            other => node_mismatch("remove_element_at", index, "Node::Element", &other),
         }
     }
 
@@ -79,7 +142,7 @@ impl Nodes {
         match self.0.get(index) {
             Some(Node::Element(element)) => Some(element),
             None => None,
-            _ => panic!("dom::nodes::Nodes::get_element expected Node::Element"),
+            Some(other) => node_mismatch("get_element", index, "Node::Element", other),
         }
     }
 
@@ -93,7 +156,7 @@ impl Nodes {
             .expect_throw("dom::nodes::Nodes::get_element_mut")
         {
             Node::Element(element) => element,
-            _ => panic!("dom::nodes::Nodes::get_element_mut expected Node::Element"),
+            other => node_mismatch("get_element_mut", index, "Node::Element", other),
         }
     }
 
@@ -131,20 +194,30 @@ impl Nodes {
         parent: &web_sys::Node,
         parent_status: ElementStatus,
         next_sibling: Option<&web_sys::Node>,
-        use_template: bool,
+        mode: ListElementCreation,
     ) -> ElementStatus {
         let item_count = self.0.len();
         if index < item_count {
             parent_status
-        } else if !use_template || item_count == 0 {
+        } else if let Some(template) = mode.template() {
+            let element = Element::from_template(template);
+            element.insert_before_a_sibling(parent, next_sibling);
+            self.0.push(Node::Element(element));
+            // Spair knows nothing about the content of the template, static content
+            // must be rendered as if the element is brand new.
+            ElementStatus::JustCreated
+        } else if !mode.use_template() || item_count == 0 {
             self.create_new_element_ns(tag, parent, next_sibling);
             ElementStatus::JustCreated
         } else {
             let element = self.0[0].clone();
             match &element {
                 Node::Element(element) => element.insert_before_a_sibling(parent, next_sibling),
-                _ => panic!(
-                    "dom::nodes::Nodes::check_or_create_element_for_list expected Node::Element"
+                other => node_mismatch(
+                    "check_or_create_element_for_list",
+                    0,
+                    "Node::Element",
+                    other,
                 ),
             }
             self.0.push(element);
@@ -172,7 +245,7 @@ impl Nodes {
             .expect_throw("dom::nodes::Nodes::grouped_nodes get_mut")
         {
             Node::GroupedNodes(gn) => gn,
-            _ => panic!("dom::nodes::Nodes::grouped_nodes expected Node::GroupedNodes"),
+            other => node_mismatch("grouped_nodes", index, "Node::GroupedNodes", other),
         }
     }
 
@@ -194,7 +267,25 @@ impl Nodes {
             .expect_throw("dom::nodes::Nodes::keyed_list first_mut")
         {
             Node::KeyedList(list) => list,
-            _ => panic!("dom::nodes::Nodes::keyed_list expected Node::KeyedList"),
+            other => node_mismatch("keyed_list", 0, "Node::KeyedList", other),
+        }
+    }
+
+    pub fn portal(&mut self, index: usize, target: &web_sys::Node) -> &mut Portal {
+        if index == self.0.len() {
+            self.0.push(Node::Portal(Portal::new(target.clone())));
+        }
+
+        match self
+            .0
+            .get_mut(index)
+            .expect_throw("dom::nodes::Nodes::portal get_mut")
+        {
+            Node::Portal(portal) => {
+                portal.set_target(target);
+                portal
+            }
+            other => node_mismatch("portal", index, "Node::Portal", other),
         }
     }
 
@@ -205,6 +296,39 @@ impl Nodes {
         self.0.push(Node::RefComponent(rc));
     }
 
+    pub fn list_component_root_node(&self, index: usize) -> Option<&web_sys::Node> {
+        match self.0.get(index) {
+            Some(Node::ListComponent(lc)) => lc.root_node(),
+            _ => None,
+        }
+    }
+
+    /// Store the component at `index`, replacing the component that was there.
+    pub fn store_list_component(
+        &mut self,
+        index: usize,
+        parent: &web_sys::Node,
+        next_sibling: Option<&web_sys::Node>,
+        lc: ListComponent,
+    ) {
+        match self.0.get_mut(index) {
+            None => {
+                lc.insert_before_a_sibling(parent, next_sibling);
+                self.0.push(Node::ListComponent(lc));
+            }
+            Some(Node::ListComponent(old)) => {
+                match old.root_node().filter(|_| old.is_attached_to(parent)) {
+                    Some(old_root) => lc.insert_before_a_sibling(parent, Some(old_root)),
+                    None => lc.insert_before_a_sibling(parent, next_sibling),
+                }
+                std::mem::replace(old, lc).remove_from(parent);
+            }
+            Some(other) => {
+                node_mismatch("store_list_component", index, "Node::ListComponent", other)
+            }
+        }
+    }
+
     fn get_owned_component_mut(&mut self, index: usize) -> &mut OwnedComponent {
         match self
             .0
@@ -212,7 +336,12 @@ impl Nodes {
             .expect_throw("dom::nodes::Nodes::get_owned_component_mut get_mut")
         {
             Node::OwnedComponent(oc) => oc,
-            _ => panic!("dom::nodes::Nodes::get_owned_component_mut expected Node::OwnedComponent"),
+            other => node_mismatch(
+                "get_owned_component_mut",
+                index,
+                "Node::OwnedComponent",
+                other,
+            ),
         }
     }
 
@@ -247,15 +376,30 @@ impl Nodes {
         self.0.last().and_then(|n| n.get_last_element())
     }
 
+    /// Return `true` if a new text node is created
     pub fn static_text(
         &mut self,
         index: usize,
         text: &str,
         parent: &web_sys::Node,
         next_sibling: Option<&web_sys::Node>,
-    ) {
+    ) -> bool {
         if index == self.0.len() {
             self.add_text_node(text, parent, next_sibling);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn append_static_text(&mut self, index: usize, text: &str) {
+        match self
+            .0
+            .get_mut(index)
+            .expect_throw("dom::nodes::Nodes::append_static_text get_mut")
+        {
+            Node::Text(text_node) => text_node.append_text(text),
+            other => node_mismatch("append_static_text", index, "Node::Text", other),
         }
     }
 
@@ -275,11 +419,30 @@ impl Nodes {
                 .expect_throw("dom::nodes::Nodes::update_text get_mut")
             {
                 Node::Text(text_node) => text_node.update_text(text),
-                _ => panic!("dom::nodes::Nodes::update_text expected Node::Text"),
+                other => node_mismatch("update_text", index, "Node::Text", other),
             }
         }
     }
 
+    /// Same as `update_text`, but `value` is formatted into a reused buffer, so rendering
+    /// a number or an enum does not allocate a new `String` on every render. The text node
+    /// is only updated if the text changes.
+    pub fn render_display(
+        &mut self,
+        index: usize,
+        value: &impl std::fmt::Display,
+        parent: &web_sys::Node,
+        next_sibling: Option<&web_sys::Node>,
+    ) {
+        TEXT_BUFFER.with(|buffer| {
+            let mut buffer = buffer.borrow_mut();
+            buffer.clear();
+            std::fmt::Write::write_fmt(&mut *buffer, format_args!("{}", value))
+                .expect_throw("dom::nodes::Nodes::render_display write_fmt");
+            self.update_text(index, &buffer, parent, next_sibling);
+        });
+    }
+
     fn add_text_node(
         &mut self,
         text: &str,
@@ -304,13 +467,15 @@ impl Nodes {
             .expect_throw("dom::nodes::Nodes::get_qr_node get_mut")
         {
             Node::QrNode(qr) => qr,
-            _ => panic!("dom::nodes::Nodes::get_qr_node expected Node::QrNode"),
+            other => node_mismatch("get_qr_node", index, "Node::QrNode", other),
         }
     }
 }
 
 pub struct GroupedNodes {
     active_index: Option<u32>,
+    // The key of the last render if this group is used by `memo`
+    memo_key: Option<Box<dyn std::any::Any>>,
     // `end_flag_node` marks the boundary of the end of this group of nodes
     end_flag_node: web_sys::Node,
     nodes: Nodes,
@@ -318,8 +483,19 @@ pub struct GroupedNodes {
 
 impl Clone for GroupedNodes {
     fn clone(&self) -> Self {
-        // a GroupedNodes should not be cloned?
-        Self::new()
+        // A GroupedNodes is cloned when its parent element is cloned (as a template
+        // for a list item), hence the active arm and its nodes must be cloned too.
+        let end_flag_node = self
+            .end_flag_node
+            .clone_node()
+            .expect_throw("dom::nodes::GroupedNodes::clone clone_node");
+        // The memo key can not be cloned, the clone will be rendered again.
+        Self {
+            active_index: self.active_index,
+            memo_key: None,
+            end_flag_node,
+            nodes: self.nodes.clone(),
+        }
     }
 }
 
@@ -336,6 +512,7 @@ impl GroupedNodes {
             .into();
         Self {
             active_index: None,
+            memo_key: None,
             end_flag_node,
             nodes: Nodes::default(),
         }
@@ -344,6 +521,7 @@ impl GroupedNodes {
     pub fn with_flag(end_flag_node: web_sys::Node) -> Self {
         Self {
             active_index: None,
+            memo_key: None,
             end_flag_node,
             nodes: Nodes::default(),
         }
@@ -353,6 +531,12 @@ impl GroupedNodes {
         &self.end_flag_node
     }
 
+    /// The index of the arm that is currently rendered, `None` if nothing is
+    /// rendered into this group yet.
+    pub fn active_index(&self) -> Option<u32> {
+        self.active_index
+    }
+
     pub fn set_active_index(&mut self, index: u32, parent: &web_sys::Node) -> ElementStatus {
         if Some(index) != self.active_index {
             self.nodes.clear_and_remove_child_from_dom(parent);
@@ -363,6 +547,22 @@ impl GroupedNodes {
         }
     }
 
+    /// Return `true` if `key` is different from the key of the last call. The
+    /// new key is stored for the next call.
+    pub fn check_memo_key<K: PartialEq + 'static>(&mut self, key: K) -> bool {
+        if let Some(old_key) = self
+            .memo_key
+            .as_ref()
+            .and_then(|old_key| old_key.downcast_ref::<K>())
+        {
+            if *old_key == key {
+                return false;
+            }
+        }
+        self.memo_key = Some(Box::new(key));
+        true
+    }
+
     pub fn remove_from_dom(self, parent: &web_sys::Node) {
         self.nodes.remove_from_dom(parent);
         self.end_flag_node.remove_from(parent);
@@ -377,21 +577,338 @@ impl GroupedNodes {
         &self.nodes
     }
 
+    pub fn write_debug_tree(&self, out: &mut String, depth: usize) {
+        let active_index = self
+            .active_index
+            .map(|index| format!(" arm={}", index))
+            .unwrap_or_default();
+        let memo = if self.memo_key.is_some() { " memo" } else { "" };
+        super::write_debug_line(out, depth, format_args!("group{}{}", active_index, memo));
+        self.nodes.write_debug_tree(out, depth + 1);
+    }
+
     pub fn nodes_mut_and_end_flag_node(&mut self) -> (&mut Nodes, &web_sys::Node) {
         (&mut self.nodes, &self.end_flag_node)
     }
 }
 
-pub struct AnyComponentHandle(Box<dyn std::any::Any>);
+/// A type-erased `ComponentHandle`. Dropping it marks the component as unmounted,
+/// `mount` marks the component as mounted again.
+pub struct AnyComponentHandle {
+    handle: Box<dyn std::any::Any>,
+    mount: Box<dyn Fn()>,
+}
 
 impl<C: Component> From<Comp<C>> for AnyComponentHandle {
     fn from(comp: Comp<C>) -> Self {
-        Self(Box::new(ComponentHandle::from(comp)))
+        Self {
+            handle: Box::new(ComponentHandle::from(comp.clone())),
+            mount: Box::new(move || comp.set_mount_status_to_mounted()),
+        }
+    }
+}
+
+impl AnyComponentHandle {
+    /// Drop the handle but keep the component mounted, because the component
+    /// is still in the DOM, as a child of another node.
+    pub fn release(self) {
+        let Self { handle, mount } = self;
+        drop(handle);
+        mount();
     }
 }
+
 impl Clone for AnyComponentHandle {
     fn clone(&self) -> Self {
         //
         panic!("Spair does not support mounting a component inside a list item");
     }
 }
+
+#[cfg(test)]
+mod nodes_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::dom::Element;
+    use crate::render::html::HtmlTag;
+
+    #[wasm_bindgen_test]
+    fn report_structure_mismatch() {
+        let parent = Element::new_ns(HtmlTag("div"));
+        let mut nodes = super::Nodes::default();
+        nodes.update_text(0, "a text", parent.ws_element().ws_node(), None);
+        let mismatch_message =
+            || super::mismatch_message("get_element_mut", 0, "Node::Element", &nodes.0[0]);
+
+        assert_eq!(
+            "dom::nodes::Nodes::get_element_mut: expected Node::Element at index 0, found [Node::Text]",
+            mismatch_message()
+        );
+
+        #[cfg(feature = "debug-trace")]
+        {
+            let _rendering = super::RenderingComponent::new("Unstable");
+            assert_eq!(
+                "dom::nodes::Nodes::get_element_mut: expected Node::Element at index 0, found [Node::Text] (rendering Unstable)",
+                mismatch_message()
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn render_display_reuses_buffers() {
+        use wasm_bindgen::JsCast;
+        let parent = Element::new_ns(HtmlTag("div"));
+        let parent = parent.ws_element().ws_node();
+        let mut nodes = super::Nodes::default();
+        let text = |nodes: &super::Nodes| match nodes.0.first() {
+            Some(super::Node::Text(text)) => text.text().clone(),
+            _ => panic!("nodes_tests::render_display_reuses_buffers expected a text node"),
+        };
+        let text_ptr = |nodes: &super::Nodes| match nodes.0.first() {
+            Some(super::Node::Text(text)) => text.text().as_ptr(),
+            _ => panic!("nodes_tests::render_display_reuses_buffers expected a text node"),
+        };
+        let buffer_capacity = || super::TEXT_BUFFER.with(|buffer| buffer.borrow().capacity());
+
+        nodes.render_display(0, &1234, parent, None);
+        assert_eq!("1234", text(&nodes));
+        assert_eq!(Some("1234".to_string()), parent.text_content());
+
+        let observer = web_sys::MutationObserver::new(&js_sys::Function::new_no_args(""))
+            .expect_throw("Create a MutationObserver");
+        let options = web_sys::MutationObserverInit::new();
+        options.set_character_data(true);
+        options.set_subtree(true);
+        observer
+            .observe_with_options(parent, &options)
+            .expect_throw("Observe the text node");
+        let capacity = buffer_capacity();
+        let ptr = text_ptr(&nodes);
+
+        nodes.render_display(0, &1234, parent, None);
+        assert_eq!(
+            0,
+            observer
+                .take_records()
+                .iter()
+                .map(|record| record.unchecked_into::<web_sys::MutationRecord>())
+                .filter(|record| record.type_() == "characterData")
+                .count()
+        );
+        assert_eq!(capacity, buffer_capacity());
+        assert_eq!(ptr, text_ptr(&nodes));
+
+        nodes.render_display(0, &5678, parent, None);
+        assert_eq!(Some("5678".to_string()), parent.text_content());
+        observer.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod grouped_nodes_tests {
+    use wasm_bindgen_test::*;
+
+    use crate::dom::ElementStatus;
+    use crate::render::html::{HemsForList, HemsHandMade, MethodsForHtmlElementContent};
+    use crate::render::ListElementCreation;
+    use crate::test_utils::TestElement;
+
+    fn render_list(root: &mut TestElement, items: &[u32]) {
+        let _ = root.updater(ElementStatus::Existing).list_with_render(
+            items.iter(),
+            ListElementCreation::Clone,
+            "span",
+            |item, span| {
+                span.match_if(|mi| match item % 2 == 0 {
+                    true => crate::set_arm!(mi).rupdate("even").done(),
+                    false => crate::set_arm!(mi).rupdate("odd").done(),
+                })
+                .rupdate(*item);
+            },
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn list_with_custom_template() {
+        use wasm_bindgen::UnwrapThrowExt;
+        let mut root = TestElement::new("ul");
+
+        let template = crate::utils::document()
+            .create_element("li")
+            .expect_throw("grouped_nodes_tests::list_with_custom_template create_element");
+        template.set_class_name("item");
+        template.set_inner_html("<b>*</b>");
+
+        let render = |root: &mut TestElement, items: &[u32]| {
+            let _ = root.updater(ElementStatus::Existing).list_with_render(
+                items.iter(),
+                ListElementCreation::Template(&template),
+                "li",
+                |item, li| {
+                    li.rupdate(*item);
+                },
+            );
+        };
+
+        render(&mut root, &[1, 2]);
+        assert_eq!(
+            r#"<li class="item"><b>*</b>1</li><li class="item"><b>*</b>2</li>"#,
+            root.ws_element().inner_html()
+        );
+
+        render(&mut root, &[3, 4, 5]);
+        assert_eq!(
+            r#"<li class="item"><b>*</b>3</li><li class="item"><b>*</b>4</li><li class="item"><b>*</b>5</li>"#,
+            root.ws_element().inner_html()
+        );
+        // The template itself is never modified
+        assert_eq!("<b>*</b>", template.inner_html());
+    }
+
+    #[wasm_bindgen_test]
+    fn changed_custom_template() {
+        use wasm_bindgen::UnwrapThrowExt;
+        let template = |class_name: &str| {
+            let template = crate::utils::document()
+                .create_element("li")
+                .expect_throw("grouped_nodes_tests::changed_custom_template create_element");
+            template.set_class_name(class_name);
+            template
+        };
+        let first = template("first");
+        let second = template("second");
+        let mut root = TestElement::new("ul");
+        let mut render = |items: &[u32], template: &web_sys::Element| {
+            let _ = root.updater(ElementStatus::Existing).list_with_render(
+                items.iter(),
+                ListElementCreation::Template(template),
+                "li",
+                |item, li| {
+                    li.rupdate(*item);
+                },
+            );
+        };
+
+        render(&[1], &first);
+        render(&[1, 2], &second);
+        assert_eq!(
+            r#"<li class="first">1</li><li class="second">2</li>"#,
+            root.ws_element().inner_html()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn static_content_of_items_from_custom_template() {
+        use crate::render::html::HamsForDistinctNames;
+        use wasm_bindgen::UnwrapThrowExt;
+        let mut root = TestElement::new("ul");
+
+        let template = crate::utils::document()
+            .create_element("li")
+            .expect_throw("grouped_nodes_tests::static_content_of_items_from_custom_template");
+
+        let render = |root: &mut TestElement, items: &[u32]| {
+            let _ = root.updater(ElementStatus::Existing).list_with_render(
+                items.iter(),
+                ListElementCreation::Template(&template),
+                "li",
+                |item, li| {
+                    li.static_attributes()
+                        .title("static")
+                        .rstatic("#")
+                        .rupdate(*item);
+                },
+            );
+        };
+
+        render(&mut root, &[1, 2]);
+        assert_eq!(
+            r#"<li title="static">#1</li><li title="static">#2</li>"#,
+            root.ws_element().inner_html()
+        );
+
+        render(&mut root, &[3, 4, 5]);
+        assert_eq!(
+            r#"<li title="static">#3</li><li title="static">#4</li><li title="static">#5</li>"#,
+            root.ws_element().inner_html()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn clone_list_item_with_match_if() {
+        let mut root = TestElement::new("div");
+
+        render_list(&mut root, &[1, 3, 2, 5]);
+        assert_eq!(
+            Some("odd1odd3even2odd5"),
+            root.ws_element().text_content().as_deref()
+        );
+
+        render_list(&mut root, &[2, 3, 4, 5, 6, 7]);
+        assert_eq!(
+            Some("even2odd3even4odd5even6odd7"),
+            root.ws_element().text_content().as_deref()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn consecutive_static_texts_are_merged() {
+        let mut root = TestElement::new("div");
+
+        for (status, value) in [
+            (ElementStatus::JustCreated, 1),
+            (ElementStatus::Existing, 2),
+        ] {
+            root.updater(status)
+                .rstatic("Hello")
+                .rstatic(", ")
+                .rstatic("world")
+                .rupdate(value)
+                .rstatic("!")
+                .rstatic("?");
+            assert_eq!(3, root.element.nodes().count());
+            assert_eq!(3, root.ws_element().child_nodes().length());
+            assert_eq!(
+                Some(format!("Hello, world{value}!?")),
+                root.ws_element().text_content()
+            );
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn memo() {
+        let mut root = TestElement::new("div");
+        let renders = std::cell::Cell::new(0);
+
+        let mut render = |status, key: &str, value: u32| {
+            root.updater(status)
+                .memo(key.to_string(), |nodes| {
+                    renders.set(renders.get() + 1);
+                    nodes.rupdate(value);
+                })
+                .rupdate("!");
+            root.ws_element().text_content()
+        };
+
+        assert_eq!(
+            Some("1!"),
+            render(ElementStatus::JustCreated, "a", 1).as_deref()
+        );
+        assert_eq!(1, renders.get());
+
+        // Same key: `f` is not executed, the DOM is untouched
+        assert_eq!(
+            Some("1!"),
+            render(ElementStatus::Existing, "a", 2).as_deref()
+        );
+        assert_eq!(1, renders.get());
+
+        assert_eq!(
+            Some("3!"),
+            render(ElementStatus::Existing, "b", 3).as_deref()
+        );
+        assert_eq!(2, renders.get());
+    }
+}
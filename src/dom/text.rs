@@ -54,7 +54,11 @@ impl TextNode {
         }
     }
 
-    #[cfg(test)]
+    pub fn append_text(&mut self, text: &str) {
+        self.text.push_str(text);
+        self.ws_node.set_text_content(Some(&self.text));
+    }
+
     pub fn text(&self) -> &String {
         &self.text
     }
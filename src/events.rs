@@ -1,10 +1,19 @@
+use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
 pub trait Listener {}
 
+/// Implemented by all event types, allows `Comp::handler_element` and
+/// `Comp::handler_element_mut` to give the handler the element that the
+/// listener is attached to.
+pub trait DomEvent: 'static {
+    fn current_target_as<T: JsCast>(&self) -> Option<T>;
+    fn as_event(&self) -> &web_sys::Event;
+}
+
 macro_rules! create_methods_for_event_trait {
-    ($($method_name:ident $EventName:ident,)+) => {
+    ($EventType:ident $($method_name:ident $EventName:ident $([$prevent_default:ident $stop_propagation:ident])?,)+) => {
         $(
             fn $method_name<F>(mut self, f: F) -> Self
             where F: $EventName
@@ -16,22 +25,84 @@ macro_rules! create_methods_for_event_trait {
                 }
                 self
             }
+            $(
+                #[doc = concat!("Same as `", stringify!($method_name), "` but `event.preventDefault()` is called before the callback.")]
+                fn $prevent_default<F>(self, f: F) -> Self
+                where F: 'static + crate::callback::CallbackArg<$EventType>
+                {
+                    self.$method_name(EventModifier::new(f, web_sys::Event::prevent_default))
+                }
+
+                #[doc = concat!("Same as `", stringify!($method_name), "` but `event.stopPropagation()` is called before the callback.")]
+                fn $stop_propagation<F>(self, f: F) -> Self
+                where F: 'static + crate::callback::CallbackArg<$EventType>
+                {
+                    self.$method_name(EventModifier::new(f, web_sys::Event::stop_propagation))
+                }
+            )?
         )+
     }
 }
 
+/// Call a method of the event (such as `preventDefault`) before passing the event
+/// to the callback. The method is called immediately, even if the callback is queued.
+pub struct EventModifier<F> {
+    callback: F,
+    modify: fn(&web_sys::Event),
+}
+
+impl<F> EventModifier<F> {
+    pub fn new(callback: F, modify: fn(&web_sys::Event)) -> Self {
+        Self { callback, modify }
+    }
+}
+
+impl<E: DomEvent, F: crate::callback::CallbackArg<E>> crate::callback::CallbackArg<E>
+    for EventModifier<F>
+{
+    fn call(&self, event: E) {
+        (self.modify)(event.as_event());
+        self.callback.call(event);
+    }
+
+    fn queue(&self, event: E) {
+        (self.modify)(event.as_event());
+        self.callback.queue(event);
+    }
+
+    fn call_or_queue(&self, event: E) {
+        (self.modify)(event.as_event());
+        self.callback.call_or_queue(event);
+    }
+}
+
 macro_rules! create_events {
     ($(
         $EventType:ident $EventListener:ident {
-            $($EventName:ident => $event_name:literal $event_method_name:ident,)+
+            $($EventName:ident => $event_name:literal $event_method_name:ident $([$($modified_method_name:ident)+])?,)+
         }
     )+) => {
         pub trait MethodsForEvents<C: crate::component::Component>: Sized + crate::render::base::ElementUpdaterMut<C> {
             $(
                 create_methods_for_event_trait! {
-                    $($event_method_name $EventName,)+
+                    $EventType
+                    $($event_method_name $EventName $([$($modified_method_name)+])?,)+
                 }
             )+
+
+            /// Call `f` with the `pointerdown` event if the pointer is held down for
+            /// `duration` without moving. Releasing, cancelling or moving the pointer
+            /// (more than a few pixels) before that cancels the long press.
+            fn on_long_press<F>(mut self, duration: std::time::Duration, f: F) -> Self
+            where F: 'static + crate::callback::CallbackArg<PointerEvent>
+            {
+                let er = self.element_updater_mut();
+                if er.require_set_listener() {
+                    let listener = LongPressListener::new(duration, f, er.element().ws_element().ws_event_target());
+                    er.store_listener(Box::new(listener));
+                }
+                self
+            }
         }
         $(
             pub struct $EventType(web_sys::$EventType);
@@ -57,10 +128,20 @@ macro_rules! create_events {
                 }
             }
 
+            impl DomEvent for $EventType {
+                fn current_target_as<T: JsCast>(&self) -> Option<T> {
+                    self.0.current_target().and_then(|et| et.dyn_into().ok())
+                }
+
+                fn as_event(&self) -> &web_sys::Event {
+                    self.0.as_ref()
+                }
+            }
+
             pub struct $EventListener {
-                _event_name: &'static str,
-                _event_target: web_sys::EventTarget,
-                _closure: Closure<dyn Fn(web_sys::$EventType)>,
+                event_name: &'static str,
+                event_target: web_sys::EventTarget,
+                closure: Closure<dyn Fn(web_sys::$EventType)>,
             }
             impl $EventListener {
                 fn new(event_name: &'static str, event_target: &web_sys::EventTarget, closure: Closure<dyn Fn(web_sys::$EventType)>) -> Self {
@@ -69,13 +150,24 @@ macro_rules! create_events {
                         closure.as_ref().unchecked_ref()
                     ).expect_throw("Expect event register to be successful");
                     Self {
-                        _event_name: event_name,
-                        _event_target: event_target.clone(),
-                        _closure: closure,
+                        event_name,
+                        event_target: event_target.clone(),
+                        closure,
                     }
                 }
             }
 
+            // The closure is invalid after being dropped, it must not be called by the
+            // event target anymore.
+            impl Drop for $EventListener {
+                fn drop(&mut self) {
+                    self.event_target.remove_event_listener_with_callback(
+                        self.event_name,
+                        self.closure.as_ref().unchecked_ref()
+                    ).expect_throw("Expect event unregister to be successful");
+                }
+            }
+
             impl Listener for $EventListener {}
             $(
                 #[doc = "Help creating "]
@@ -109,35 +201,44 @@ create_events! {
     FocusEvent FocusEventListener {
         Focus => "focus" on_focus,
         Blur => "blur" on_blur,
+        FocusIn => "focusin" on_focus_in,
+        FocusOut => "focusout" on_focus_out,
+    }
+    PointerEvent PointerEventListener {
+        PointerDown => "pointerdown" on_pointer_down,
+        PointerUp => "pointerup" on_pointer_up,
+        PointerMove => "pointermove" on_pointer_move,
+        PointerCancel => "pointercancel" on_pointer_cancel,
+        PointerLeave => "pointerleave" on_pointer_leave,
     }
     MouseEvent MouseEventListener {
         AuxClick => "auxclick" on_aux_click,
-        Click => "click" on_click,
-        DblClick => "dblclick" on_double_click,
+        Click => "click" on_click [on_click_prevent_default on_click_stop_propagation],
+        DblClick => "dblclick" on_double_click [on_double_click_prevent_default on_double_click_stop_propagation],
         MouseEnter => "mouseenter" on_mouse_enter,
         MouseOver => "mouseover" on_mouse_over,
         MouseMove => "mousemove" on_mouse_move,
-        MouseDown => "mousedown" on_mouse_down,
-        MouseUp => "mouseup" on_mouse_up,
+        MouseDown => "mousedown" on_mouse_down [on_mouse_down_prevent_default on_mouse_down_stop_propagation],
+        MouseUp => "mouseup" on_mouse_up [on_mouse_up_prevent_default on_mouse_up_stop_propagation],
         MouseLeave => "mouseleave" on_mouse_leave,
         MouseOut => "mouseout" on_mouse_out,
-        ContextMenu => "contextmenu" on_context_menu,
+        ContextMenu => "contextmenu" on_context_menu [on_context_menu_prevent_default on_context_menu_stop_propagation],
     }
     WheelEvent WheelEventListener {
-        Wheel => "wheel" on_wheel,
+        Wheel => "wheel" on_wheel [on_wheel_prevent_default on_wheel_stop_propagation],
     }
     InputEvent InputEventListener {
         Input => "input" on_input,
     }
     KeyboardEvent KeyboardEventListener {
-        KeyDown => "keydown" on_key_down,
-        KeyPress => "keypress" on_key_press,
+        KeyDown => "keydown" on_key_down [on_key_down_prevent_default on_key_down_stop_propagation],
+        KeyPress => "keypress" on_key_press [on_key_press_prevent_default on_key_press_stop_propagation],
         KeyUp => "keyup" on_key_up,
     }
     Event EventListener {
         Change => "change" on_change,
-        Reset => "reset" on_reset,
-        Submit => "submit" on_submit,
+        Reset => "reset" on_reset [on_reset_prevent_default on_reset_stop_propagation],
+        Submit => "submit" on_submit [on_submit_prevent_default on_submit_stop_propagation],
         PointerLockChange => "pointerlockchange" on_pointer_lock_change,
         PointerLockError => "pointerlockerror" on_pointer_lock_error,
 
@@ -145,6 +246,88 @@ create_events! {
     }
 }
 
+// How far (in CSS pixels) the pointer may move before a long press is cancelled.
+const LONG_PRESS_MOVE_TOLERANCE: i32 = 10;
+
+/// The listeners of a long press, created by `MethodsForEvents::on_long_press`.
+pub struct LongPressListener {
+    _listeners: Vec<PointerEventListener>,
+}
+
+impl Listener for LongPressListener {}
+
+impl LongPressListener {
+    pub fn new<F>(duration: std::time::Duration, callback: F, target: &web_sys::EventTarget) -> Self
+    where
+        F: 'static + crate::callback::CallbackArg<PointerEvent>,
+    {
+        // The position where the pointer is pressed and the timer of the pending press.
+        type Press = Option<((i32, i32), crate::timer::Timeout)>;
+        let press: Rc<RefCell<Press>> = Rc::new(RefCell::new(None));
+        let callback = Rc::new(callback);
+
+        let down = {
+            let press = press.clone();
+            move |event: web_sys::PointerEvent| {
+                let position = (event.client_x(), event.client_y());
+                let callback = callback.clone();
+                let timeout = crate::timer::Timeout::new(duration, move || {
+                    callback.call(PointerEvent(event));
+                });
+                *press.borrow_mut() = Some((position, timeout));
+            }
+        };
+        let moved = {
+            let press = press.clone();
+            move |event: web_sys::PointerEvent| {
+                let mut press = press.borrow_mut();
+                let too_far = match press.as_ref() {
+                    Some(((x, y), _)) => {
+                        (event.client_x() - x).abs() > LONG_PRESS_MOVE_TOLERANCE
+                            || (event.client_y() - y).abs() > LONG_PRESS_MOVE_TOLERANCE
+                    }
+                    None => false,
+                };
+                if too_far {
+                    press.take();
+                }
+            }
+        };
+        let listener = |event_name, f: Box<dyn Fn(web_sys::PointerEvent)>| {
+            PointerEventListener::new(event_name, target, Closure::wrap(f))
+        };
+        let cancel_on = |event_name| {
+            let press = press.clone();
+            listener(
+                event_name,
+                Box::new(move |_| drop(press.borrow_mut().take())),
+            )
+        };
+        Self {
+            _listeners: vec![
+                listener("pointerdown", Box::new(down)),
+                listener("pointermove", Box::new(moved)),
+                cancel_on("pointerup"),
+                cancel_on("pointercancel"),
+                cancel_on("pointerleave"),
+            ],
+        }
+    }
+}
+
+impl FocusEvent {
+    /// The element that is losing focus (for `focus`/`focusin`) or receiving focus
+    /// (for `blur`/`focusout`), if any. With the bubbling `focusout`, this can be used
+    /// to check whether the focus left a whole widget, such as a dropdown.
+    pub fn related_target(&self) -> Option<web_sys::EventTarget> {
+        self.0.related_target()
+    }
+
+    pub fn related_target_as<T: JsCast>(&self) -> Option<T> {
+        self.0.related_target().and_then(|et| et.dyn_into().ok())
+    }
+}
+
 impl InputEvent {
     pub fn current_target_as_input_element(&self) -> Option<web_sys::HtmlInputElement> {
         self.current_target_as()
@@ -160,3 +343,196 @@ impl Event {
         self.current_target_as()
     }
 }
+
+#[cfg(test)]
+mod events_tests {
+    use wasm_bindgen::{JsCast, UnwrapThrowExt};
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::render::base::MethodsForEvents;
+    use crate::render::html::{
+        HamsHandMade, HamsWithPredefinedValues, HemsForDistinctNames, InputType,
+    };
+    use crate::test_utils::sleep;
+
+    struct State {
+        outer_clicks: u32,
+        inner_clicks: u32,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            let comp = element.comp();
+            element.div(|d| {
+                d.on_click(comp.handler_mut(|state: &mut State| state.outer_clicks += 1))
+                    .input(|i| {
+                        i.input_type(InputType::CheckBox).on_click_prevent_default(
+                            comp.handler_mut(|state: &mut State| state.inner_clicks += 1),
+                        );
+                    })
+                    .button(|b| {
+                        b.on_click_stop_propagation(
+                            comp.handler_mut(|state: &mut State| state.inner_clicks += 1),
+                        );
+                    });
+            });
+        }
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                outer_clicks: 0,
+                inner_clicks: 0,
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn prevent_default_and_stop_propagation() {
+        let (root, rc) = crate::test_utils::mount::<State>();
+        let clicks = || {
+            let instance = rc.comp_instance();
+            let state = instance.state();
+            (state.outer_clicks, state.inner_clicks)
+        };
+        let query = |selector| -> web_sys::HtmlElement {
+            root.ws_element()
+                .as_ref()
+                .query_selector(selector)
+                .expect_throw("events_tests query")
+                .expect_throw("events_tests no element")
+                .unchecked_into()
+        };
+
+        let checkbox: web_sys::HtmlInputElement = query("input").unchecked_into();
+        checkbox.click();
+        assert!(!checkbox.checked());
+        assert_eq!((1, 1), clicks());
+
+        query("button").click();
+        assert_eq!((1, 2), clicks());
+    }
+
+    struct Dropdown {
+        focus_moved_to: Option<String>,
+    }
+
+    impl Component for Dropdown {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            let comp = element.comp();
+            element.div(|d| {
+                d.on_focus_out(comp.handler_arg_mut(
+                    |state: &mut Dropdown, event: crate::FocusEvent| {
+                        state.focus_moved_to = event
+                            .related_target_as::<web_sys::Element>()
+                            .map(|element| element.id());
+                    },
+                ))
+                .input(|i| {
+                    i.id("search");
+                });
+            });
+        }
+    }
+
+    impl crate::application::Application for Dropdown {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                focus_moved_to: None,
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn focus_out_related_target() {
+        let (root, rc) = crate::test_utils::mount::<Dropdown>();
+        let input = root
+            .ws_element()
+            .as_ref()
+            .query_selector("input")
+            .expect_throw("events_tests::focus_out_related_target query")
+            .expect_throw("events_tests::focus_out_related_target no input");
+        let outside = crate::utils::document()
+            .create_element("button")
+            .expect_throw("events_tests::focus_out_related_target create button");
+        outside.set_id("outside");
+
+        let init = web_sys::FocusEventInit::new();
+        init.set_bubbles(true);
+        init.set_related_target(Some(outside.as_ref()));
+        let event = web_sys::FocusEvent::new_with_focus_event_init_dict("focusout", &init)
+            .expect_throw("events_tests::focus_out_related_target new event");
+        input
+            .dispatch_event(&event)
+            .expect_throw("events_tests::focus_out_related_target dispatch");
+        assert_eq!(
+            Some("outside"),
+            rc.comp_instance().state().focus_moved_to.as_deref()
+        );
+    }
+
+    struct Card {
+        long_presses: u32,
+    }
+
+    impl Component for Card {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            let comp = element.comp();
+            element.div(|d| {
+                d.on_long_press(
+                    std::time::Duration::from_millis(30),
+                    comp.handler_mut(|state: &mut Card| state.long_presses += 1),
+                );
+            });
+        }
+    }
+
+    impl crate::application::Application for Card {
+        fn init(_: &Comp<Self>) -> Self {
+            Self { long_presses: 0 }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn long_press() {
+        let (root, rc) = crate::test_utils::mount::<Card>();
+        let card = root
+            .ws_element()
+            .as_ref()
+            .first_element_child()
+            .expect_throw("events_tests::long_press card");
+        let pointer = |event_name, client_x| {
+            let init = web_sys::PointerEventInit::new();
+            init.set_client_x(client_x);
+            let event = web_sys::PointerEvent::new_with_event_init_dict(event_name, &init)
+                .expect_throw("events_tests::long_press new event");
+            card.dispatch_event(&event)
+                .expect_throw("events_tests::long_press dispatch");
+        };
+        let long_presses = || rc.comp_instance().state().long_presses;
+
+        // Held past the threshold
+        pointer("pointerdown", 0);
+        sleep(60).await;
+        pointer("pointerup", 0);
+        assert_eq!(1, long_presses());
+
+        // Released too early
+        pointer("pointerdown", 0);
+        sleep(5).await;
+        pointer("pointerup", 0);
+        sleep(60).await;
+        assert_eq!(1, long_presses());
+
+        // Moved away while holding
+        pointer("pointerdown", 0);
+        pointer("pointermove", 50);
+        sleep(60).await;
+        assert_eq!(1, long_presses());
+    }
+}
@@ -0,0 +1,580 @@
+use wasm_bindgen::{JsCast, JsValue};
+
+#[derive(thiserror::Error, Debug)]
+pub enum FetchError {
+    #[error("Error on fetching: {0:?}")]
+    Js(JsValue),
+    #[error("The server responded with status {0}")]
+    Status(u16),
+    /// The encoding label is not supported, or the body is not valid in that encoding.
+    #[error("Error on decoding the body as {0}: {1:?}")]
+    Decode(String, JsValue),
+    #[cfg(feature = "serde")]
+    #[error("Error on (de)serializing the body: {0}")]
+    Serde(#[from] serde_json::Error),
+    /// The GraphQL server reported errors, see `GraphQlResponse::into_result`.
+    #[cfg(feature = "serde")]
+    #[error("GraphQL errors: {0:?}")]
+    GraphQl(Vec<GraphQlError>),
+}
+
+impl From<JsValue> for FetchError {
+    fn from(value: JsValue) -> Self {
+        Self::Js(value)
+    }
+}
+
+/// A builder of a request for `window.fetch`. It is usually created by [`get`],
+/// [`post_json`], [`put_json`] or [`delete`].
+pub struct Request {
+    method: &'static str,
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    signal: Option<web_sys::AbortSignal>,
+}
+
+impl Request {
+    pub fn new(method: &'static str, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            signal: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Abort the request when `signal` is aborted, see `Command::scoped_future`.
+    pub fn signal(mut self, signal: &web_sys::AbortSignal) -> Self {
+        self.signal = Some(signal.clone());
+        self
+    }
+
+    /// Serialize `body` to JSON and set it as the body, with `Content-Type: application/json`.
+    #[cfg(feature = "serde")]
+    pub fn json<T: serde::Serialize + ?Sized>(self, body: &T) -> Result<Self, FetchError> {
+        let body = serde_json::to_string(body)?;
+        Ok(self.header("Content-Type", "application/json").body(body))
+    }
+
+    pub fn build(&self) -> Result<web_sys::Request, FetchError> {
+        let headers = web_sys::Headers::new()?;
+        for (name, value) in self.headers.iter() {
+            headers.set(name, value)?;
+        }
+        let init = web_sys::RequestInit::new();
+        init.set_method(self.method);
+        init.set_headers(&headers);
+        if let Some(body) = self.body.as_deref() {
+            init.set_body(&JsValue::from_str(body));
+        }
+        init.set_signal(self.signal.as_ref());
+        Ok(web_sys::Request::new_with_str_and_init(&self.url, &init)?)
+    }
+
+    // Send the request without checking the status of the response.
+    async fn fetch(self) -> Result<web_sys::Response, FetchError> {
+        let request = self.build()?;
+        Ok(wasm_bindgen_futures::JsFuture::from(
+            crate::utils::window().fetch_with_request(&request),
+        )
+        .await?
+        .unchecked_into::<web_sys::Response>())
+    }
+
+    /// Send the request. Returns `FetchError::Status` if the response status is not 2xx.
+    pub async fn send(self) -> Result<web_sys::Response, FetchError> {
+        let response = self.fetch().await?;
+        if !response.ok() {
+            return Err(FetchError::Status(response.status()));
+        }
+        Ok(response)
+    }
+
+    /// Send the request and get the body of the response as text.
+    pub async fn text(self) -> Result<String, FetchError> {
+        let response = self.send().await?;
+        response_text(&response).await
+    }
+
+    /// Send the request and decode the body of the response with the encoding `label`
+    /// (such as `"latin1"` or `"shift_jis"`), for servers that do not respond in UTF-8.
+    pub async fn text_with_encoding(self, label: &str) -> Result<String, FetchError> {
+        let response = self.send().await?;
+        let buffer = wasm_bindgen_futures::JsFuture::from(response.array_buffer()?).await?;
+        decode_text(&js_sys::Uint8Array::new(&buffer).to_vec(), label)
+    }
+
+    /// Send the request and deserialize the body of the response from JSON.
+    #[cfg(feature = "serde")]
+    pub async fn json_response<T: serde::de::DeserializeOwned>(self) -> Result<T, FetchError> {
+        let text = self.text().await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+}
+
+async fn response_text(response: &web_sys::Response) -> Result<String, FetchError> {
+    let text = wasm_bindgen_futures::JsFuture::from(response.text()?).await?;
+    text.as_string()
+        .ok_or_else(|| FetchError::Js(JsValue::from_str("fetch::Request::text: not a string")))
+}
+
+/// Decode `bytes` with the encoding `label`, using `TextDecoder`. Invalid bytes are
+/// reported as `FetchError::Decode` instead of being replaced by U+FFFD.
+pub fn decode_text(bytes: &[u8], label: &str) -> Result<String, FetchError> {
+    let options = web_sys::TextDecoderOptions::new();
+    options.set_fatal(true);
+    web_sys::TextDecoder::new_with_label_and_options(label, &options)
+        .and_then(|decoder| decoder.decode_with_u8_array(bytes))
+        .map_err(|error| FetchError::Decode(label.to_string(), error))
+}
+
+pub fn get(url: impl Into<String>) -> Request {
+    Request::new("GET", url)
+}
+
+pub fn delete(url: impl Into<String>) -> Request {
+    Request::new("DELETE", url)
+}
+
+#[cfg(feature = "serde")]
+pub fn post_json<T: serde::Serialize + ?Sized>(
+    url: impl Into<String>,
+    body: &T,
+) -> Result<Request, FetchError> {
+    Request::new("POST", url).json(body)
+}
+
+#[cfg(feature = "serde")]
+pub fn put_json<T: serde::Serialize + ?Sized>(
+    url: impl Into<String>,
+    body: &T,
+) -> Result<Request, FetchError> {
+    Request::new("PUT", url).json(body)
+}
+
+/// The base URL and default headers of an API, configured once per app. Requests
+/// created by it have paths resolved against the base URL and carry the default
+/// headers, which can be overridden by calling `Request::header` with the same name.
+#[derive(Debug, Clone, Default)]
+pub struct ApiClient {
+    base_url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl ApiClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header that is sent with every request, such as `Authorization`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Resolve `path` against the base URL. An absolute URL (with a scheme) is
+    /// returned unchanged.
+    pub fn url(&self, path: &str) -> String {
+        if has_scheme(path) {
+            return path.to_string();
+        }
+        format!(
+            "{}/{}",
+            self.base_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    pub fn request(&self, method: &'static str, path: &str) -> Request {
+        let mut request = Request::new(method, self.url(path));
+        request.headers.extend(self.headers.iter().cloned());
+        request
+    }
+
+    pub fn get(&self, path: &str) -> Request {
+        self.request("GET", path)
+    }
+
+    pub fn delete(&self, path: &str) -> Request {
+        self.request("DELETE", path)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn post_json<T: serde::Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<Request, FetchError> {
+        self.request("POST", path).json(body)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn put_json<T: serde::Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<Request, FetchError> {
+        self.request("PUT", path).json(body)
+    }
+}
+
+// Whether `url` starts with a scheme, such as `https://`. A scheme is a letter followed
+// by letters, digits, "+", "-" or ".".
+fn has_scheme(url: &str) -> bool {
+    match url.split_once("://") {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+/// An item of `errors` of a GraphQL response.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphQlError {
+    pub message: String,
+    /// The path to the field that caused the error, empty if not given.
+    pub path: Vec<serde_json::Value>,
+}
+
+/// Create a request that posts `{"query": query, "variables": variables}` to `endpoint`.
+#[cfg(feature = "serde")]
+pub fn graphql_request<V: serde::Serialize + ?Sized>(
+    endpoint: impl Into<String>,
+    query: &str,
+    variables: &V,
+) -> Result<Request, FetchError> {
+    let mut envelope = serde_json::Map::new();
+    envelope.insert("query".to_string(), query.into());
+    envelope.insert("variables".to_string(), serde_json::to_value(variables)?);
+    post_json(endpoint, &envelope)
+}
+
+/// A decoded GraphQL response. A server may return partial `data` together with
+/// `errors`, so both are kept.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphQlResponse<T> {
+    /// `None` if the response has no `data` or `data` is `null`.
+    pub data: Option<T>,
+    pub errors: Vec<GraphQlError>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> GraphQlResponse<T> {
+    /// Get `data` if the server reported no errors, otherwise, returns
+    /// `FetchError::GraphQl`, discarding the partial data.
+    pub fn into_result(self) -> Result<T, FetchError> {
+        match self.data {
+            Some(data) if self.errors.is_empty() => Ok(data),
+            _ => Err(FetchError::GraphQl(self.errors)),
+        }
+    }
+
+    // Whether the body was a GraphQL response at all, rather than, e.g., an error
+    // page of a proxy that happens to be JSON.
+    fn is_empty(&self) -> bool {
+        self.data.is_none() && self.errors.is_empty()
+    }
+}
+
+/// Decode the body of a GraphQL response, deserializing `data` into `T`.
+#[cfg(feature = "serde")]
+pub fn decode_graphql_response<T: serde::de::DeserializeOwned>(
+    body: &str,
+) -> Result<GraphQlResponse<T>, FetchError> {
+    let mut response: serde_json::Value = serde_json::from_str(body)?;
+    let errors = response
+        .get("errors")
+        .and_then(|e| e.as_array())
+        .map(|errors| {
+            errors
+                .iter()
+                .map(|error| GraphQlError {
+                    message: error
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    path: error
+                        .get("path")
+                        .and_then(|p| p.as_array())
+                        .cloned()
+                        .unwrap_or_default(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let data = response
+        .get_mut("data")
+        .map(serde_json::Value::take)
+        .unwrap_or_default();
+    Ok(GraphQlResponse {
+        data: serde_json::from_value(data)?,
+        errors,
+    })
+}
+
+/// Post a GraphQL query to `endpoint` and decode the response. GraphQL servers may
+/// report errors with a non-2xx status, so the body is decoded whatever the status is.
+/// `FetchError::Status` is only returned if a non-2xx response has neither `data` nor
+/// `errors`. Call `GraphQlResponse::into_result` to treat any error as a failure.
+#[cfg(feature = "serde")]
+pub async fn graphql<T, V>(
+    endpoint: impl Into<String>,
+    query: &str,
+    variables: &V,
+) -> Result<GraphQlResponse<T>, FetchError>
+where
+    T: serde::de::DeserializeOwned,
+    V: serde::Serialize + ?Sized,
+{
+    let response = graphql_request(endpoint, query, variables)?.fetch().await?;
+    let body = response_text(&response).await?;
+    match decode_graphql_response(&body) {
+        Ok(decoded) if response.ok() || !decoded.is_empty() => Ok(decoded),
+        Err(error) if response.ok() => Err(error),
+        _ => Err(FetchError::Status(response.status())),
+    }
+}
+
+#[cfg(test)]
+mod fetch_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    async fn body_of(request: &web_sys::Request) -> String {
+        let promise = request.text().expect_throw("fetch_tests::body_of text");
+        wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .expect_throw("fetch_tests::body_of await")
+            .as_string()
+            .expect_throw("fetch_tests::body_of as_string")
+    }
+
+    fn content_type(request: &web_sys::Request) -> Option<String> {
+        request
+            .headers()
+            .get("Content-Type")
+            .expect_throw("fetch_tests::content_type")
+    }
+
+    #[wasm_bindgen_test]
+    fn get_and_delete() {
+        let request = super::get("/items").build().expect_throw("build get");
+        assert_eq!("GET", request.method());
+        assert!(request.url().ends_with("/items"));
+
+        let request = super::delete("/items/1")
+            .build()
+            .expect_throw("build delete");
+        assert_eq!("DELETE", request.method());
+        assert!(request.url().ends_with("/items/1"));
+    }
+
+    #[wasm_bindgen_test]
+    fn api_client() {
+        let client = super::ApiClient::new("https://api.example.com/v1/")
+            .header("Authorization", "Bearer token")
+            .header("Accept", "application/json");
+        let header = |request: &web_sys::Request, name| {
+            request
+                .headers()
+                .get(name)
+                .expect_throw("fetch_tests::api_client get header")
+        };
+
+        let request = client.get("/items").build().expect_throw("build get");
+        assert_eq!("https://api.example.com/v1/items", request.url());
+        assert_eq!(
+            Some("Bearer token"),
+            header(&request, "Authorization").as_deref()
+        );
+        assert_eq!(
+            Some("application/json"),
+            header(&request, "Accept").as_deref()
+        );
+
+        let request = client
+            .delete("items/1")
+            .header("Authorization", "Bearer other")
+            .build()
+            .expect_throw("build delete");
+        assert_eq!("DELETE", request.method());
+        assert_eq!("https://api.example.com/v1/items/1", request.url());
+        assert_eq!(
+            Some("Bearer other"),
+            header(&request, "Authorization").as_deref()
+        );
+
+        assert_eq!(
+            "https://cdn.example.com/logo.png",
+            client.url("https://cdn.example.com/logo.png")
+        );
+        // A URL in the query does not make the path absolute
+        assert_eq!(
+            "https://api.example.com/v1/login?next=https://example.com/",
+            client.url("/login?next=https://example.com/")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn decode_text() {
+        // "café" in latin1 and "日本" in Shift_JIS
+        assert_eq!(
+            "café",
+            super::decode_text(&[0x63, 0x61, 0x66, 0xE9], "latin1").expect_throw("latin1")
+        );
+        assert_eq!(
+            "日本",
+            super::decode_text(&[0x93, 0xFA, 0x96, 0x7B], "shift_jis").expect_throw("shift_jis")
+        );
+        assert!(matches!(
+            super::decode_text(&[0x63, 0x61, 0x66, 0xE9], "utf-8"),
+            Err(super::FetchError::Decode(label, _)) if label == "utf-8"
+        ));
+        assert!(matches!(
+            super::decode_text(b"spair", "no-such-encoding"),
+            Err(super::FetchError::Decode(..))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[wasm_bindgen_test]
+    async fn post_and_put_json() {
+        #[derive(serde::Serialize)]
+        struct Item {
+            name: &'static str,
+        }
+
+        let request = super::post_json("/items", &Item { name: "spair" })
+            .expect_throw("post_json")
+            .build()
+            .expect_throw("build post");
+        assert_eq!("POST", request.method());
+        assert_eq!(Some("application/json"), content_type(&request).as_deref());
+        assert_eq!(r#"{"name":"spair"}"#, body_of(&request).await);
+
+        let request = super::put_json("/items/1", &Item { name: "fetch" })
+            .expect_throw("put_json")
+            .build()
+            .expect_throw("build put");
+        assert_eq!("PUT", request.method());
+        assert_eq!(Some("application/json"), content_type(&request).as_deref());
+        assert_eq!(r#"{"name":"fetch"}"#, body_of(&request).await);
+    }
+
+    #[cfg(feature = "serde")]
+    #[wasm_bindgen_test]
+    async fn graphql_envelope() {
+        #[derive(serde::Serialize)]
+        struct Variables {
+            id: u32,
+        }
+
+        let request = super::graphql_request(
+            "/graphql",
+            "query User($id: Int!) { user(id: $id) { name } }",
+            &Variables { id: 7 },
+        )
+        .expect_throw("graphql_request")
+        .build()
+        .expect_throw("build graphql");
+        assert_eq!("POST", request.method());
+        assert_eq!(Some("application/json"), content_type(&request).as_deref());
+        let body: serde_json::Value =
+            serde_json::from_str(&body_of(&request).await).expect_throw("graphql body");
+        assert_eq!(
+            Some("query User($id: Int!) { user(id: $id) { name } }"),
+            body["query"].as_str()
+        );
+        assert_eq!(Some(7), body["variables"]["id"].as_u64());
+    }
+
+    #[cfg(feature = "serde")]
+    #[wasm_bindgen_test]
+    fn decode_graphql_response() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct User {
+            name: String,
+        }
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Data {
+            user: User,
+            friend: Option<User>,
+        }
+
+        let user = |name: &str| User {
+            name: name.to_string(),
+        };
+        let not_found = super::GraphQlError {
+            message: "Not found".to_string(),
+            path: vec!["friend".into()],
+        };
+
+        let response =
+            super::decode_graphql_response::<Data>(r#"{"data": {"user": {"name": "spair"}}}"#)
+                .expect_throw("decode data");
+        assert_eq!(
+            super::GraphQlResponse {
+                data: Some(Data {
+                    user: user("spair"),
+                    friend: None,
+                }),
+                errors: Vec::new(),
+            },
+            response
+        );
+        assert_eq!(
+            Some(user("spair")),
+            response.into_result().ok().map(|data| data.user)
+        );
+
+        // Partial data is kept along with the errors
+        let response = super::decode_graphql_response::<Data>(
+            r#"{"data": {"user": {"name": "spair"}, "friend": null},
+                "errors": [{"message": "Not found", "path": ["friend"]}]}"#,
+        )
+        .expect_throw("decode partial data");
+        assert_eq!(
+            super::GraphQlResponse {
+                data: Some(Data {
+                    user: user("spair"),
+                    friend: None,
+                }),
+                errors: vec![not_found.clone()],
+            },
+            response
+        );
+        match response.into_result() {
+            Err(super::FetchError::GraphQl(errors)) => assert_eq!(vec![not_found.clone()], errors),
+            other => panic!("Expected GraphQL errors, found {:?}", other),
+        }
+
+        let response = super::decode_graphql_response::<Data>(
+            r#"{"data": null, "errors": [{"message": "Not found", "path": ["friend"]}]}"#,
+        )
+        .expect_throw("decode errors");
+        assert_eq!(None, response.data);
+        assert_eq!(vec![not_found], response.errors);
+    }
+}
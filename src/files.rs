@@ -0,0 +1,59 @@
+use wasm_bindgen::{JsCast, JsValue};
+
+async fn read_file(
+    file: &web_sys::File,
+    read: fn(&web_sys::FileReader, &web_sys::Blob) -> Result<(), JsValue>,
+) -> Result<JsValue, JsValue> {
+    let reader = web_sys::FileReader::new()?;
+    // `loadend` is fired exactly once, after `load`, `error` or `abort`.
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        reader.set_onloadend(Some(resolve.unchecked_ref()));
+    });
+    read(&reader, file)?;
+    wasm_bindgen_futures::JsFuture::from(promise).await?;
+    reader.set_onloadend(None);
+    if let Some(error) = reader.error() {
+        return Err(error.into());
+    }
+    reader.result()
+}
+
+/// Read the content of the file (for example, a file from an `<input type="file">`) as text.
+pub async fn read_file_as_text(file: web_sys::File) -> Result<String, JsValue> {
+    read_file(&file, web_sys::FileReader::read_as_text)
+        .await?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("files::read_file_as_text: the result is not a string"))
+}
+
+/// Read the content of the file (for example, a file from an `<input type="file">`) as bytes.
+pub async fn read_file_as_bytes(file: web_sys::File) -> Result<Vec<u8>, JsValue> {
+    let buffer = read_file(&file, web_sys::FileReader::read_as_array_buffer).await?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+#[cfg(test)]
+mod files_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    async fn read_file_as_text() {
+        let parts = js_sys::Array::of2(&"Hello, ".into(), &"Spair!".into());
+        let file = web_sys::File::new_with_str_sequence(&parts, "hello.txt")
+            .expect_throw("files_tests::read_file_as_text new file");
+        assert_eq!(
+            Ok("Hello, Spair!".to_string()),
+            super::read_file_as_text(file).await
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn read_file_as_bytes() {
+        let bytes: &[u8] = &[0, 1, 2, 254, 255];
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let file = web_sys::File::new_with_u8_array_sequence(&parts, "bytes.bin")
+            .expect_throw("files_tests::read_file_as_bytes new file");
+        assert_eq!(Ok(bytes.to_vec()), super::read_file_as_bytes(file).await);
+    }
+}
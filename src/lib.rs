@@ -3,23 +3,44 @@ wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
 mod application;
 mod callback;
+mod canvas;
+mod commands;
 mod component;
+mod context;
+mod diagnostics;
 mod dom;
 mod events;
+pub mod fetch;
+mod files;
 mod future;
 mod macros;
 mod render;
+mod resize_observer;
 mod routing;
+#[cfg(test)]
+pub(crate) mod test_utils;
+mod timer;
 mod utils;
 
 #[cfg(feature = "queue-render")]
 mod queue_render;
 
-pub use application::Application;
-pub use component::{AsChildComp, Checklist, Child, ChildComp, Comp, Component, ShouldRender};
+pub use application::{AppHandle, Application};
+pub use canvas::Canvas2d;
+pub use commands::{delay, every, poll, TimerHandle};
+#[cfg(feature = "error-boundary")]
+pub use component::ComponentError;
+pub use component::{
+    batch, AsChildComp, Checklist, Child, ChildComp, Comp, Component, MessageComponent,
+    ShouldRender, Snapshot,
+};
+pub use context::{provide_context, use_context};
+pub use diagnostics::{
+    remove_diagnostic_handler, set_diagnostic_handler, Level as DiagnosticLevel,
+};
+pub use dom::{ElementStatus, TagName};
 #[cfg(feature = "keyed-list")]
-pub use dom::Keyed;
-pub use dom::TagName;
+pub use dom::{KeyField, Keyed, ListItemKey};
 #[cfg(feature = "queue-render")]
 pub use queue_render::{val::QrVal, vec::QrVec};
 pub use render::html::HtmlTag;
@@ -36,8 +57,11 @@ pub use render::{
 
 // TODO selectively export event traits only?
 pub use events::*;
+pub use files::{read_file_as_bytes, read_file_as_text};
 pub use future::Future;
-pub use routing::{Router, Routes};
+pub use resize_observer::ResizeObserver;
+pub use routing::{current_history_state, push_state_with, Router, Routes, TrailingSlash};
+pub use timer::{Interval, Timeout};
 pub use utils::*;
 
 pub use web_sys;
@@ -85,6 +109,8 @@ pub mod prelude {
 
 pub type Callback = Box<dyn callback::Callback>;
 pub type CallbackArg<A> = Box<dyn callback::CallbackArg<A>>;
+pub type CallbackOnce = Box<dyn callback::BoxedCallbackOnce>;
+pub type CallbackOnceArg<A> = Box<dyn callback::BoxedCallbackOnceArg<A>>;
 
 pub fn spawn_local<F, A>(future: F, callback: CallbackArg<A>)
 where
@@ -131,6 +157,17 @@ where
     }
 }
 
+impl<C> FromIterator<Command<C>> for Checklist<C>
+where
+    C: 'static + Component,
+{
+    fn from_iter<I: IntoIterator<Item = Command<C>>>(cmds: I) -> Self {
+        let mut checklist = C::default_checklist();
+        checklist.extend_commands(cmds.into_iter().map(OptionCommand::from));
+        checklist
+    }
+}
+
 impl<C> From<Option<Command<C>>> for OptionCommand<C> {
     fn from(cmd: Option<Command<C>>) -> Self {
         OptionCommand(cmd.map(|cmd| cmd.0))
@@ -168,3 +205,165 @@ impl<T: wasm_bindgen::JsCast> WsRef<T> {
         }
     }
 }
+
+pub struct WsRefList<T>(std::cell::RefCell<Vec<Option<T>>>);
+
+impl<T: wasm_bindgen::JsCast> Default for WsRefList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: wasm_bindgen::JsCast> WsRefList<T> {
+    pub fn new() -> Self {
+        Self(std::cell::RefCell::new(Vec::new()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<std::cell::Ref<T>> {
+        std::cell::Ref::filter_map(self.0.borrow(), |list| {
+            list.get(index).and_then(|item| item.as_ref())
+        })
+        .ok()
+    }
+
+    /// Store the element at the given position in the list. Call this in the
+    /// render function of a list with the index of the item, the list does not
+    /// track the items by itself:
+    /// * The index is supplied by the caller, such as the `index` of
+    ///   `indexed_list_with_render()` or an index from `.enumerate()` on the items.
+    /// * For a keyed list, the index must be the position of the item in the
+    ///   current render, and `set_at` must be called for every item on every render.
+    ///   Otherwise, the refs are misaligned after the items are reordered.
+    /// * Refs of removed items are kept until [`WsRefList::truncate`] is called
+    ///   with the new number of items after the list is rendered.
+    pub fn set_at<C: component::Component>(
+        &self,
+        index: usize,
+        element: &crate::render::html::HtmlElementUpdater<C>,
+    ) {
+        self.store_at(index, element.ws_element().unchecked_into::<T>());
+    }
+
+    /// Same as [`WsRefList::set_at`], for an element of an svg list.
+    #[cfg(feature = "svg")]
+    pub fn set_svg_at<C: component::Component>(
+        &self,
+        index: usize,
+        element: &crate::render::svg::SvgElementUpdater<C>,
+    ) {
+        self.store_at(index, element.ws_element().unchecked_into::<T>());
+    }
+
+    fn store_at(&self, index: usize, element: T) {
+        let mut list = self.0.borrow_mut();
+        if index >= list.len() {
+            list.resize_with(index + 1, || None);
+        }
+        list[index] = Some(element);
+    }
+
+    /// Drop refs at positions `>= len`. Call this after rendering a list with
+    /// the number of items, so refs to removed items do not keep hanging around.
+    pub fn truncate(&self, len: usize) {
+        self.0.borrow_mut().truncate(len);
+    }
+
+    pub fn clear(&self) {
+        self.0.borrow_mut().clear();
+    }
+
+    pub fn execute_at(&self, index: usize, f: impl FnOnce(&T)) {
+        if let Some(t) = self.get(index) {
+            f(&t);
+        }
+    }
+
+    pub fn for_each(&self, mut f: impl FnMut(usize, &T)) {
+        self.0
+            .borrow()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| item.as_ref().map(|t| (index, t)))
+            .for_each(|(index, t)| f(index, t));
+    }
+}
+
+#[cfg(test)]
+mod ws_ref_list_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::render::html::{HemsForList, MethodsForHtmlElementContent};
+    use crate::render::ListElementCreation;
+    use crate::test_utils::TestElement;
+
+    #[wasm_bindgen_test]
+    fn ws_ref_list_populate_and_iterate() {
+        let mut root = TestElement::new("div");
+        let refs = super::WsRefList::<web_sys::HtmlElement>::new();
+        let render = |root: &mut TestElement, items: &[&str]| {
+            let _ = root
+                .updater(crate::dom::ElementStatus::Existing)
+                .list_with_render(
+                    items.iter().enumerate(),
+                    ListElementCreation::New,
+                    "span",
+                    |(index, item), span| {
+                        refs.set_at(index, &span);
+                        span.rupdate(*item);
+                    },
+                );
+            refs.truncate(items.len());
+        };
+
+        render(&mut root, &["a", "b", "c"]);
+        assert_eq!(3, refs.len());
+        let mut texts = Vec::new();
+        refs.for_each(|index, e| texts.push((index, e.text_content().unwrap_throw())));
+        assert_eq!(
+            vec![
+                (0, "a".to_string()),
+                (1, "b".to_string()),
+                (2, "c".to_string())
+            ],
+            texts
+        );
+        assert_eq!(
+            Some("b"),
+            refs.get(1).and_then(|e| e.text_content()).as_deref()
+        );
+
+        render(&mut root, &["x"]);
+        assert_eq!(1, refs.len());
+        assert!(refs.get(1).is_none());
+    }
+
+    #[cfg(feature = "svg")]
+    #[wasm_bindgen_test]
+    fn ws_ref_list_of_svg_elements() {
+        use crate::render::html::HemsHandMade;
+        use crate::render::svg::SemsForList;
+
+        let mut root = TestElement::new("div");
+        let refs = super::WsRefList::<web_sys::Element>::new();
+        root.updater(crate::dom::ElementStatus::JustCreated)
+            .svg(|svg| {
+                let _ = svg.indexed_list_with_render(
+                    [1, 2].iter(),
+                    ListElementCreation::New,
+                    "g",
+                    |index, _, g| refs.set_svg_at(index, &g),
+                );
+            });
+        assert_eq!(2, refs.len());
+        assert_eq!(Some("g".to_string()), refs.get(1).map(|e| e.tag_name()));
+    }
+}
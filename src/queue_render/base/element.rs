@@ -1,6 +1,9 @@
+use wasm_bindgen::JsCast;
+
 use crate::{
-    component::Component,
+    component::{Component, ShouldRender},
     dom::{ElementStatus, WsElement},
+    events::Input,
     queue_render::val::{QrVal, QrValMap, QrValMapWithState, QueueRender},
     render::base::ElementUpdater,
 };
@@ -29,7 +32,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(this.value());
                 this.add_render(Box::new(q));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         }
     }
 
@@ -54,7 +57,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 let q = QrNormalAttributeMap::new(q, fn_map);
                 this.add_render(Box::new(q));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -81,7 +84,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 let q = QrNormalAttributeMapWithState::new(q, self.comp(), fn_map);
                 this.add_render(Box::new(q));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -102,7 +105,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(this.value());
                 this.add_render(Box::new(q));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         }
     }
 
@@ -125,7 +128,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(&u);
                 this.add_render(Box::new(QrPropertyMap::new(q, fn_map)));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -154,7 +157,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                     fn_map,
                 )));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -171,7 +174,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(this.value());
                 this.add_render(Box::new(q));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         }
     }
 
@@ -190,7 +193,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(&u);
                 this.add_render(Box::new(QrClassMap::new(q, fn_map)));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -209,7 +212,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(&u);
                 this.add_render(Box::new(QrClassMap::new(q, fn_map)));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -229,7 +232,7 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(&u);
                 this.add_render(Box::new(QrClassMapWithState::new(q, self.comp(), fn_map)));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
 
@@ -249,7 +252,180 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
                 q.render(&u);
                 this.add_render(Box::new(QrClassMapWithState::new(q, self.comp(), fn_map)));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         };
     }
+
+    /// Add `class_name` when `value` is `true`, remove it when `value` is `false`.
+    pub fn qr_class_if(&self, class_name: &'static str, value: &QrVal<bool>) {
+        self.qr_property(
+            move |ws: &WsElement, class_on: &bool| match class_on {
+                true => ws.add_class(class_name),
+                false => ws.remove_class(class_name),
+            },
+            value,
+        );
+    }
+
+    /// Bind the `value` of an `<input>` to a number. On `input`, the value is only
+    /// set if the text parses to a `T`, empty or invalid text leaves it unchanged.
+    /// The text of the input is only replaced when it does not already represent
+    /// the value, so typing `1.50` is not reformatted to `1.5`.
+    pub fn qr_bind_number<T>(&mut self, value: &QrVal<T>)
+    where
+        T: 'static + Copy + PartialEq + std::str::FromStr + std::fmt::Display,
+    {
+        fn parse<T: std::str::FromStr>(text: &str) -> Option<T> {
+            text.trim().parse().ok()
+        }
+
+        self.qr_property(
+            |ws: &WsElement, value: &T| {
+                let input = ws.as_ref().unchecked_ref::<web_sys::HtmlInputElement>();
+                if parse(&input.value()) != Some(*value) {
+                    input.set_value(&value.to_string());
+                }
+            },
+            value,
+        );
+
+        if self.require_set_listener() {
+            let value = value.clone();
+            let handler =
+                self.comp()
+                    .handler_arg_mut(move |_: &mut C, event: crate::events::InputEvent| {
+                        let text = event.current_target_as_input_element().map(|i| i.value());
+                        if let Some(number) = text.as_deref().and_then(parse) {
+                            value.clone().set(number);
+                        }
+                        ShouldRender::No
+                    });
+            let listener = Input::on(handler, self.element().ws_element().ws_event_target());
+            self.store_listener(listener);
+        }
+    }
+}
+
+#[cfg(test)]
+mod qr_element_tests {
+    use wasm_bindgen::{JsCast, UnwrapThrowExt};
+    use wasm_bindgen_test::*;
+
+    use crate::prelude::*;
+
+    use crate::component::Component;
+    use crate::queue_render::val::QrVal;
+
+    struct State {
+        price: QrVal<f64>,
+        quantity: QrVal<i32>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element
+                .input(|i| i.bind_value_f64(&self.price).done())
+                .input(|i| i.bind_value_i32(&self.quantity).done());
+        }
+    }
+
+    impl Application for State {
+        fn init(_: &crate::Comp<Self>) -> Self {
+            Self {
+                price: 0.5.into(),
+                quantity: 1.into(),
+            }
+        }
+    }
+
+    fn type_in(input: &web_sys::HtmlInputElement, text: &str) {
+        input.set_value(text);
+        let event = web_sys::InputEvent::new("input")
+            .expect_throw("qr_element_tests::type_in new input event");
+        input
+            .dispatch_event(&event)
+            .expect_throw("qr_element_tests::type_in dispatch");
+    }
+
+    #[wasm_bindgen_test]
+    fn bind_number_values() {
+        let (root, rc) = crate::test_utils::mount::<State>();
+        let root_element = root.ws_element().as_ref();
+        let price: web_sys::HtmlInputElement = root_element
+            .first_element_child()
+            .expect_throw("qr_element_tests::bind_number_values price")
+            .unchecked_into();
+        let quantity: web_sys::HtmlInputElement = root_element
+            .last_element_child()
+            .expect_throw("qr_element_tests::bind_number_values quantity")
+            .unchecked_into();
+        assert_eq!("0.5", price.value());
+        assert_eq!("1", quantity.value());
+
+        type_in(&price, "12");
+        type_in(&quantity, "12");
+        assert_eq!(12.0, rc.comp_instance().state().price.get());
+        assert_eq!(12, rc.comp_instance().state().quantity.get());
+
+        type_in(&price, "abc");
+        type_in(&quantity, "1.5");
+        assert_eq!(12.0, rc.comp_instance().state().price.get());
+        assert_eq!(12, rc.comp_instance().state().quantity.get());
+        assert_eq!("abc", price.value());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut State| {
+                state.price.set(5.0);
+                state.quantity.set(5);
+            })
+            .call();
+        assert_eq!("5", price.value());
+        assert_eq!("5", quantity.value());
+    }
+
+    struct Menu {
+        open: QrVal<bool>,
+    }
+
+    impl Component for Menu {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.div(|d| d.class("menu").qr_class_if("open", &self.open).done());
+        }
+    }
+
+    impl Application for Menu {
+        fn init(_: &crate::Comp<Self>) -> Self {
+            Self { open: false.into() }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn toggle_class() {
+        let (root, rc) = crate::test_utils::mount::<Menu>();
+        let class_name = || {
+            root.ws_element()
+                .as_ref()
+                .first_element_child()
+                .expect_throw("qr_element_tests::toggle_class menu")
+                .class_name()
+        };
+        let toggle = || {
+            rc.comp()
+                .callback_once_mut(|state: &mut Menu| {
+                    state.open.toggle();
+                    crate::ShouldRender::No
+                })
+                .call()
+        };
+        assert_eq!("menu", class_name());
+
+        toggle();
+        assert!(rc.comp_instance().state().open.get());
+        assert_eq!("menu open", class_name());
+
+        toggle();
+        assert_eq!("menu", class_name());
+    }
 }
@@ -8,11 +8,32 @@ use crate::{
         dom::QrListRepresentative,
         vec::{Diff, ListRender},
     },
-    render::base::ElementUpdater,
+    render::{base::ElementUpdater, ListElementCreation},
+};
+#[cfg(feature = "keyed-list")]
+use crate::{
+    dom::{KeyedElement, KeyedList, ListItemKey},
+    render::base::{KeyedListContext, KeyedListUpdater, KeyedListUpdaterContext},
 };
 
 type FnElementUpdater<C, I> = Box<dyn Fn(I, ElementUpdater<C>)>;
 
+#[cfg(feature = "keyed-list")]
+fn key_of_item<I>(item: &(ListItemKey, I)) -> &ListItemKey {
+    &item.0
+}
+
+fn list_element_creation(
+    use_template: bool,
+    template: Option<&web_sys::Element>,
+) -> ListElementCreation<'_> {
+    match template {
+        Some(template) => ListElementCreation::Template(template),
+        None if use_template => ListElementCreation::Clone,
+        None => ListElementCreation::New,
+    }
+}
+
 pub struct QrListRender<C: Component, E, I> {
     comp: Comp<C>,
     parent: web_sys::Node,
@@ -20,6 +41,9 @@ pub struct QrListRender<C: Component, E, I> {
     end_flag_node: Option<web_sys::Node>,
     element_tag: E,
     use_template: bool,
+    // The list outlives the render that creates it, so it keeps its own handle of
+    // the template of `ListElementCreation::Template`
+    template: Option<web_sys::Element>,
     fn_render: FnElementUpdater<C, I>,
     unmounted: Rc<Cell<bool>>,
 }
@@ -41,7 +65,7 @@ impl<C: Component, E: ElementTag, I: Clone> QrListRender<C, E, I> {
         parent: web_sys::Node,
         end_flag_node: Option<web_sys::Node>,
         fn_render: impl Fn(I, ElementUpdater<C>) + 'static,
-        use_template: bool,
+        mode: ListElementCreation,
     ) -> Self {
         Self {
             comp,
@@ -49,7 +73,8 @@ impl<C: Component, E: ElementTag, I: Clone> QrListRender<C, E, I> {
             nodes: Nodes::default(),
             end_flag_node,
             element_tag,
-            use_template,
+            use_template: mode.use_template(),
+            template: mode.template().cloned(),
             fn_render: Box::new(fn_render),
             unmounted: Rc::new(Cell::new(false)),
         }
@@ -73,6 +98,102 @@ impl<C: Component, E: ElementTag, I: Clone> QrListRender<C, E, I> {
         }
     }
 
+    #[cfg(feature = "keyed-list")]
+    fn render_keyed_list(
+        &mut self,
+        items: &[I],
+        diffs: Vec<Diff<I>>,
+        keys: &mut Vec<ListItemKey>,
+        keyed_list: &mut KeyedList,
+        fn_get_key: &dyn Fn(&I) -> ListItemKey,
+    ) {
+        let rc_comp = self.comp.upgrade();
+        let comp = rc_comp
+            .try_borrow()
+            .expect_throw("QrListRender::render_keyed_list::rc_comp.try_borrow().");
+        let state = comp.state();
+        if diffs.iter().any(|d| matches!(d, Diff::New)) {
+            self.keyed_all_new(state, items, keys, keyed_list, fn_get_key);
+            return;
+        }
+        for d in diffs {
+            match &d {
+                Diff::New | Diff::Render { .. } => {}
+                Diff::Push { value } => keys.push(fn_get_key(value)),
+                Diff::Pop => {
+                    keys.pop();
+                }
+                Diff::Insert { index, value } => keys.insert(*index, fn_get_key(value)),
+                Diff::RemoveAt { index } => {
+                    keys.remove(*index);
+                }
+                Diff::ReplaceAt { index, new_value } => keys[*index] = fn_get_key(new_value),
+                Diff::Move {
+                    old_index,
+                    new_index,
+                } => {
+                    let key = keys.remove(*old_index);
+                    keys.insert(*new_index, key);
+                }
+                Diff::Swap { index_1, index_2 } => keys.swap(*index_1, *index_2),
+            }
+            self.render_change(state, items, d);
+        }
+    }
+
+    // The elements are stored in `self.nodes` for the per-diff updates, they are
+    // moved into `keyed_list` while it is updated by `KeyedListUpdater`. A qr keyed
+    // list is always a full list (no `end_flag_node`), so the updater is free to
+    // append items to, or clear, the parent.
+    #[cfg(feature = "keyed-list")]
+    fn keyed_all_new(
+        &mut self,
+        state: &C,
+        items: &[I],
+        keys: &mut Vec<ListItemKey>,
+        keyed_list: &mut KeyedList,
+        fn_get_key: &dyn Fn(&I) -> ListItemKey,
+    ) {
+        debug_assert!(self.end_flag_node.is_none());
+        let mut old_items = Vec::with_capacity(keys.len());
+        while let Some(element) = self.nodes.pop_element() {
+            let key = keys
+                .pop()
+                .expect_throw("QrListRender::keyed_all_new old key");
+            old_items.push(KeyedElement::new(key, element));
+        }
+        keyed_list.set_active_items(old_items.into_iter().rev());
+
+        let items: Vec<(ListItemKey, I)> = items
+            .iter()
+            .map(|item| (fn_get_key(item), item.clone()))
+            .collect();
+        let fn_render = &self.fn_render;
+        // Like the other updates of a qr list, the selected option of a parent
+        // <select> is not set again
+        let _ = KeyedListUpdater::new(
+            KeyedListContext::new(
+                keyed_list,
+                self.element_tag,
+                items.len(),
+                &self.parent,
+                list_element_creation(self.use_template, self.template.as_ref()),
+            ),
+            KeyedListUpdaterContext::new(
+                &self.comp,
+                state,
+                key_of_item,
+                |(_, item): (ListItemKey, I), er: ElementUpdater<C>| fn_render(item, er),
+            ),
+        )
+        .update(items.into_iter());
+
+        for (index, item) in keyed_list.take_active_items().enumerate() {
+            keys.push(item.key);
+            self.nodes.insert_element_at(index, item.element);
+        }
+    }
+
     fn render_change(&mut self, state: &C, items: &[I], diff: Diff<I>) {
         match diff {
             Diff::New => self.all_new(state, items.to_vec()),
@@ -114,7 +235,7 @@ impl<C: Component, E: ElementTag, I: Clone> QrListRender<C, E, I> {
             &self.parent,
             ElementStatus::JustCreated,
             self.end_flag_node.as_ref(),
-            self.use_template,
+            list_element_creation(self.use_template, self.template.as_ref()),
         );
         let element = self.nodes.get_element_mut(index);
         let render = ElementUpdater::new(&self.comp, state, element, status);
@@ -131,7 +252,9 @@ impl<C: Component, E: ElementTag, I: Clone> QrListRender<C, E, I> {
         // An insert at the end of the list is handled by QrVec as a push
         let existing_element = self.nodes.get_element(index);
         let next_sibling = existing_element.map(|e| e.ws_node());
-        let (mut new_element, status) = if self.use_template {
+        let (mut new_element, status) = if let Some(template) = self.template.as_ref() {
+            (Element::from_template(template), ElementStatus::JustCreated)
+        } else if self.use_template {
             let new_element = existing_element
                 .expect_throw("guanrantee valid index by QrVec::insert")
                 .clone();
@@ -192,6 +315,49 @@ impl<C: Component, E: ElementTag, I: Clone> QrListRender<C, E, I> {
     }
 }
 
+/// A [`QrListRender`] that tracks the key of each item, so that the elements of
+/// the remaining items are reused when the whole list is replaced by `new_values`.
+#[cfg(feature = "keyed-list")]
+pub struct QrKeyedListRender<C: Component, E, I> {
+    list: QrListRender<C, E, I>,
+    fn_get_key: Box<dyn Fn(&I) -> ListItemKey>,
+    keys: Vec<ListItemKey>,
+    // Only used while the whole list is replaced, see `QrListRender::keyed_all_new`
+    keyed_list: KeyedList,
+}
+
+#[cfg(feature = "keyed-list")]
+impl<C: Component, E: ElementTag, I: Clone> QrKeyedListRender<C, E, I> {
+    pub fn new(
+        list: QrListRender<C, E, I>,
+        fn_get_key: impl Fn(&I) -> ListItemKey + 'static,
+    ) -> Self {
+        Self {
+            list,
+            fn_get_key: Box::new(fn_get_key),
+            keys: Vec::new(),
+            keyed_list: KeyedList::default(),
+        }
+    }
+}
+
+#[cfg(feature = "keyed-list")]
+impl<C: Component, E: ElementTag, I: Clone> ListRender<I> for QrKeyedListRender<C, E, I> {
+    fn render(&mut self, items: &[I], diffs: Vec<Diff<I>>) {
+        self.list.render_keyed_list(
+            items,
+            diffs,
+            &mut self.keys,
+            &mut self.keyed_list,
+            &self.fn_get_key,
+        );
+    }
+
+    fn unmounted(&self) -> bool {
+        self.list.unmounted()
+    }
+}
+
 #[cfg(test)]
 mod qr_list_tests {
     use wasm_bindgen_test::*;
@@ -299,4 +465,134 @@ mod qr_list_tests {
         }) }
         both_eq! { "", qr_list_test(&rc, |vec| { vec.get_mut().clear(); }) }
     }
+
+    #[cfg(feature = "keyed-list")]
+    mod keyed {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_test::*;
+
+        use crate::prelude::*;
+
+        use crate::component::Component;
+        use crate::queue_render::vec::QrVec;
+        use crate::render::html::ElementRender;
+        use crate::render::ListElementCreation;
+
+        #[derive(Clone)]
+        struct Row {
+            id: u32,
+            label: &'static str,
+        }
+
+        impl crate::dom::Keyed for Row {
+            type Key = u32;
+            fn key(&self) -> &Self::Key {
+                &self.id
+            }
+        }
+
+        impl ElementRender<State> for Row {
+            const ELEMENT_TAG: &'static str = "input";
+            fn render(self, item: crate::Element<State>) {
+                item.value(self.label);
+            }
+        }
+
+        struct State {
+            rows: QrVec<Row>,
+        }
+
+        impl Component for State {
+            type Routes = ();
+            fn render(&self, element: crate::Element<Self>) {
+                element.div(|d| {
+                    d.qr_keyed_list(&self.rows, ListElementCreation::Clone);
+                });
+            }
+        }
+
+        impl Application for State {
+            fn init(_comp: &crate::Comp<Self>) -> Self {
+                Self {
+                    rows: QrVec::with_values(vec![
+                        Row {
+                            id: 1,
+                            label: "one",
+                        },
+                        Row {
+                            id: 2,
+                            label: "two",
+                        },
+                    ]),
+                }
+            }
+        }
+
+        fn inputs(root: &crate::dom::Element) -> Vec<web_sys::HtmlInputElement> {
+            let list = root
+                .ws_element()
+                .as_ref()
+                .query_selector_all("input")
+                .expect_throw("qr_list_tests::keyed::inputs");
+            (0..list.length())
+                .filter_map(|index| list.get(index))
+                .map(|node| node.unchecked_into())
+                .collect()
+        }
+
+        fn values(root: &crate::dom::Element) -> Vec<String> {
+            inputs(root).iter().map(|input| input.value()).collect()
+        }
+
+        #[wasm_bindgen_test]
+        fn keep_nodes_and_focus() {
+            let (root, rc) = crate::test_utils::mount_in_body::<State>();
+            crate::queue_render::execute_render_queue();
+            assert_eq!(vec!["one", "two"], values(&root));
+
+            let old = inputs(&root);
+            old[1].focus().expect_throw("qr_list_tests::keyed focus");
+            let is_focused = |input: &web_sys::HtmlInputElement| {
+                crate::utils::document().active_element().as_ref() == Some(input.as_ref())
+            };
+
+            rc.comp_instance()
+                .state()
+                .rows
+                .get_mut()
+                .insert_at(
+                    0,
+                    Row {
+                        id: 0,
+                        label: "zero",
+                    },
+                )
+                .expect_throw("insert at 0");
+            crate::queue_render::execute_render_queue();
+            assert_eq!(vec!["zero", "one", "two"], values(&root));
+            assert_eq!(old[..], inputs(&root)[1..]);
+            assert!(is_focused(&old[1]));
+
+            rc.comp_instance().state().rows.get_mut().new_values(vec![
+                Row {
+                    id: 3,
+                    label: "three",
+                },
+                Row {
+                    id: 1,
+                    label: "one",
+                },
+                Row {
+                    id: 2,
+                    label: "TWO",
+                },
+            ]);
+            crate::queue_render::execute_render_queue();
+            assert_eq!(vec!["three", "one", "TWO"], values(&root));
+            assert_eq!(old[..], inputs(&root)[1..]);
+            assert!(is_focused(&old[1]));
+
+            root.ws_element().as_ref().remove();
+        }
+    }
 }
@@ -75,7 +75,7 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
                 self.parent().clone(),
                 end_flag_node,
                 fn_render,
-                mode.use_template(),
+                mode,
             );
             self.nodes_mut()
                 .add_qr_node(QrNode::List(list.make_representative()));
@@ -89,14 +89,7 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
                 QrNode::List(_) => None,
                 QrNode::ClonedWsNode(wsn) => match wsn.take() {
                     Some(wsn) => {
-                        let list = QrListRender::new(
-                            tag,
-                            comp,
-                            parent,
-                            Some(wsn),
-                            fn_render,
-                            mode.use_template(),
-                        );
+                        let list = QrListRender::new(tag, comp, parent, Some(wsn), fn_render, mode);
                         *qr_node = QrNode::List(list.make_representative());
                         Some(list)
                     }
@@ -1,5 +1,11 @@
 use wasm_bindgen::UnwrapThrowExt;
 
+#[cfg(feature = "keyed-list")]
+use crate::{
+    dom::{Keyed, ListItemKey},
+    queue_render::base::QrKeyedListRender,
+};
+
 use crate::{queue_render::vec::QrVec, render::html::ElementRender};
 
 use crate::{
@@ -65,6 +71,55 @@ pub trait HemsForQrList<'a, C: Component>: Sized + Into<NodesOwned<'a, C>> {
     {
         self.qr_list_with_render(list, ListElementCreation::Clone, I::ELEMENT_TAG, I::render)
     }
+
+    /// Same as `qr_list_with_render`, but the elements are associated with the keys of
+    /// the items. When the whole list is replaced (by `new_values`), the elements of the
+    /// items whose keys are still in the list are reused (and only moved if their
+    /// positions change) instead of being rendered from scratch.
+    #[cfg(feature = "keyed-list")]
+    fn qr_keyed_list_with_render<I, G, K, R>(
+        self,
+        list: &QrVec<I>,
+        mode: ListElementCreation,
+        tag: &'static str,
+        fn_get_key: G,
+        render: R,
+    ) where
+        I: 'static + Clone,
+        G: 'static + Fn(&I) -> &K,
+        R: 'static + Fn(I, crate::Element<C>),
+        ListItemKey: for<'k> From<&'k K>,
+    {
+        let mut nodes_updater: NodesOwned<C> = self.into();
+        let qr_list_render = match nodes_updater.nodes_updater_mut().create_qr_list_render(
+            true,
+            mode,
+            HtmlTag(tag),
+            move |item: I, er: ElementUpdater<C>| render(item, er.into()),
+        ) {
+            None => return,
+            Some(render) => render,
+        };
+        list.content()
+            .try_borrow_mut()
+            .expect_throw(
+                "queue_render::html::list::HemsForQrList::qr_keyed_list_with_render content borrow mut",
+            )
+            .add_render(Box::new(QrKeyedListRender::new(
+                qr_list_render,
+                move |item: &I| ListItemKey::from(fn_get_key(item)),
+            )));
+        list.check_and_queue_a_render();
+    }
+
+    #[cfg(feature = "keyed-list")]
+    fn qr_keyed_list<I>(self, list: &QrVec<I>, mode: ListElementCreation)
+    where
+        I: 'static + Clone + Keyed + ElementRender<C>,
+        ListItemKey: for<'k> From<&'k <I as Keyed>::Key>,
+    {
+        self.qr_keyed_list_with_render(list, mode, I::ELEMENT_TAG, I::key, I::render)
+    }
 }
 
 impl<'a, C: Component> HemsForQrList<'a, C> for HtmlElementUpdater<'a, C> {}
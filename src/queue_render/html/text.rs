@@ -28,7 +28,7 @@ where
                     text_node.update_text(&this.value().to_string());
                     this.add_render(Box::new(text_node));
                 }
-                Err(e) => log::error!("{}", e),
+                Err(e) => crate::diagnostics::error(format_args!("{}", e)),
             }
         }
     }
@@ -50,7 +50,7 @@ where
                     map_node.update_text(&u.to_string());
                     this.add_render(Box::new(map_node));
                 }
-                Err(e) => log::error!("{}", e),
+                Err(e) => crate::diagnostics::error(format_args!("{}", e)),
             };
         }
     }
@@ -74,7 +74,7 @@ where
                     map_node.update_text(&u.to_string());
                     this.add_render(Box::new(map_node));
                 }
-                Err(e) => log::error!("{}", e),
+                Err(e) => crate::diagnostics::error(format_args!("{}", e)),
             };
         }
     }
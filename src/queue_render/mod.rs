@@ -1,4 +1,7 @@
-use std::{cell::RefCell, collections::VecDeque};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+};
 
 pub mod base;
 pub mod dom;
@@ -13,21 +16,45 @@ type FnMapC<C, T, U> = Box<dyn Fn(&C, &T) -> U>;
 
 struct RenderQueue {
     queue: RefCell<VecDeque<Box<dyn FnOnce()>>>,
+    a_flush_is_scheduled: Cell<bool>,
 }
 
 thread_local! {
     static RENDER_QUEUE: RenderQueue = RenderQueue {
-        queue: RefCell::new(VecDeque::new())
+        queue: RefCell::new(VecDeque::new()),
+        a_flush_is_scheduled: Cell::new(false),
     };
 }
 
+// Number of flushes that executed some renders, to check the batching in tests
+#[cfg(test)]
+thread_local! {
+    static FLUSHES: Cell<u32> = const { Cell::new(0) };
+}
+
 fn queue_render(fn_render: impl FnOnce() + 'static) {
     RENDER_QUEUE.with(|rq| rq.add(Box::new(fn_render)));
 }
 
 impl RenderQueue {
+    // The queue is executed after a component update. But values may also be changed
+    // outside of any update (e.g. in a timer), so a flush is scheduled in a microtask
+    // for the first render queued in the current tick. All renders queued in the same
+    // tick are executed by that flush, each value only queues itself once.
     fn add(&self, f: Box<dyn FnOnce()>) {
         self.queue.borrow_mut().push_back(f);
+        if !self.a_flush_is_scheduled.replace(true) {
+            wasm_bindgen_futures::spawn_local(async { RENDER_QUEUE.with(|rq| rq.flush()) });
+        }
+    }
+
+    fn flush(&self) {
+        self.a_flush_is_scheduled.set(false);
+        #[cfg(test)]
+        if !self.queue.borrow().is_empty() {
+            FLUSHES.with(|flushes| flushes.set(flushes.get() + 1));
+        }
+        self.execute();
     }
 
     fn take(&self) -> Option<Box<dyn FnOnce()>> {
@@ -44,3 +71,64 @@ impl RenderQueue {
 pub fn execute_render_queue() {
     RENDER_QUEUE.with(|uq| uq.execute());
 }
+
+#[cfg(test)]
+mod render_queue_tests {
+    use wasm_bindgen_test::*;
+
+    use crate::prelude::*;
+
+    use crate::component::Component;
+    use crate::queue_render::val::QrVal;
+    use crate::render::ListElementCreation;
+    use crate::test_utils::sleep;
+
+    struct State {
+        values: Vec<QrVal<u32>>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.list_with_render(
+                self.values.iter(),
+                ListElementCreation::New,
+                "span",
+                |value, span| {
+                    span.rupdate(value);
+                },
+            );
+        }
+    }
+
+    impl Application for State {
+        fn init(_: &crate::Comp<Self>) -> Self {
+            Self {
+                values: (0..10).map(|_| 0.into()).collect(),
+            }
+        }
+    }
+
+    fn flushes() -> u32 {
+        super::FLUSHES.with(|flushes| flushes.get())
+    }
+
+    #[wasm_bindgen_test]
+    async fn flush_once_per_tick() {
+        let (root, rc) = crate::test_utils::mount::<State>();
+        let text = || root.ws_element().as_ref().text_content();
+        assert_eq!(Some("0000000000".to_string()), text());
+        sleep(0).await;
+        let flushes_before = flushes();
+
+        for (index, value) in rc.comp_instance().state().values.iter().enumerate() {
+            value.clone().set(index as u32);
+        }
+        // Nothing is rendered until the flush
+        assert_eq!(Some("0000000000".to_string()), text());
+
+        sleep(0).await;
+        assert_eq!(Some("0123456789".to_string()), text());
+        assert_eq!(flushes_before + 1, flushes());
+    }
+}
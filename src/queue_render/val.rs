@@ -83,7 +83,7 @@ impl<T: 'static + PartialEq + Copy> QrVal<T> {
 }
 
 impl<T: 'static + PartialEq> QrVal<T> {
-    fn clone(&self) -> Self {
+    pub(crate) fn clone(&self) -> Self {
         Self(self.0.clone())
     }
 
@@ -102,7 +102,7 @@ impl<T: 'static + PartialEq> QrVal<T> {
             }
             Ok(_) => false,
             Err(e) => {
-                log::error!("{}", e);
+                crate::diagnostics::error(format_args!("{}", e));
                 return;
             }
         };
@@ -121,7 +121,7 @@ impl<T: 'static + PartialEq> QrVal<T> {
                 }
             }
             Err(e) => {
-                log::error!("{}", e);
+                crate::diagnostics::error(format_args!("{}", e));
                 return;
             }
         };
@@ -131,7 +131,9 @@ impl<T: 'static + PartialEq> QrVal<T> {
     fn render(&self) {
         match self.0.try_borrow_mut() {
             Ok(mut this) => this.render(),
-            Err(e) => log::error!("queue_render::value::Value::render: {}", e),
+            Err(e) => {
+                crate::diagnostics::error(format_args!("queue_render::value::Value::render: {}", e))
+            }
         }
     }
 
@@ -156,6 +158,13 @@ impl<T: 'static + PartialEq> QrVal<T> {
     }
 }
 
+impl QrVal<bool> {
+    /// Flip the value, like `set(!value)`.
+    pub fn toggle(&mut self) {
+        self.set_with(|value| !value);
+    }
+}
+
 pub trait QueueRender<T> {
     fn render(&mut self, t: &T);
     fn unmounted(&self) -> bool;
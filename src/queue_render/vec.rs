@@ -35,16 +35,23 @@ pub struct QrVecContent<I: Clone> {
     values: Vec<I>,
     a_render_is_queued: bool,
     diffs: Vec<Diff<I>>,
-    // TODO: remove dropped renders
     renders: Vec<Box<dyn ListRender<I>>>,
 }
 
 impl<I: Clone> QrVecContent<I> {
     pub(crate) fn add_render(&mut self, r: Box<dyn ListRender<I>>) {
+        self.remove_unmounted_renders();
         self.renders.push(r);
     }
 
+    // A QrVec may outlive the components that render it, the renders of
+    // the lists that were removed from the DOM must be dropped.
+    fn remove_unmounted_renders(&mut self) {
+        self.renders.retain(|r| !r.unmounted());
+    }
+
     fn render(&mut self) {
+        self.remove_unmounted_renders();
         if self.renders.is_empty() {
             return;
         }
@@ -322,7 +329,9 @@ impl<I: 'static + Clone> QrVec<I> {
     fn render(&self) {
         match self.0.try_borrow_mut() {
             Ok(mut this) => this.render(),
-            Err(e) => log::error!("queue_render::vec::QrVec::render {}", e),
+            Err(e) => {
+                crate::diagnostics::error(format_args!("queue_render::vec::QrVec::render {}", e))
+            }
         }
     }
 }
@@ -423,7 +432,7 @@ impl<I: 'static + Clone> QrVec<I> {
                 };
                 this.add_render(Box::new(osi));
             }
-            Err(e) => log::error!("{}", e),
+            Err(e) => crate::diagnostics::error(format_args!("{}", e)),
         }
         selected_id
     }
@@ -468,3 +477,61 @@ pub enum QrVecError {
     #[error("Index value = {0} is out of bounds")]
     IndexOutBounds(usize),
 }
+
+#[cfg(test)]
+mod qr_vec_tests {
+    use wasm_bindgen_test::*;
+
+    use crate::prelude::*;
+
+    use super::QrVec;
+    use crate::render::ListElementCreation;
+
+    // A vec that lives longer than the components that render it.
+    struct SharedVec(QrVec<u32>);
+
+    struct Viewer {
+        vec: QrVec<u32>,
+    }
+
+    impl Component for Viewer {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.div(|d| {
+                d.qr_list_with_render(
+                    &self.vec,
+                    ListElementCreation::New,
+                    "strong",
+                    |item: u32, s: crate::Element<Self>| {
+                        s.rupdate(item);
+                    },
+                );
+            });
+        }
+    }
+
+    impl Application for Viewer {
+        fn init(_: &crate::Comp<Self>) -> Self {
+            let shared = crate::use_context::<SharedVec>().expect_throw("The shared vec");
+            Self {
+                vec: shared.0.clone(),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn remove_renders_of_unmounted_lists() {
+        let vec = QrVec::with_values(vec![1]);
+        crate::provide_context(SharedVec(vec.clone()));
+        for value in 2..6 {
+            let (_root, rc) = crate::test_utils::mount::<Viewer>();
+            vec.get_mut().push(value);
+            crate::queue_render::execute_render_queue();
+            assert_eq!(1, vec.get_ref().renders.len());
+            drop(rc);
+        }
+        vec.get_mut().push(6);
+        crate::queue_render::execute_render_queue();
+        assert_eq!(0, vec.get_ref().renders.len());
+    }
+}
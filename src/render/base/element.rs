@@ -1,7 +1,7 @@
 use super::ListUpdater;
 use crate::{
     component::{Comp, Component},
-    dom::{AttributeValueList, Element, ElementStatus},
+    dom::{AttributeValueList, Element, ElementStatus, Slot, SlotName},
     render::ListElementCreation,
 };
 use wasm_bindgen::UnwrapThrowExt;
@@ -39,7 +39,26 @@ pub struct ElementUpdater<'a, C: Component> {
     update_mode: bool,
     index: usize,
     status: ElementStatus,
-    element: &'a mut Element,
+    // `href_external` is called by the current render, `rel` is checked when the
+    // attributes are done, after `target` is set wherever it is in the render.
+    external_href: bool,
+    // Only `None` after `into_parts`
+    element: Option<&'a mut Element>,
+}
+
+impl<'a, C: Component> Drop for ElementUpdater<'a, C> {
+    fn drop(&mut self) {
+        self.clear_unset_slots();
+    }
+}
+
+fn set_safe_rel_for_target_blank(element: &web_sys::Element) {
+    if element.get_attribute("target").as_deref() == Some("_blank") && !element.has_attribute("rel")
+    {
+        element
+            .set_attribute("rel", "noopener noreferrer")
+            .expect_throw("render::base::element::set_safe_rel_for_target_blank");
+    }
 }
 
 impl<'a, C: Component> ElementUpdater<'a, C> {
@@ -55,7 +74,8 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
             update_mode: true,
             index: 0,
             status,
-            element,
+            external_href: false,
+            element: Some(element),
         }
     }
 
@@ -67,23 +87,65 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
         self.comp.clone()
     }
 
-    pub fn into_parts(self) -> (&'a Comp<C>, &'a C, ElementStatus, &'a mut Element) {
-        (self.comp, self.state, self.status, self.element)
+    pub fn into_parts(mut self) -> (&'a Comp<C>, &'a C, ElementStatus, &'a mut Element) {
+        self.clear_unset_slots();
+        let element = self
+            .element
+            .take()
+            .expect_throw("render::base::element::ElementUpdater::into_parts");
+        (self.comp, self.state, self.status, element)
     }
 
     pub fn element(&self) -> &Element {
         self.element
+            .as_deref()
+            .expect_throw("render::base::element::ElementUpdater::element")
     }
 
     pub fn element_mut(&mut self) -> &mut Element {
         self.element
+            .as_deref_mut()
+            .expect_throw("render::base::element::ElementUpdater::element_mut")
     }
 
-    #[cfg(feature = "queue-render")]
-    pub(crate) fn status(&self) -> ElementStatus {
+    // Attributes are set before the child nodes, so the attributes of the element are
+    // done when the updater is converted to render the nodes, or it is dropped.
+    fn clear_unset_slots(&mut self) {
+        if let Some(element) = self.element.as_deref_mut() {
+            element.clear_unset_slots(self.index);
+            if self.external_href {
+                set_safe_rel_for_target_blank(element.ws_element().as_ref());
+            }
+        }
+    }
+
+    pub fn status(&self) -> ElementStatus {
         self.status
     }
 
+    /// Execute `f` if the element is not an existing element, it means that the
+    /// element is just created or cloned (a list item may be cloned from another item).
+    pub fn once_created(&self, f: impl FnOnce(&web_sys::Element)) {
+        if self.status != ElementStatus::Existing {
+            f(self.element().ws_element().as_ref());
+        }
+    }
+
+    /// Execute `f` with the element on every render.
+    pub fn with_ws_element(&self, f: impl FnOnce(&web_sys::Element)) {
+        f(self.element().ws_element().as_ref());
+    }
+
+    /// Execute `f` with the bounding client rect of the element, in a microtask
+    /// after the current render, when a just-created element is already inserted
+    /// into the document. Reading the rect forces the browser to compute the layout.
+    pub fn measure(&self, f: impl FnOnce(web_sys::DomRect) + 'static) {
+        let element = self.element().ws_element().as_ref().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            f(element.get_bounding_client_rect());
+        });
+    }
+
     // pub(crate) fn index(&self) -> usize {
     //     self.index
     // }
@@ -113,6 +175,8 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
             if self.status == ElementStatus::Existing {
                 // self.store_listener will not be invoked.
                 // We must update the index here to count over the static event.
+                let index = self.index;
+                self.element_mut().attribute_list_mut().mark_seen(index);
                 self.index += 1;
                 false
             } else {
@@ -127,118 +191,261 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
 
     pub fn store_listener(&mut self, listener: Box<dyn crate::events::Listener>) {
         let index = self.index;
-        self.element_mut()
-            .attribute_list_mut()
-            .store_listener(index, listener);
+        let attribute_list = self.element_mut().attribute_list_mut();
+        attribute_list.set_slot(index, Slot::Plain);
+        attribute_list.store_listener(index, listener);
         self.index += 1;
     }
 
-    pub fn must_update_attribute<T>(
+    /// Check the value of the slot at the current index, `slot` tells what the slot is
+    /// used for, to undo it when a later render does not set the slot. Returns `true` if
+    /// the value must be set.
+    pub fn must_update_slot<T>(
         &mut self,
+        slot: Slot,
         value: T,
         check: impl FnOnce(&mut AttributeValueList, usize, T) -> bool,
     ) -> bool {
         if self.is_static_mode() {
             self.status == ElementStatus::JustCreated
         } else {
-            let rs = check(self.element.attribute_list_mut(), self.index, value);
+            let index = self.index;
+            let attribute_list = self.element_mut().attribute_list_mut();
+            let changed_slot = attribute_list.set_slot(index, slot);
+            let rs = check(attribute_list, index, value);
             self.index += 1;
-            rs
+            rs || changed_slot
         }
     }
 
-    pub fn set_bool_attribute(&mut self, name: &str, value: bool) {
-        if !self.must_update_attribute(value, AttributeValueList::check_bool_attribute) {
+    /// Check the value of a slot that has nothing to undo (such as an action like
+    /// `focus`), returns `true` if the value must be set.
+    pub fn must_update_attribute<T>(
+        &mut self,
+        value: T,
+        check: impl FnOnce(&mut AttributeValueList, usize, T) -> bool,
+    ) -> bool {
+        self.must_update_slot(Slot::Plain, value, check)
+    }
+
+    /// Same as `must_update_attribute`, but the slot is the attribute `name`. An
+    /// attribute that is set in update mode by the last render but not by the current
+    /// render is removed from the element.
+    pub fn must_update_named_attribute<T>(
+        &mut self,
+        name: &'static str,
+        value: T,
+        check: impl FnOnce(&mut AttributeValueList, usize, T) -> bool,
+    ) -> bool {
+        self.must_update_slot(Slot::Attribute(SlotName::Static(name)), value, check)
+    }
+
+    /// Same as `must_update_attribute`, but the slot is the property `name`. A property
+    /// that is set in update mode by the last render but not by the current render is
+    /// set to `null`.
+    pub fn must_update_property<T>(
+        &mut self,
+        name: &'static str,
+        value: T,
+        check: impl FnOnce(&mut AttributeValueList, usize, T) -> bool,
+    ) -> bool {
+        self.must_update_slot(Slot::Property(SlotName::Static(name)), value, check)
+    }
+
+    pub fn set_bool_attribute(&mut self, name: &'static str, value: bool) {
+        if !self.must_update_named_attribute(name, value, AttributeValueList::check_bool_attribute)
+        {
+            return;
+        }
+        self.element().ws_element().set_bool_attribute(name, value);
+    }
+
+    pub fn set_str_attribute(&mut self, name: &'static str, value: &str) {
+        if !self.must_update_named_attribute(name, value, AttributeValueList::check_str_attribute) {
+            return;
+        }
+        self.element().ws_element().set_str_attribute(name, value);
+    }
+
+    /// Same as `set_str_attribute`, for a name that is not known at compile time. The
+    /// name is copied into the slot.
+    pub fn set_runtime_str_attribute(&mut self, name: &str, value: &str) {
+        if !self.must_update_slot(
+            Slot::Attribute(SlotName::Runtime(name)),
+            value,
+            AttributeValueList::check_str_attribute,
+        ) {
+            return;
+        }
+        self.element().ws_element().set_str_attribute(name, value);
+    }
+
+    pub fn set_string_attribute(&mut self, name: &'static str, value: String) {
+        if !self.must_update_named_attribute(
+            name,
+            value.as_str(),
+            AttributeValueList::check_str_attribute,
+        ) {
             return;
         }
-        self.element.ws_element().set_bool_attribute(name, value);
+        self.element().ws_element().set_str_attribute(name, &value);
     }
 
-    pub fn set_str_attribute(&mut self, name: &str, value: &str) {
-        if !self.must_update_attribute(value, AttributeValueList::check_str_attribute) {
+    pub fn set_i32_attribute(&mut self, name: &'static str, value: i32) {
+        if !self.must_update_named_attribute(name, value, AttributeValueList::check_i32_attribute) {
             return;
         }
-        self.element.ws_element().set_str_attribute(name, value);
+        self.element().ws_element().set_attribute(name, value);
     }
 
-    pub fn set_string_attribute(&mut self, name: &str, value: String) {
-        if !self.must_update_attribute(value.as_str(), AttributeValueList::check_str_attribute) {
+    pub fn set_u32_attribute(&mut self, name: &'static str, value: u32) {
+        if !self.must_update_named_attribute(name, value, AttributeValueList::check_u32_attribute) {
             return;
         }
-        self.element.ws_element().set_str_attribute(name, &value);
+        self.element().ws_element().set_attribute(name, value);
     }
 
-    pub fn set_i32_attribute(&mut self, name: &str, value: i32) {
-        if !self.must_update_attribute(value, AttributeValueList::check_i32_attribute) {
+    pub fn set_f64_attribute(&mut self, name: &'static str, value: f64) {
+        if !self.must_update_named_attribute(name, value, AttributeValueList::check_f64_attribute) {
             return;
         }
-        self.element.ws_element().set_attribute(name, value);
+        self.element().ws_element().set_attribute(name, value);
     }
 
-    pub fn set_u32_attribute(&mut self, name: &str, value: u32) {
-        if !self.must_update_attribute(value, AttributeValueList::check_u32_attribute) {
+    /// Set a JS property (not an attribute) of the element. The value is
+    /// only set if it is different from the value of the last render.
+    pub fn set_property(&mut self, name: &str, value: wasm_bindgen::JsValue) {
+        if !self.must_update_slot(
+            Slot::Property(SlotName::Runtime(name)),
+            &value,
+            AttributeValueList::check_js_value,
+        ) {
             return;
         }
-        self.element.ws_element().set_attribute(name, value);
+        self.element().ws_element().set_property(name, &value);
     }
 
-    pub fn set_f64_attribute(&mut self, name: &str, value: f64) {
-        if !self.must_update_attribute(value, AttributeValueList::check_f64_attribute) {
+    pub fn value_number(&mut self, value: f64) {
+        // `value` is cleared if a later render does not set the number
+        if !self.must_update_property("value", value, AttributeValueList::check_f64_attribute) {
             return;
         }
-        self.element.ws_element().set_attribute(name, value);
+        self.element().ws_element().set_value_as_number(value);
     }
 
     /// Always checked.
     pub fn checked(&self, value: bool) {
-        self.element.ws_element().checked(value);
+        self.element().ws_element().checked(value);
     }
 
     pub fn enabled(&self, value: bool) {
-        self.element.ws_element().enabled(value);
+        self.element().ws_element().enabled(value);
     }
 
-    pub fn class(&mut self, class_name: &str) {
-        let (changed, old_value) = if self.is_update_mode() {
-            let rs = self
-                .element
-                .attribute_list_mut()
-                .check_str_attribute_and_return_old_value(self.index, class_name);
+    // Check the value of a slot of classes, returns `true` if the classes changed, and
+    // the classes of the last render (if they are known).
+    fn check_classes(&mut self, class_names: &str) -> (bool, Option<String>) {
+        self.check_classes_with(|attribute_list, index| {
+            attribute_list.check_str_attribute_and_return_old_value(index, class_names)
+        })
+    }
+
+    fn check_class_names(&mut self, class_names: &[&str]) -> (bool, Option<String>) {
+        self.check_classes_with(|attribute_list, index| {
+            attribute_list.check_class_names_and_return_old_value(index, class_names)
+        })
+    }
+
+    fn check_classes_with(
+        &mut self,
+        check: impl FnOnce(&mut AttributeValueList, usize) -> (bool, Option<String>),
+    ) -> (bool, Option<String>) {
+        if self.is_update_mode() {
+            let index = self.index;
+            let attribute_list = self.element_mut().attribute_list_mut();
+            attribute_list.set_slot(index, Slot::Classes);
+            let rs = check(attribute_list, index);
             self.index += 1;
             rs
         } else {
             (self.status == ElementStatus::JustCreated, None)
-        };
+        }
+    }
+
+    pub fn class(&mut self, class_name: &str) {
+        let (changed, old_value) = self.check_classes(class_name);
         if let Some(old_value) = old_value {
-            self.element.ws_element().remove_class(&old_value);
+            self.element().ws_element().remove_class(&old_value);
         }
         if changed {
-            self.element.ws_element().add_class(class_name);
+            self.element().ws_element().add_class(class_name);
+        }
+    }
+
+    /// Set classes from a whitespace-separated list via `classList`. Only tokens that
+    /// are removed from the list since the last render are removed from the element,
+    /// classes added by other code are kept.
+    pub fn class_tokens(&mut self, class_names: &str) {
+        let (changed, old_value) = self.check_classes(class_names);
+        if !changed {
+            return;
+        }
+        let ws_element = self.element().ws_element();
+        if let Some(old_value) = old_value {
+            old_value
+                .split_whitespace()
+                .filter(|old| !class_names.split_whitespace().any(|new| new == *old))
+                .for_each(|old| ws_element.remove_class(old));
         }
+        class_names
+            .split_whitespace()
+            .for_each(|new| ws_element.add_class(new));
     }
 
-    /// Make sure that value of `class_name` does not change between calls.
+    // The slot stores the class name when it is on, or an empty string.
     pub fn class_if(&mut self, class_on: bool, class_name: &str) {
-        if !self.must_update_attribute(class_on, AttributeValueList::check_bool_attribute) {
+        let (changed, old_value) = self.check_classes(if class_on { class_name } else { "" });
+        if let Some(old_value) = old_value.filter(|old_value| !old_value.is_empty()) {
+            self.element().ws_element().remove_class(&old_value);
+        }
+        if changed && class_on {
+            self.element().ws_element().add_class(class_name);
+        }
+    }
+
+    /// The slot stores the names of the classes that are on (or an empty string), so
+    /// `class_names` may change between renders: classes that are no longer in the
+    /// group are removed.
+    pub fn classes_if(&mut self, class_on: bool, class_names: &[&str]) {
+        let names_on = if class_on { class_names } else { &[] };
+        let (changed, old_value) = self.check_class_names(names_on);
+        if !changed {
             return;
         }
-        if class_on {
-            self.element.ws_element().add_class(class_name);
-        } else {
-            self.element.ws_element().remove_class(class_name);
+        let ws_element = self.element().ws_element();
+        if let Some(old_value) = old_value {
+            let stale: Vec<&str> = old_value
+                .split_whitespace()
+                .filter(|old| !names_on.contains(old))
+                .collect();
+            if !stale.is_empty() {
+                ws_element.remove_classes(&stale);
+            }
+        }
+        if !names_on.is_empty() {
+            ws_element.add_classes(names_on);
         }
     }
 
+    // The slot stores the class that is on.
     pub fn class_or(&mut self, first: bool, first_class: &str, second_class: &str) {
-        if !self.must_update_attribute(first, AttributeValueList::check_bool_attribute) {
-            return;
+        let class_name = if first { first_class } else { second_class };
+        let (changed, old_value) = self.check_classes(class_name);
+        if let Some(old_value) = old_value {
+            self.element().ws_element().remove_class(&old_value);
         }
-        if first {
-            self.element.ws_element().add_class(first_class);
-            self.element.ws_element().remove_class(second_class);
-        } else {
-            self.element.ws_element().remove_class(first_class);
-            self.element.ws_element().add_class(second_class);
+        if changed {
+            self.element().ws_element().add_class(class_name);
         }
     }
 
@@ -247,34 +454,159 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
             return;
         }
         if value {
-            self.element
+            self.element()
                 .ws_html_element()
                 .focus()
                 .expect_throw("render::base::element::ElementUpdater::focus");
         }
     }
 
+    /// Like `focus`, but the element is focused in a microtask, after the current
+    /// render completes. A just-created element is not in the document yet while it
+    /// is being rendered, hence, focusing it immediately does nothing.
+    pub fn focus_when_attached(&mut self, value: bool) {
+        if !self.must_update_attribute(value, AttributeValueList::check_bool_attribute) {
+            return;
+        }
+        if value {
+            let element = self.element().ws_html_element().clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                element
+                    .focus()
+                    .expect_throw("render::base::element::ElementUpdater::focus_when_attached");
+            });
+        }
+    }
+
+    pub fn blur(&mut self, value: bool) {
+        if !self.must_update_attribute(value, AttributeValueList::check_bool_attribute) {
+            return;
+        }
+        if value {
+            self.element()
+                .ws_html_element()
+                .blur()
+                .expect_throw("render::base::element::ElementUpdater::blur");
+        }
+    }
+
+    pub fn select_text(&mut self, value: bool) {
+        if !self.must_update_attribute(value, AttributeValueList::check_bool_attribute) {
+            return;
+        }
+        if value {
+            self.element().ws_element().select_text();
+        }
+    }
+
+    pub fn scroll_into_view(&mut self, value: bool) {
+        if !self.must_update_attribute(value, AttributeValueList::check_bool_attribute) {
+            return;
+        }
+        if value {
+            self.element().ws_element().scroll_to_view_with_bool(true);
+        }
+    }
+
+    pub fn auto_focus(&mut self, value: bool) {
+        if !self.must_update_named_attribute(
+            "autofocus",
+            value,
+            AttributeValueList::check_bool_attribute,
+        ) {
+            return;
+        }
+        self.element()
+            .ws_element()
+            .set_bool_attribute("autofocus", value);
+        // `autofocus` does not work reliably on elements that are inserted
+        // dynamically, hence we also focus the element when it's created.
+        if value && self.status != ElementStatus::Existing {
+            self.element()
+                .ws_html_element()
+                .focus()
+                .expect_throw("render::base::element::ElementUpdater::auto_focus");
+        }
+    }
+
+    /// Set an enumerated attribute that accepts two values, such as
+    /// `spellcheck="true|false"` or `translate="yes|no"`.
+    pub fn set_two_state_attribute(
+        &mut self,
+        name: &'static str,
+        value: bool,
+        on: &str,
+        off: &str,
+    ) {
+        if !self.must_update_named_attribute(name, value, AttributeValueList::check_bool_attribute)
+        {
+            return;
+        }
+        self.element()
+            .ws_element()
+            .set_str_attribute(name, if value { on } else { off });
+    }
+
     pub fn href(&mut self, route: &C::Routes) {
         // Should `route` be stored in attribute list as an PartialEq object?
         // Is that possible? It may avoid calling `route.url()` if the route does not change.
         use crate::routing::Routes;
-        let url = route.url();
-        if !self.must_update_attribute(url.as_str(), AttributeValueList::check_str_attribute) {
+        // The link is in the same form as the url that the router navigates to.
+        let url = route.normalized_url();
+        if !self.must_update_named_attribute(
+            "href",
+            url.as_str(),
+            AttributeValueList::check_str_attribute,
+        ) {
             return;
         }
-        self.element.ws_element().set_str_attribute("href", &url);
+        self.element().ws_element().set_str_attribute("href", &url);
     }
 
-    pub fn id(&mut self, id: &str) {
-        if !self.must_update_attribute(id, AttributeValueList::check_str_attribute) {
+    /// Set `href` to an URL that is not a route of the app. If the element has
+    /// `target="_blank"` but no `rel` when its attributes are done, `rel="noopener
+    /// noreferrer"` is also set, so the opened page can not access `window.opener`.
+    pub fn href_external(&mut self, url: &str) {
+        self.external_href = true;
+        if !self.must_update_named_attribute("href", url, AttributeValueList::check_str_attribute) {
             return;
         }
-        self.element.ws_element().set_id(id);
+        self.element().ws_element().set_str_attribute("href", url);
     }
 
-    pub fn list_updater(&mut self, mode: ListElementCreation) -> (&Comp<C>, &C, ListUpdater) {
-        let (parent, nodes) = self.element.ws_node_and_nodes_mut();
-        let lr = ListUpdater::new(nodes, parent, self.status, None, mode.use_template());
+    /// Set both `href` and `xlink:href` (for browsers that do not support `href` on
+    /// svg elements yet).
+    #[cfg(feature = "svg")]
+    pub fn svg_href(&mut self, value: &str) {
+        if !self.must_update_named_attribute("href", value, AttributeValueList::check_str_attribute)
+        {
+            return;
+        }
+        let ws_element = self.element().ws_element();
+        ws_element.set_str_attribute("href", value);
+        ws_element
+            .as_ref()
+            .set_attribute_ns(Some("http://www.w3.org/1999/xlink"), "xlink:href", value)
+            .expect_throw("render::base::element::ElementUpdater::svg_href");
+    }
+
+    pub fn id(&mut self, id: &str) {
+        if !self.must_update_named_attribute("id", id, AttributeValueList::check_str_attribute) {
+            return;
+        }
+        self.element().ws_element().set_id(id);
+    }
+
+    pub fn list_updater<'m>(
+        &'m mut self,
+        mode: ListElementCreation<'m>,
+    ) -> (&'m Comp<C>, &'m C, ListUpdater<'m>) {
+        let (parent, nodes) = self
+            .element
+            .as_deref_mut()
+            .expect_throw("render::base::element::ElementUpdater: no element")
+            .ws_node_and_nodes_mut();
+        let lr = ListUpdater::new(nodes, parent, self.status, None, mode);
         (self.comp, self.state, lr)
     }
 
@@ -299,12 +631,23 @@ impl<'a, C: Component> ElementUpdater<'a, C> {
         // we need to collect items into a vec to know exact size
         let items: Vec<_> = items.into_iter().collect();
 
-        let use_template = mode.use_template();
-        let (parent, nodes) = self.element.ws_node_and_nodes_mut();
+        let (parent, nodes) = self
+            .element
+            .as_deref_mut()
+            .expect_throw("render::base::element::ElementUpdater: no element")
+            .ws_node_and_nodes_mut();
         let mut keyed_list_updater = KeyedListUpdater::new(
-            KeyedListContext::new(nodes.keyed_list(), tag, items.len(), parent, use_template),
+            KeyedListContext::new(nodes.keyed_list(), tag, items.len(), parent, mode),
             KeyedListUpdaterContext::new(self.comp, self.state, fn_get_key, fn_render),
         );
-        keyed_list_updater.update(items.into_iter())
+        let rs = keyed_list_updater.update(items.into_iter());
+        #[cfg(feature = "debug-trace")]
+        if let Some(key) = self.element_mut().nodes_mut().keyed_list().duplicated_key() {
+            crate::diagnostics::warn(format_args!(
+                "Spair: the key {:?} is used by more than one item of a keyed list. All items are rendered, but elements of items with the same key are not reliably reused",
+                key
+            ));
+        }
+        rs
     }
 }
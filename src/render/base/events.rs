@@ -16,4 +16,194 @@ pub trait StateHelperMethods<C: crate::component::Component>: MethodsForEvents<C
             }),
         )
     }
+
+    /// Call `updater` with the value of the `<select>`, `<input>` or `<textarea>` on
+    /// `change`.
+    fn on_change_value(
+        self,
+        comp: &crate::Comp<C>,
+        updater: impl Fn(&mut C, String) + 'static,
+    ) -> Self {
+        self.on_change(
+            comp.handler_arg_mut(move |state, event: crate::events::Event| {
+                let value = event
+                    .current_target_as_select_element()
+                    .map(|s| s.value())
+                    .or_else(|| {
+                        event
+                            .current_target_as::<web_sys::HtmlInputElement>()
+                            .map(|i| i.value())
+                    })
+                    .or_else(|| {
+                        event
+                            .current_target_as::<web_sys::HtmlTextAreaElement>()
+                            .map(|t| t.value())
+                    });
+                if let Some(value) = value {
+                    updater(state, value);
+                }
+            }),
+        )
+    }
+
+    /// Call `updater` with the `checked` state of the checkbox (or radio) `<input>` on
+    /// `change`.
+    fn on_change_checked(
+        self,
+        comp: &crate::Comp<C>,
+        updater: impl Fn(&mut C, bool) + 'static,
+    ) -> Self {
+        self.on_change(
+            comp.handler_arg_mut(move |state, event: crate::events::Event| {
+                if let Some(input) = event.current_target_as::<web_sys::HtmlInputElement>() {
+                    updater(state, input.checked());
+                }
+            }),
+        )
+    }
+
+    /// Call `updater` with the position of the click relative to the top-left corner
+    /// of the element that the listener is attached to (`clientX/Y` of the event minus
+    /// the position of the element's bounding rect), rather than to the viewport.
+    fn on_click_position(
+        self,
+        comp: &crate::Comp<C>,
+        updater: impl Fn(&mut C, (f64, f64)) + 'static,
+    ) -> Self {
+        self.on_click(
+            comp.handler_arg_mut(move |state, event: crate::events::MouseEvent| {
+                if let Some(element) = event.current_target_as::<web_sys::Element>() {
+                    let rect = element.get_bounding_client_rect();
+                    let x = event.raw().client_x() as f64 - rect.left();
+                    let y = event.raw().client_y() as f64 - rect.top();
+                    updater(state, (x, y));
+                }
+            }),
+        )
+    }
+
+    /// Two-way binding of the value of an `<input>` to a `QrVal<f64>`. See
+    /// `ElementUpdater::qr_bind_number` for how invalid text is handled.
+    #[cfg(feature = "queue-render")]
+    fn bind_value_f64(mut self, value: &crate::queue_render::val::QrVal<f64>) -> Self {
+        self.element_updater_mut().qr_bind_number(value);
+        self
+    }
+
+    /// Two-way binding of the value of an `<input>` to a `QrVal<i32>`.
+    #[cfg(feature = "queue-render")]
+    fn bind_value_i32(mut self, value: &crate::queue_render::val::QrVal<i32>) -> Self {
+        self.element_updater_mut().qr_bind_number(value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod state_helper_tests {
+    use wasm_bindgen::{JsCast, UnwrapThrowExt};
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::prelude::*;
+    use crate::render::html::InputType;
+
+    struct State {
+        fruit: String,
+        agreed: bool,
+        clicked_at: Option<(f64, f64)>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            let comp = element.comp();
+            element
+                .select(|s| {
+                    s.on_change_value(&comp, |state: &mut State, value| state.fruit = value)
+                        .option(|o| {
+                            o.value("apple").rupdate("Apple");
+                        })
+                        .option(|o| {
+                            o.value("banana").rupdate("Banana");
+                        });
+                })
+                .input(|i| {
+                    i.input_type(InputType::CheckBox)
+                        .on_change_checked(&comp, |state: &mut State, checked| {
+                            state.agreed = checked
+                        });
+                })
+                .div(|d| {
+                    d.style(
+                        "position: fixed; left: 100px; top: 50px; width: 200px; height: 100px;",
+                    )
+                    .on_click_position(&comp, |state: &mut State, position| {
+                        state.clicked_at = Some(position)
+                    });
+                });
+        }
+    }
+
+    impl Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                fruit: String::new(),
+                agreed: false,
+                clicked_at: None,
+            }
+        }
+    }
+
+    fn dispatch_change(target: &web_sys::EventTarget) {
+        let event = web_sys::Event::new("change")
+            .expect_throw("state_helper_tests::dispatch_change new event");
+        target
+            .dispatch_event(&event)
+            .expect_throw("state_helper_tests::dispatch_change dispatch");
+    }
+
+    fn query<T: JsCast>(root: &crate::dom::Element, selector: &str) -> T {
+        root.ws_element()
+            .as_ref()
+            .query_selector(selector)
+            .expect_throw("state_helper_tests::query")
+            .expect_throw("state_helper_tests::query no element")
+            .unchecked_into()
+    }
+
+    #[wasm_bindgen_test]
+    fn change_value_and_checked() {
+        let (root, rc) = crate::test_utils::mount::<State>();
+
+        let select: web_sys::HtmlSelectElement = query(&root, "select");
+        select.set_value("banana");
+        dispatch_change(&select);
+        assert_eq!("banana", rc.comp_instance().state().fruit);
+
+        let checkbox: web_sys::HtmlInputElement = query(&root, "input");
+        checkbox.set_checked(true);
+        dispatch_change(&checkbox);
+        assert!(rc.comp_instance().state().agreed);
+
+        checkbox.set_checked(false);
+        dispatch_change(&checkbox);
+        assert!(!rc.comp_instance().state().agreed);
+    }
+
+    #[wasm_bindgen_test]
+    fn click_position() {
+        let (root, rc) = crate::test_utils::mount_in_body::<State>();
+
+        let div: web_sys::HtmlElement = query(&root, "div");
+        let init = web_sys::MouseEventInit::new();
+        init.set_client_x(130);
+        init.set_client_y(70);
+        let event = web_sys::MouseEvent::new_with_mouse_event_init_dict("click", &init)
+            .expect_throw("state_helper_tests::click_position new event");
+        div.dispatch_event(&event)
+            .expect_throw("state_helper_tests::click_position dispatch");
+        assert_eq!(Some((30.0, 20.0)), rc.comp_instance().state().clicked_at);
+
+        root.ws_element().as_ref().remove();
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
         AChildNode, Element, ElementStatus, ElementTag, KeyedElement, KeyedList, ListItemKey,
         ListItemTemplate, OldElement,
     },
+    render::ListElementCreation,
 };
 
 pub struct KeyedListContext<'a, E> {
@@ -29,13 +30,19 @@ impl<'a, E: ElementTag> KeyedListContext<'a, E> {
         root_item_tag: E,
         new_item_count: usize,
         parent: &'a web_sys::Node,
-        use_template: bool,
+        mode: ListElementCreation,
     ) -> Self {
         list.pre_update(new_item_count);
 
-        let require_init_template = match use_template {
-            true => list.require_init_template(|| Element::new_ns(root_item_tag)),
-            false => false,
+        let require_init_template = match mode {
+            ListElementCreation::Clone => {
+                list.require_init_template(|| Element::new_ns(root_item_tag))
+            }
+            ListElementCreation::New => false,
+            ListElementCreation::Template(template) => {
+                list.set_template(template);
+                false
+            }
         };
 
         let (template, old, new, old_elements_map) = list.items_mut();
@@ -133,7 +140,7 @@ where
     }
     fn create_element_for_new_item(&self) -> (Element, ElementStatus) {
         match &self.list_context.template {
-            Some(template) => (Clone::clone(&template.element), ElementStatus::JustCloned),
+            Some(template) => (Clone::clone(&template.element), template.clone_status),
             None => (
                 Element::new_ns(self.list_context.root_item_tag),
                 ElementStatus::JustCreated,
@@ -428,9 +435,15 @@ where
             let KeyedElement { key, element } = item.take().expect_throw(
                 "render::base::keyed_list::KeyedListUpdater::construct_old_elements_map_from_remaining_old_elements",
             );
-            self.list_context
+            let duplicated = self
+                .list_context
                 .old_elements_map
                 .insert(key, OldElement { index, element });
+            // Multiple items with the same key: only the last one can be reused, the
+            // others must be removed here, otherwise, they are left in the DOM.
+            if let Some(duplicated) = duplicated {
+                duplicated.element.remove_from(self.list_context.parent);
+            }
         }
     }
 
@@ -689,10 +702,10 @@ fn longest_increasing_subsequence<I>(items: &mut [ItemWithLis<I>]) {
 
 #[cfg(test)]
 mod keyed_list_with_render_tests {
-    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen::{JsCast, UnwrapThrowExt};
     use wasm_bindgen_test::*;
 
-    use crate::dom::{Element, Keyed, Node};
+    use crate::dom::{Element, Keyed, ListItemKey, Node};
     use crate::render::ListElementCreation;
     use crate::render::{
         base::ElementUpdater,
@@ -805,6 +818,26 @@ mod keyed_list_with_render_tests {
         }
     }
 
+    struct GroupItem {
+        key: (u32, u32),
+        name: &'static str,
+    }
+
+    impl Keyed for GroupItem {
+        type Key = (u32, u32);
+        fn key(&self) -> &Self::Key {
+            &self.key
+        }
+    }
+
+    impl ElementRender<Unit> for &GroupItem {
+        const ELEMENT_TAG: &'static str = "span";
+        fn render(self, item: crate::Element<Unit>) {
+            use crate::render::html::MethodsForHtmlElementContent;
+            item.rupdate(self.name);
+        }
+    }
+
     impl PhantomApp {
         fn new() -> Self {
             let root = crate::dom::Element::new_ns(HtmlTag("div"));
@@ -864,6 +897,73 @@ mod keyed_list_with_render_tests {
         keyed_list(ListElementCreation::New);
     }
 
+    #[wasm_bindgen_test]
+    fn keyed_list_custom_template() {
+        let template = crate::utils::document()
+            .create_element("span")
+            .expect_throw("keyed_list_tests::keyed_list_custom_template create_element");
+        keyed_list(ListElementCreation::Template(&template));
+    }
+
+    #[wasm_bindgen_test]
+    fn static_content_of_items_from_custom_template() {
+        use crate::render::html::{HamsForDistinctNames, MethodsForHtmlElementContent};
+        let template = crate::utils::document()
+            .create_element("span")
+            .expect_throw("keyed_list_tests::static_content_of_items_from_custom_template");
+        let mut pa = PhantomApp::new();
+        let render = |pa: &mut PhantomApp, data: &[&'static str]| {
+            let _ = pa.create_updater().keyed_list_with_render(
+                data,
+                ListElementCreation::Template(&template),
+                "span",
+                |item: &&&'static str| *item,
+                |item, span| {
+                    span.static_attributes()
+                        .title("static")
+                        .rstatic("#")
+                        .rupdate(*item);
+                },
+            );
+        };
+
+        render(&mut pa, &["a", "b"]);
+        render(&mut pa, &["c", "a", "b"]);
+        assert_eq!(
+            r#"<span title="static">#c</span><span title="static">#a</span><span title="static">#b</span>"#,
+            pa.root.ws_element().as_ref().inner_html()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn changed_custom_template() {
+        let template = |class_name: &str| {
+            let template = crate::utils::document()
+                .create_element("span")
+                .expect_throw("keyed_list_tests::changed_custom_template create_element");
+            template.set_class_name(class_name);
+            template
+        };
+        let first = template("first");
+        let second = template("second");
+        let mut pa = PhantomApp::new();
+
+        let _ = pa
+            .create_updater()
+            .keyed_list(&["a"], ListElementCreation::Template(&first));
+        // Like a non-keyed list, new items are cloned from the current template
+        let _ = pa
+            .create_updater()
+            .keyed_list(&["a", "b"], ListElementCreation::Template(&second));
+        let _ = pa
+            .create_updater()
+            .keyed_list(&["a", "b", "c"], ListElementCreation::Template(&second));
+        assert_eq!(
+            r#"<span class="first">a</span><span class="second">b</span><span class="second">c</span>"#,
+            pa.root.ws_element().as_ref().inner_html()
+        );
+    }
+
     fn keyed_list(mode: ListElementCreation) {
         let mut pa = PhantomApp::new();
 
@@ -948,4 +1048,175 @@ mod keyed_list_with_render_tests {
         assert_eq!(data, pa.collect_from_keyed_list());
         assert_eq!(Some("abcdefghijk"), pa.collect_text_from_root().as_deref());
     }
+
+    #[wasm_bindgen_test]
+    fn keyed_list_composite_key() {
+        let item = |group, id, name| GroupItem {
+            key: (group, id),
+            name,
+        };
+        let mut pa = PhantomApp::new();
+        let data = vec![item(1, 1, "a"), item(1, 2, "b"), item(2, 1, "c")];
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+        assert_eq!(Some("abc"), pa.collect_text_from_root().as_deref());
+        let first: web_sys::Node = pa
+            .root
+            .ws_element()
+            .ws_node()
+            .first_child()
+            .expect_throw("The first item");
+
+        // "b" moves to group 2, (2, 2) is a new key, so its element is not reused
+        let data = vec![item(2, 1, "c"), item(2, 2, "b"), item(1, 1, "a")];
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+        assert_eq!(Some("cba"), pa.collect_text_from_root().as_deref());
+        let last = pa
+            .root
+            .ws_element()
+            .ws_node()
+            .last_child()
+            .expect_throw("The last item");
+        // The element of the key (1, 1) is moved to the end of the list
+        assert!(first.is_same_node(Some(&last)));
+
+        let key = ListItemKey::from(&(1u32, 2u32));
+        assert!((1u32, 2u32) == key);
+        assert!((2u32, 1u32) != key);
+        assert!((1u32, 2u32, 0u32) != key);
+    }
+
+    #[wasm_bindgen_test]
+    fn keyed_list_duplicated_keys() {
+        let mut pa = PhantomApp::new();
+        let child_count = |pa: &PhantomApp| pa.root.ws_element().as_ref().child_element_count();
+
+        #[cfg(feature = "debug-trace")]
+        let warnings = {
+            let warnings = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+            let received = warnings.clone();
+            crate::set_diagnostic_handler(move |level, message| {
+                received.borrow_mut().push((level, message.to_string()))
+            });
+            warnings
+        };
+        let data = vec!["x", "a", "y", "a", "z"];
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+        assert_eq!(Some("xayaz"), pa.collect_text_from_root().as_deref());
+        #[cfg(feature = "debug-trace")]
+        {
+            crate::remove_diagnostic_handler();
+            let warnings = warnings.borrow();
+            assert_eq!(1, warnings.len());
+            assert_eq!(crate::DiagnosticLevel::Warn, warnings[0].0);
+            assert!(
+                warnings[0].1.contains(r#"String("a")"#),
+                "{}",
+                warnings[0].1
+            );
+            match pa.root.nodes().nodes_vec().first().unwrap_throw() {
+                Node::KeyedList(kl) => assert_eq!(
+                    Some(&ListItemKey::String("a".to_string())),
+                    kl.duplicated_key()
+                ),
+                _ => panic!("Should be a keyed list"),
+            }
+        }
+
+        // Both elements of "a" are removed
+        let data = vec!["z", "y", "x"];
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+        assert_eq!(Some("zyx"), pa.collect_text_from_root().as_deref());
+        assert_eq!(3, child_count(&pa));
+
+        let data = vec!["a", "z", "a", "y"];
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+        assert_eq!(Some("azay"), pa.collect_text_from_root().as_deref());
+        assert_eq!(data, pa.collect_from_keyed_list());
+        assert_eq!(4, child_count(&pa));
+    }
+
+    #[wasm_bindgen_test]
+    fn keyed_list_reversed_moves_minimal_elements() {
+        let mut pa = PhantomApp::new();
+        let data = vec!["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+
+        let observer = web_sys::MutationObserver::new(&js_sys::Function::new_no_args(""))
+            .expect_throw("Create a MutationObserver");
+        let options = web_sys::MutationObserverInit::new();
+        options.set_child_list(true);
+        observer
+            .observe_with_options(pa.root.ws_element().ws_node(), &options)
+            .expect_throw("Observe the list's parent");
+
+        let data: Vec<_> = data.into_iter().rev().collect();
+        let _ = pa
+            .create_updater()
+            .keyed_list(&data, ListElementCreation::New);
+        assert_eq!(Some("jihgfedcba"), pa.collect_text_from_root().as_deref());
+
+        let moves = observer
+            .take_records()
+            .iter()
+            .map(|record| record.unchecked_into::<web_sys::MutationRecord>())
+            .filter(|record| record.added_nodes().length() > 0)
+            .count();
+        observer.disconnect();
+        // The longest increasing subsequence of a reversed list has one item,
+        // every other item must be moved.
+        assert_eq!(data.len() - 1, moves);
+    }
+
+    #[wasm_bindgen_test]
+    fn keyed_map() {
+        use crate::render::html::MethodsForHtmlElementContent;
+        let render = |pa: &mut PhantomApp, map: &std::collections::BTreeMap<u32, &'static str>| {
+            let _ = pa.create_updater().keyed_map(
+                map,
+                ListElementCreation::New,
+                "span",
+                |_, value, element| {
+                    element.rupdate(*value);
+                },
+            );
+        };
+        let node = |pa: &PhantomApp, index| {
+            pa.root
+                .ws_element()
+                .ws_node()
+                .child_nodes()
+                .get(index)
+                .expect_throw("keyed_list_tests::keyed_map node")
+        };
+        let mut pa = PhantomApp::new();
+        let mut map = std::collections::BTreeMap::new();
+        map.insert(3, "c");
+        map.insert(1, "a");
+        map.insert(2, "b");
+        render(&mut pa, &map);
+        assert_eq!(Some("abc"), pa.collect_text_from_root().as_deref());
+        let b = node(&pa, 1);
+        let c = node(&pa, 2);
+
+        map.remove(&1);
+        map.insert(0, "z");
+        map.insert(2, "B");
+        render(&mut pa, &map);
+        assert_eq!(Some("zBc"), pa.collect_text_from_root().as_deref());
+        // The elements of the keys 2 and 3 are reused
+        assert!(b.is_same_node(Some(&node(&pa, 1))));
+        assert!(c.is_same_node(Some(&node(&pa, 2))));
+    }
 }
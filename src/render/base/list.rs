@@ -2,13 +2,14 @@ use super::ElementUpdater;
 use crate::{
     component::{Comp, Component},
     dom::{ElementStatus, ElementTag, Nodes},
+    render::ListElementCreation,
 };
 
 #[must_use = "Caller should set selected option for <select> element"]
 pub struct RememberSettingSelectedOption;
 
 pub struct ListUpdater<'a> {
-    use_template: bool,
+    mode: ListElementCreation<'a>,
     parent: &'a web_sys::Node,
     parent_status: ElementStatus,
     // This is None if it is a whole-list, the list is the only content of the parent node.
@@ -24,10 +25,10 @@ impl<'a> ListUpdater<'a> {
         parent: &'a web_sys::Node,
         parent_status: ElementStatus,
         end_of_list_flag: Option<&'a web_sys::Node>,
-        use_template: bool,
+        mode: ListElementCreation<'a>,
     ) -> Self {
         Self {
-            use_template,
+            mode,
             parent,
             parent_status,
             end_of_list_flag,
@@ -69,7 +70,7 @@ impl<'a> ListUpdater<'a> {
                 self.parent,
                 self.parent_status,
                 self.end_of_list_flag,
-                self.use_template,
+                self.mode,
             );
             let element = self.list.get_element_mut(index);
             let r = ElementUpdater::new(comp, state, element, status);
@@ -2,8 +2,10 @@ use super::{ElementUpdater, ListUpdater};
 use crate::{
     component::{Child, ChildComp, Comp, Component},
     dom::{
-        AChildNode, ElementStatus, ElementTag, GroupedNodes, Nodes, OwnedComponent, RefComponent,
+        AChildNode, ElementStatus, ElementTag, GroupedNodes, ListComponent, Nodes, OwnedComponent,
+        RefComponent,
     },
+    render::ListElementCreation,
 };
 use wasm_bindgen::UnwrapThrowExt;
 
@@ -21,6 +23,9 @@ pub struct NodesUpdater<'a, C: Component> {
     parent: &'a web_sys::Node,
     next_sibling: Option<&'a web_sys::Node>,
     nodes: &'a mut Nodes,
+    // The index right after the last static text, and whether it was just
+    // created. Consecutive static texts are merged into one text node.
+    last_static_text: Option<(usize, bool)>,
 }
 
 impl<'a, C: Component> From<ElementUpdater<'a, C>> for NodesUpdater<'a, C> {
@@ -37,6 +42,7 @@ impl<'a, C: Component> From<ElementUpdater<'a, C>> for NodesUpdater<'a, C> {
             parent,
             next_sibling: None,
             nodes,
+            last_static_text: None,
         }
     }
 }
@@ -84,12 +90,43 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
         self.index += 1;
     }
 
-    pub fn static_text(&mut self, text: &str) {
+    /// Format `args` into a reused buffer, then update the text node with it. The
+    /// text node only allocates (and the DOM is only touched) if the text changes.
+    pub fn update_text_fmt(&mut self, args: std::fmt::Arguments) {
+        if let Some(text) = args.as_str() {
+            self.update_text(text);
+            return;
+        }
+        self.render_display(&args);
+    }
+
+    /// Render `value` as a text node without allocating a new `String` on every render.
+    pub fn render_display(&mut self, value: &impl std::fmt::Display) {
         self.nodes
-            .static_text(self.index, text, self.parent, self.next_sibling);
+            .render_display(self.index, value, self.parent, self.next_sibling);
         self.index += 1;
     }
 
+    pub fn static_text(&mut self, text: &str) {
+        match self.last_static_text {
+            // No other node is rendered since the last static text, so we just
+            // append `text` to it (if it is just created) instead of creating a
+            // new text node.
+            Some((index, just_created)) if index == self.index => {
+                if just_created {
+                    self.nodes.append_static_text(self.index - 1, text);
+                }
+            }
+            _ => {
+                let just_created =
+                    self.nodes
+                        .static_text(self.index, text, self.parent, self.next_sibling);
+                self.index += 1;
+                self.last_static_text = Some((self.index, just_created));
+            }
+        }
+    }
+
     pub fn get_element_updater<E: ElementTag>(&mut self, tag: E) -> ElementUpdater<C> {
         let status = self.nodes.check_or_create_element(
             tag,
@@ -104,6 +141,93 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
         ElementUpdater::new(self.comp, self.state, element, status)
     }
 
+    /// Return a `NodesUpdater` for the nodes of the memo if `key` changed since
+    /// the last render, otherwise, return `None`, the nodes are left untouched.
+    pub fn get_memo_updater<K: PartialEq + 'static>(&mut self, key: K) -> Option<NodesUpdater<C>> {
+        let grouped_nodes = self
+            .nodes
+            .grouped_nodes(self.index, self.parent, self.next_sibling);
+        self.index += 1;
+        if !grouped_nodes.check_memo_key(key) {
+            return None;
+        }
+        let parent_status = if grouped_nodes.nodes().count() == 0 {
+            ElementStatus::JustCreated
+        } else {
+            self.parent_status
+        };
+        let (nodes, next_sibling) = grouped_nodes.nodes_mut_and_end_flag_node();
+        Some(NodesUpdater {
+            comp: self.comp,
+            state: self.state,
+
+            update_mode: true,
+            index: 0,
+            parent_status,
+            parent: self.parent,
+            next_sibling: Some(next_sibling),
+            nodes,
+            last_static_text: None,
+        })
+    }
+
+    /// Return a `NodesUpdater` for the nodes of a `for_each`. The caller must call
+    /// `remove_nodes_after_index` after rendering all items, to remove the nodes
+    /// that were rendered by extra items in the last render.
+    pub fn get_for_each_updater(&mut self) -> NodesUpdater<C> {
+        let grouped_nodes = self
+            .nodes
+            .grouped_nodes(self.index, self.parent, self.next_sibling);
+        self.index += 1;
+        let parent_status = if grouped_nodes.nodes().count() == 0 {
+            ElementStatus::JustCreated
+        } else {
+            self.parent_status
+        };
+        let (nodes, next_sibling) = grouped_nodes.nodes_mut_and_end_flag_node();
+        NodesUpdater {
+            comp: self.comp,
+            state: self.state,
+
+            update_mode: true,
+            index: 0,
+            parent_status,
+            parent: self.parent,
+            next_sibling: Some(next_sibling),
+            nodes,
+            last_static_text: None,
+        }
+    }
+
+    pub fn remove_nodes_after_index(&mut self) {
+        self.nodes.remove_from_dom_after(self.index, self.parent);
+    }
+
+    /// Return a `NodesUpdater` that renders its nodes into `target` instead of
+    /// `self.parent`.
+    pub fn get_portal_updater(&mut self, target: &web_sys::Node) -> NodesUpdater<C> {
+        let portal = self.nodes.portal(self.index, target);
+        self.index += 1;
+        let (target, nodes) = portal.target_and_nodes_mut();
+        let parent_status = if nodes.count() == 0 {
+            ElementStatus::JustCreated
+        } else {
+            self.parent_status
+        };
+        NodesUpdater {
+            comp: self.comp,
+            state: self.state,
+
+            update_mode: true,
+            index: 0,
+            parent_status,
+            parent: target,
+            next_sibling: None,
+            nodes,
+            last_static_text: None,
+        }
+    }
+
     pub fn get_match_if_updater(&mut self) -> MatchIfUpdater<C> {
         let grouped_nodes = self
             .nodes
@@ -117,7 +241,10 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
         }
     }
 
-    pub fn get_list_updater(&mut self, use_template: bool) -> (&Comp<C>, &C, ListUpdater) {
+    pub fn get_list_updater<'m>(
+        &'m mut self,
+        mode: ListElementCreation<'m>,
+    ) -> (&'m Comp<C>, &'m C, ListUpdater<'m>) {
         let gn = self
             .nodes
             .grouped_nodes(self.index, self.parent, self.next_sibling);
@@ -128,7 +255,7 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
             self.parent,
             self.parent_status,
             Some(next_sibling),
-            use_template,
+            mode,
         );
         (self.comp, self.state, lr)
     }
@@ -141,6 +268,8 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
         // on the second subsequent renders, do nothing.
 
         if self.parent_status == ElementStatus::JustCreated || !child.comp_instance().is_mounted() {
+            #[cfg(feature = "error-boundary")]
+            child.set_error_handler(self.comp.callback_arg_mut(C::on_child_error));
             child.first_render();
             child
                 .comp_instance()
@@ -151,6 +280,31 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
         }
     }
 
+    pub fn component_in_list<CC: Component>(&mut self, child: &ChildComp<CC>) {
+        // Unlike component_ref, the node at self.index may hold a different
+        // component from the last render (the items of the list changed).
+        let is_in_slot = {
+            let instance = child.comp_instance();
+            let root_node = instance.root_element().ws_element().ws_node();
+            self.nodes.list_component_root_node(self.index) == Some(root_node)
+        };
+        if is_in_slot {
+            return;
+        }
+        #[cfg(feature = "error-boundary")]
+        child.set_error_handler(self.comp.callback_arg_mut(C::on_child_error));
+        if child.comp_instance().root_element().is_empty() {
+            child.first_render();
+        }
+        self.nodes.store_list_component(
+            self.index,
+            self.parent,
+            self.next_sibling,
+            ListComponent::new(child, self.parent),
+        );
+        child.comp().set_mount_status_to_mounted();
+    }
+
     pub fn component_owned<CC, T>(
         &mut self,
         create_child_comp: impl FnOnce(&C, &Comp<C>) -> Child<C, CC, T>,
@@ -176,12 +330,18 @@ impl<'a, C: Component> NodesUpdater<'a, C> {
             .expect_throw("render::base::nodes::NodesUpdater::component get_any_component");
         match any.downcast_mut::<Child<C, CC, T>>() {
             Some(child) => {
+                #[cfg(feature = "error-boundary")]
+                if just_created {
+                    child.set_error_handler(comp.callback_arg_mut(C::on_child_error));
+                }
                 let have_a_queue_update = child.update(state);
                 if just_created && !have_a_queue_update {
                     child.first_render();
                 }
             }
-            None => log::warn!("Failed to downcast to the expected child component"),
+            None => crate::diagnostics::warn(format_args!(
+                "Failed to downcast to the expected child component"
+            )),
         }
     }
 
@@ -230,6 +390,10 @@ impl<'a, C: Component> MatchIfUpdater<'a, C> {
         self.comp.clone()
     }
 
+    pub fn active_index(&self) -> Option<u32> {
+        self.grouped_nodes.active_index()
+    }
+
     pub fn render_on_arm_index(self, index: u32) -> NodesUpdater<'a, C> {
         let status = self.grouped_nodes.set_active_index(index, self.parent);
         let (nodes, next_sibling) = self.grouped_nodes.nodes_mut_and_end_flag_node();
@@ -244,6 +408,7 @@ impl<'a, C: Component> MatchIfUpdater<'a, C> {
             parent: self.parent,
             next_sibling: Some(next_sibling),
             nodes,
+            last_static_text: None,
         }
     }
 }
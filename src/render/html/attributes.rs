@@ -141,17 +141,37 @@ pub trait HamsHandMade<C: Component>:
     fn done(self) {}
 
     fn set_attribute_str(mut self, name: &str, value: &str) -> Self {
-        self.element_updater_mut().set_str_attribute(name, value);
+        self.element_updater_mut()
+            .set_runtime_str_attribute(name, value);
+        self
+    }
+
+    /// Set a JS property of the element, such as `volume` of a media element
+    /// or `valueAsNumber` of an input. These are not attributes, hence they can
+    /// not be set by attribute methods. The property is only set if the value
+    /// changed since the last render.
+    fn property(mut self, name: &str, value: wasm_bindgen::JsValue) -> Self {
+        self.element_updater_mut().set_property(name, value);
+        self
+    }
+
+    /// Set `valueAsNumber` of an `<input type="number">` (or `type="range"`), the
+    /// `type` must be set before this. Unlike `.value()`, the number is not formatted
+    /// into a string, hence it is not affected by the locale. The property is only set
+    /// if the value changed since the last render.
+    fn value_number(mut self, value: f64) -> Self {
+        self.element_updater_mut().value_number(value);
         self
     }
 
     /// Only execute `input.set_checked` if the value changed. But it's safer
     /// to use `.checked()` instead.
     fn checked_if_changed(mut self, value: bool) -> Self {
-        if self
-            .element_updater_mut()
-            .must_update_attribute(value, AttributeValueList::check_bool_attribute)
-        {
+        if self.element_updater_mut().must_update_property(
+            "checked",
+            value,
+            AttributeValueList::check_bool_attribute,
+        ) {
             self.checked(value)
         } else {
             self
@@ -181,6 +201,32 @@ pub trait HamsHandMade<C: Component>:
         self
     }
 
+    /// Set the `autofocus` attribute. Because `autofocus` does not work reliably
+    /// on elements that are inserted dynamically, the element is also focused
+    /// when it is created.
+    fn auto_focus(mut self, value: bool) -> Self {
+        self.element_updater_mut().auto_focus(value);
+        self
+    }
+
+    fn draggable(mut self, value: bool) -> Self {
+        self.element_updater_mut()
+            .set_two_state_attribute("draggable", value, "true", "false");
+        self
+    }
+
+    fn spell_check(mut self, value: bool) -> Self {
+        self.element_updater_mut()
+            .set_two_state_attribute("spellcheck", value, "true", "false");
+        self
+    }
+
+    fn translate(mut self, value: bool) -> Self {
+        self.element_updater_mut()
+            .set_two_state_attribute("translate", value, "yes", "no");
+        self
+    }
+
     fn class(mut self, value: impl Class<C>) -> Self {
         value.render(self.element_updater_mut());
         self
@@ -191,6 +237,33 @@ pub trait HamsHandMade<C: Component>:
         self
     }
 
+    /// Like `class_if`, but toggles a group of classes together (such as utility
+    /// classes like `["bg-blue-500", "text-white", "shadow"]`), with only one
+    /// attribute slot and one class list change when `class_on` changes.
+    fn classes_if(mut self, class_on: bool, class_names: &[&str]) -> Self {
+        self.element_updater_mut().classes_if(class_on, class_names);
+        self
+    }
+
+    /// Add or remove `class_name` when `class_on` changes, without rendering the component.
+    #[cfg(feature = "queue-render")]
+    fn qr_class_if(
+        mut self,
+        class_name: &'static str,
+        class_on: &crate::queue_render::val::QrVal<bool>,
+    ) -> Self {
+        self.element_updater_mut().qr_class_if(class_name, class_on);
+        self
+    }
+
+    /// Set classes from a whitespace-separated list, such as `"btn btn-primary"`.
+    /// Unlike `class`, the list can contain many classes, and classes that are not
+    /// in the list (for example, added by a third-party library) are not touched.
+    fn class_tokens(mut self, class_names: &str) -> Self {
+        self.element_updater_mut().class_tokens(class_names);
+        self
+    }
+
     /// Set the `first_class` if `first` is true, otherwise, set the `second_class`
     fn class_or(mut self, first: bool, first_class: &str, second_class: &str) -> Self {
         self.element_updater_mut()
@@ -208,6 +281,56 @@ pub trait HamsHandMade<C: Component>:
         self
     }
 
+    /// Focus the element when `value` changes to `true`, after the current render
+    /// completes. Use this instead of `focus` for elements that are just rendered,
+    /// such as an input in a modal that has just been opened.
+    fn focus_when_attached(mut self, value: bool) -> Self {
+        self.element_updater_mut().focus_when_attached(value);
+        self
+    }
+
+    /// Remove focus from the element when `value` changes to `true`.
+    fn blur(mut self, value: bool) -> Self {
+        self.element_updater_mut().blur(value);
+        self
+    }
+
+    /// Select all the text in an `<input>` or a `<textarea>` when `value`
+    /// changes to `true`. Useful to select-all when an input is focused.
+    fn select_text(mut self, value: bool) -> Self {
+        self.element_updater_mut().select_text(value);
+        self
+    }
+
+    /// Scroll the element into view (aligned to the top) when `value` changes to `true`.
+    fn scroll_into_view(mut self, value: bool) -> Self {
+        self.element_updater_mut().scroll_into_view(value);
+        self
+    }
+
+    /// Execute `f` with the element only when it is just created (or cloned), not
+    /// on subsequent renders. Useful to set up things that are not managed by spair.
+    fn once_created(mut self, f: impl FnOnce(&web_sys::Element)) -> Self {
+        self.element_updater_mut().once_created(f);
+        self
+    }
+
+    /// Execute `f` with the element on every render, an escape hatch to call a method
+    /// of the element that spair does not provide, without storing a `WsRef`. Changes
+    /// made by `f` are not tracked by spair, the chain continues as usual.
+    fn with_ws_element(mut self, f: impl FnOnce(&web_sys::Element)) -> Self {
+        self.element_updater_mut().with_ws_element(f);
+        self
+    }
+
+    /// Execute `f` with the bounding client rect of the element after the current
+    /// render, such as to position a tooltip. Reading the rect forces a layout, avoid
+    /// measuring many elements on every render.
+    fn measure(mut self, f: impl FnOnce(web_sys::DomRect) + 'static) -> Self {
+        self.element_updater_mut().measure(f);
+        self
+    }
+
     /// This method only accepts a &Route. If you want set `href` with a str, please use `href_str()`.
     /// It is possible to make this method accept either a Route or a str, but I intentionally make
     /// them two separate methods. The purpose is to remind users to use a Route when it's possible.
@@ -216,6 +339,14 @@ pub trait HamsHandMade<C: Component>:
         self
     }
 
+    /// Set `href` to an external URL, such as `https://example.com`. If the element has
+    /// `target="_blank"` (set before or after this method, in any render), then
+    /// `rel="noopener noreferrer"` is also set unless the element already has a `rel`.
+    fn href_external(mut self, url: &str) -> Self {
+        self.element_updater_mut().href_external(url);
+        self
+    }
+
     fn id(mut self, id: &str) -> Self {
         self.element_updater_mut().id(id);
         self
@@ -281,6 +412,7 @@ make_trait_for_attribute_methods! {
         str     dir_name "dirname"
         bool    disabled
         str     download
+        str     export_parts "exportparts"
         str     r#for "for"
 
         // moved to ../attributes_elements_with_ambiguous_names
@@ -294,7 +426,13 @@ make_trait_for_attribute_methods! {
         f64     high
         str     href_str "href" // method named `href` is used for routing
         str     href_lang "hreflang"
+        str     is
         bool    is_map "ismap"
+        str     item_id "itemid"
+        str     item_prop "itemprop"
+        str     item_ref "itemref"
+        bool    item_scope "itemscope"
+        str     item_type "itemtype"
 
         // moved to ../attributes_elements_with_ambiguous_names
         // str     label
@@ -312,6 +450,7 @@ make_trait_for_attribute_methods! {
         bool    no_validate "novalidate"
         bool    open
         f64     optimum
+        str     part
         str     pattern
         str     ping
         str     placeholder
@@ -328,6 +467,7 @@ make_trait_for_attribute_methods! {
         bool    selected
         u32     size
         str     sizes
+        str     slot_name "slot" // method named `slot` is used for the <slot> element
 
         // moved to ../attributes_elements_with_ambiguous_names
         // u32     span
@@ -445,3 +585,568 @@ impl<'er, C: Component> HamsForDistinctNames<C> for StaticAttributesOnly<'er, C>
 impl<'er, C: Component> MethodsForEvents<C> for StaticAttributes<'er, C> {}
 impl<'er, C: Component> MethodsForEvents<C> for StaticAttributesOnly<'er, C> {}
 impl<'er, C: Component> MethodsForEvents<C> for AttributesOnly<'er, C> {}
+
+#[cfg(test)]
+mod attributes_tests {
+    use wasm_bindgen::{JsCast, UnwrapThrowExt};
+    use wasm_bindgen_test::*;
+
+    use crate::dom::{AChildNode, ElementStatus};
+    use crate::render::html::{
+        EnterKeyHint, HamsForDistinctNames, HamsHandMade, HamsWithPredefinedValues, InputMode,
+        MethodsForSelectedValueSelectedIndex,
+    };
+    use crate::test_utils::TestElement;
+
+    #[wasm_bindgen_test]
+    fn auto_focus() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated).auto_focus(true);
+        assert_eq!(Some(""), te.attribute("autofocus").as_deref());
+        let active_element = crate::utils::document().active_element();
+        assert_eq!(
+            Some(te.element.ws_element().as_ref()),
+            active_element.as_ref()
+        );
+
+        te.updater(ElementStatus::Existing).auto_focus(false);
+        assert_eq!(None, te.attribute("autofocus"));
+    }
+
+    #[wasm_bindgen_test]
+    fn two_state_attributes() {
+        let mut te = TestElement::in_body("div");
+        te.updater(ElementStatus::JustCreated)
+            .draggable(true)
+            .spell_check(false)
+            .translate(true);
+        assert_eq!(Some("true"), te.attribute("draggable").as_deref());
+        assert_eq!(Some("false"), te.attribute("spellcheck").as_deref());
+        assert_eq!(Some("yes"), te.attribute("translate").as_deref());
+
+        te.updater(ElementStatus::Existing)
+            .draggable(false)
+            .spell_check(true)
+            .translate(false);
+        assert_eq!(Some("false"), te.attribute("draggable").as_deref());
+        assert_eq!(Some("true"), te.attribute("spellcheck").as_deref());
+        assert_eq!(Some("no"), te.attribute("translate").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn input_mode_and_enter_key_hint() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .input_mode(InputMode::Numeric)
+            .enter_key_hint(EnterKeyHint::Search);
+        assert_eq!(Some("numeric"), te.attribute("inputmode").as_deref());
+        assert_eq!(Some("search"), te.attribute("enterkeyhint").as_deref());
+
+        te.updater(ElementStatus::Existing)
+            .input_mode(InputMode::Email)
+            .enter_key_hint(EnterKeyHint::Send);
+        assert_eq!(Some("email"), te.attribute("inputmode").as_deref());
+        assert_eq!(Some("send"), te.attribute("enterkeyhint").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn predefined_values_accept_strings() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .auto_complete("email")
+            .input_mode(String::from("decimal"));
+        assert_eq!(Some("email"), te.attribute("autocomplete").as_deref());
+        assert_eq!(Some("decimal"), te.attribute("inputmode").as_deref());
+
+        let token = "current-password".to_string();
+        te.updater(ElementStatus::Existing)
+            .auto_complete(&token)
+            .input_mode(InputMode::Numeric);
+        assert_eq!(
+            Some("current-password"),
+            te.attribute("autocomplete").as_deref()
+        );
+        assert_eq!(Some("numeric"), te.attribute("inputmode").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn custom_element_and_microdata_attributes() {
+        let mut te = TestElement::in_body("button");
+        te.updater(ElementStatus::JustCreated)
+            .is("my-button")
+            .part("label")
+            .item_scope(true)
+            .item_prop("name")
+            .slot_name("header");
+        assert_eq!(Some("my-button"), te.attribute("is").as_deref());
+        assert_eq!(Some("header"), te.attribute("slot").as_deref());
+        assert_eq!(Some("label"), te.attribute("part").as_deref());
+        assert_eq!(Some(""), te.attribute("itemscope").as_deref());
+        assert_eq!(Some("name"), te.attribute("itemprop").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn property() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .set_attribute_str("type", "number")
+            .property("valueAsNumber", wasm_bindgen::JsValue::from_f64(42.0));
+        let input: web_sys::HtmlInputElement =
+            te.element.ws_element().as_ref().clone().unchecked_into();
+        assert_eq!(42.0, input.value_as_number());
+        assert_eq!("42", input.value());
+
+        // The user changes the value, the property is not set again because
+        // the value in the render does not change.
+        input.set_value_as_number(1.0);
+        te.updater(ElementStatus::Existing)
+            .set_attribute_str("type", "number")
+            .property("valueAsNumber", wasm_bindgen::JsValue::from_f64(42.0));
+        assert_eq!(1.0, input.value_as_number());
+
+        te.updater(ElementStatus::Existing)
+            .set_attribute_str("type", "number")
+            .property("valueAsNumber", wasm_bindgen::JsValue::from_f64(7.0));
+        assert_eq!(7.0, input.value_as_number());
+    }
+
+    #[wasm_bindgen_test]
+    fn value_number() {
+        let warnings = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let received = warnings.clone();
+        crate::set_diagnostic_handler(move |level, message| {
+            received.borrow_mut().push((level, message.to_string()))
+        });
+
+        let mut te = TestElement::in_body("input");
+        let input: web_sys::HtmlInputElement =
+            te.element.ws_element().as_ref().clone().unchecked_into();
+        for status in [ElementStatus::JustCreated, ElementStatus::Existing] {
+            te.updater(status)
+                .set_attribute_str("type", "text")
+                .value_number(2.5);
+        }
+        // The text input is untouched, the unchanged value does not warn again
+        assert_eq!("", input.value());
+
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .set_attribute_str("type", "number")
+            .value_number(2.5);
+        let input: web_sys::HtmlInputElement =
+            te.element.ws_element().as_ref().clone().unchecked_into();
+        assert_eq!(2.5, input.value_as_number());
+        crate::remove_diagnostic_handler();
+
+        assert_eq!(
+            vec![(
+                crate::DiagnosticLevel::Warn,
+                r#".value_number() is called on an element that is not an <input type="number"> or <input type="range">"#.to_string()
+            )],
+            *warnings.borrow()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn classes_if() {
+        let classes = ["bg-blue", "text-white", "shadow"];
+        let mut te = TestElement::in_body("div");
+        let render = |te: &mut TestElement, status, active| {
+            te.updater(status)
+                .class("card")
+                .classes_if(active, &classes)
+                .done();
+        };
+        render(&mut te, ElementStatus::JustCreated, false);
+        assert_eq!(Some("card"), te.attribute("class").as_deref());
+
+        let observer = web_sys::MutationObserver::new(&js_sys::Function::new_no_args(""))
+            .expect_throw("Create a MutationObserver");
+        let options = web_sys::MutationObserverInit::new();
+        options.set_attributes(true);
+        observer
+            .observe_with_options(te.element.ws_node(), &options)
+            .expect_throw("Observe the element");
+        let mutations = || observer.take_records().length();
+
+        render(&mut te, ElementStatus::Existing, true);
+        assert_eq!(
+            Some("card bg-blue text-white shadow"),
+            te.attribute("class").as_deref()
+        );
+        // All classes are added at once
+        assert_eq!(1, mutations());
+
+        render(&mut te, ElementStatus::Existing, true);
+        assert_eq!(0, mutations());
+
+        render(&mut te, ElementStatus::Existing, false);
+        assert_eq!(Some("card"), te.attribute("class").as_deref());
+        assert_eq!(1, mutations());
+        observer.disconnect();
+    }
+
+    #[wasm_bindgen_test]
+    fn classes_if_with_changed_names() {
+        let mut te = TestElement::in_body("div");
+        let render = |te: &mut TestElement, status, class_names: &[&str]| {
+            te.updater(status)
+                .class("card")
+                .classes_if(true, class_names)
+                .done();
+        };
+        render(&mut te, ElementStatus::JustCreated, &["bg-blue", "shadow"]);
+        assert_eq!(
+            Some("card bg-blue shadow"),
+            te.attribute("class").as_deref()
+        );
+
+        render(&mut te, ElementStatus::Existing, &["bg-red", "shadow"]);
+        assert_eq!(Some("card shadow bg-red"), te.attribute("class").as_deref());
+
+        render(&mut te, ElementStatus::Existing, &[]);
+        assert_eq!(Some("card"), te.attribute("class").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn once_created() {
+        let runs = std::cell::Cell::new(0);
+        let mut te = TestElement::in_body("div");
+        te.updater(ElementStatus::JustCreated).once_created(|e| {
+            e.set_attribute("data-setup", "done").unwrap_throw();
+            runs.set(runs.get() + 1);
+        });
+        assert_eq!(1, runs.get());
+        assert_eq!(Some("done".to_string()), te.attribute("data-setup"));
+
+        te.updater(ElementStatus::Existing)
+            .once_created(|_| runs.set(runs.get() + 1));
+        assert_eq!(1, runs.get());
+
+        te.updater(ElementStatus::JustCloned)
+            .once_created(|_| runs.set(runs.get() + 1));
+        assert_eq!(2, runs.get());
+    }
+
+    #[wasm_bindgen_test]
+    fn with_ws_element() {
+        let mut te = TestElement::in_body("div");
+        te.updater(ElementStatus::JustCreated)
+            .id("first")
+            .with_ws_element(|e| e.set_attribute("data-raw", "1").unwrap_throw())
+            .class("item");
+        assert_eq!(Some("1"), te.attribute("data-raw").as_deref());
+        assert_eq!(Some("item"), te.attribute("class").as_deref());
+
+        te.updater(ElementStatus::Existing)
+            .id("first")
+            .with_ws_element(|e| e.set_attribute("data-raw", "2").unwrap_throw())
+            .class("active");
+        assert_eq!(Some("2"), te.attribute("data-raw").as_deref());
+        assert_eq!(Some("first"), te.attribute("id").as_deref());
+        assert_eq!(Some("active"), te.attribute("class").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn href_external_with_target_blank() {
+        use crate::render::html::Target;
+        let mut te = TestElement::in_body("a");
+        te.updater(ElementStatus::JustCreated)
+            .target(Target::_Blank)
+            .href_external("https://example.com");
+        assert_eq!(Some("https://example.com"), te.attribute("href").as_deref());
+        assert_eq!(Some("noopener noreferrer"), te.attribute("rel").as_deref());
+
+        let mut te = TestElement::in_body("a");
+        te.updater(ElementStatus::JustCreated)
+            .href_external("https://example.com");
+        assert_eq!(None, te.attribute("rel"));
+
+        let mut te = TestElement::in_body("a");
+        te.updater(ElementStatus::JustCreated)
+            .rel("external")
+            .target(Target::_Blank)
+            .href_external("https://example.com");
+        assert_eq!(Some("external"), te.attribute("rel").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn href_external_before_target_blank() {
+        use crate::render::html::Target;
+        let mut te = TestElement::in_body("a");
+        te.updater(ElementStatus::JustCreated)
+            .href_external("https://example.com")
+            .target(Target::_Blank);
+        assert_eq!(Some("noopener noreferrer"), te.attribute("rel").as_deref());
+
+        // `target` becomes `_blank` on a later render, the url does not change
+        let mut te = TestElement::in_body("a");
+        te.updater(ElementStatus::JustCreated)
+            .href_external("https://example.com")
+            .target(Target::_Self);
+        assert_eq!(None, te.attribute("rel"));
+        te.updater(ElementStatus::Existing)
+            .href_external("https://example.com")
+            .target(Target::_Blank);
+        assert_eq!(Some("noopener noreferrer"), te.attribute("rel").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn remove_omitted_attributes() {
+        let mut te = TestElement::in_body("button");
+        te.updater(ElementStatus::JustCreated)
+            .id("submit")
+            .disabled(true)
+            .title("Submit");
+        assert_eq!(Some(""), te.attribute("disabled").as_deref());
+
+        // `disabled` is omitted, `title` takes its slot
+        te.updater(ElementStatus::Existing)
+            .id("submit")
+            .title("Submit");
+        assert_eq!(None, te.attribute("disabled"));
+        assert_eq!(Some("Submit"), te.attribute("title").as_deref());
+        assert_eq!(Some("submit"), te.attribute("id").as_deref());
+
+        te.updater(ElementStatus::Existing)
+            .id("submit")
+            .title("Submit")
+            .disabled(true);
+        assert_eq!(Some(""), te.attribute("disabled").as_deref());
+
+        te.updater(ElementStatus::Existing).disabled(true);
+        assert_eq!(Some(""), te.attribute("disabled").as_deref());
+        assert_eq!(None, te.attribute("title"));
+        assert_eq!(None, te.attribute("id"));
+    }
+
+    #[wasm_bindgen_test]
+    fn remove_omitted_classes_and_properties() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .class("field")
+            .class_if(true, "active")
+            .set_attribute_str("data-state", "dirty")
+            .value("typed");
+        assert_eq!(Some("field active"), te.attribute("class").as_deref());
+        assert_eq!(Some("dirty"), te.attribute("data-state").as_deref());
+        let input: &web_sys::HtmlInputElement = te.element.ws_element().as_ref().unchecked_ref();
+        assert_eq!("typed", input.value());
+
+        // `class_if`, `set_attribute_str` and `value` are omitted
+        te.updater(ElementStatus::Existing).class("field");
+        assert_eq!(Some("field"), te.attribute("class").as_deref());
+        assert_eq!(None, te.attribute("data-state"));
+        let input: &web_sys::HtmlInputElement = te.element.ws_element().as_ref().unchecked_ref();
+        assert_eq!("", input.value());
+
+        // The class moves to another slot, it is not removed
+        te.updater(ElementStatus::Existing)
+            .class("field")
+            .class_if(true, "active");
+        assert_eq!(Some("field active"), te.attribute("class").as_deref());
+        te.updater(ElementStatus::Existing).class("active");
+        assert_eq!(Some("active"), te.attribute("class").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn class_tokens() {
+        let mut te = TestElement::in_body("div");
+        te.updater(ElementStatus::JustCreated)
+            .class_tokens("btn btn-primary");
+        te.element.ws_element().add_class("tooltip-attached");
+        assert_eq!(
+            Some("btn btn-primary tooltip-attached"),
+            te.attribute("class").as_deref()
+        );
+
+        te.updater(ElementStatus::Existing)
+            .class_tokens("btn  btn-danger active");
+        assert_eq!(
+            Some("btn tooltip-attached btn-danger active"),
+            te.attribute("class").as_deref()
+        );
+
+        te.updater(ElementStatus::Existing).class_tokens("");
+        assert_eq!(Some("tooltip-attached"), te.attribute("class").as_deref());
+    }
+
+    #[wasm_bindgen_test]
+    fn blur() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .focus(true)
+            .blur(false);
+        let active_element = crate::utils::document().active_element();
+        assert_eq!(
+            Some(te.element.ws_element().as_ref()),
+            active_element.as_ref()
+        );
+
+        te.updater(ElementStatus::Existing).focus(true).blur(true);
+        let active_element = crate::utils::document().active_element();
+        assert_ne!(
+            Some(te.element.ws_element().as_ref()),
+            active_element.as_ref()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn select_text() {
+        let mut te = TestElement::in_body("input");
+        te.updater(ElementStatus::JustCreated)
+            .value("hello")
+            .select_text(false);
+        let input: web_sys::HtmlInputElement =
+            te.element.ws_element().as_ref().clone().unchecked_into();
+        assert_eq!(
+            input.selection_start().unwrap_throw(),
+            input.selection_end().unwrap_throw()
+        );
+
+        te.updater(ElementStatus::Existing)
+            .value("hello")
+            .select_text(true);
+        assert_eq!(Some(0), input.selection_start().unwrap_throw());
+        assert_eq!(Some(5), input.selection_end().unwrap_throw());
+
+        let mut te = TestElement::in_body("textarea");
+        te.updater(ElementStatus::JustCreated)
+            .value("hi")
+            .select_text(true);
+        let text_area: web_sys::HtmlTextAreaElement =
+            te.element.ws_element().as_ref().clone().unchecked_into();
+        assert_eq!(Some(2), text_area.selection_end().unwrap_throw());
+
+        // Not an input, nothing happens
+        let mut te = TestElement::in_body("div");
+        te.updater(ElementStatus::JustCreated).select_text(true);
+    }
+
+    #[wasm_bindgen_test]
+    fn scroll_into_view() {
+        let window = crate::utils::window();
+        window.scroll_to_with_x_and_y(0.0, 0.0);
+        let mut te = TestElement::in_body("div");
+        te.updater(ElementStatus::JustCreated)
+            .style("margin-top: 3000px; height: 10px;")
+            .scroll_into_view(false);
+        assert_eq!(0.0, window.scroll_y().unwrap_throw());
+
+        te.updater(ElementStatus::Existing)
+            .style("margin-top: 3000px; height: 10px;")
+            .scroll_into_view(true);
+        assert!(window.scroll_y().unwrap_throw() > 0.0);
+        window.scroll_to_with_x_and_y(0.0, 0.0);
+    }
+
+    // The root element of a child component is rendered before it is inserted
+    // into the document.
+    struct SearchBox {
+        focus_when_attached: bool,
+    }
+
+    impl crate::component::Component for SearchBox {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            match self.focus_when_attached {
+                true => element.focus_when_attached(true),
+                false => element.focus(true),
+            };
+        }
+    }
+
+    impl crate::component::AsChildComp for SearchBox {
+        const ROOT_ELEMENT_TAG: crate::TagName = crate::TagName::Html(crate::HtmlTag("input"));
+        type Properties = bool;
+        fn init(_: &crate::Comp<Self>, focus_when_attached: bool) -> Self {
+            Self {
+                focus_when_attached,
+            }
+        }
+    }
+
+    struct Toolbar {
+        search_box: Option<crate::ChildComp<SearchBox>>,
+    }
+
+    impl crate::component::Component for Toolbar {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::html::HemsHandMade;
+            element.match_if(|mi| match self.search_box.as_ref() {
+                Some(search_box) => crate::set_arm!(mi).component_ref(search_box).done(),
+                None => crate::set_arm!(mi).done(),
+            });
+        }
+    }
+
+    impl crate::application::Application for Toolbar {
+        fn init(_: &crate::Comp<Self>) -> Self {
+            Self { search_box: None }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn focus_when_attached() {
+        use crate::component::AsChildComp;
+        for focus_when_attached in [false, true] {
+            let (root, rc) = crate::test_utils::mount_in_body::<Toolbar>();
+            rc.comp()
+                .callback_once_mut(move |state: &mut Toolbar| {
+                    state.search_box = Some(SearchBox::with_props(focus_when_attached));
+                })
+                .call();
+            crate::test_utils::sleep(0).await;
+
+            let input = root
+                .ws_element()
+                .as_ref()
+                .query_selector("input")
+                .expect_throw("attributes_tests::focus_when_attached query")
+                .expect_throw("attributes_tests::focus_when_attached no input");
+            // `focus` does nothing on the detached input
+            assert_eq!(
+                focus_when_attached,
+                crate::utils::document().active_element() == Some(input)
+            );
+            root.ws_element().as_ref().remove();
+        }
+    }
+
+    struct Tooltip {
+        anchor_size: Option<(f64, f64)>,
+    }
+
+    impl crate::component::Component for Tooltip {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::html::HemsForDistinctNames;
+            let comp = element.comp();
+            element.div(|d| {
+                d.style("width: 120px; height: 30px;").measure(move |rect| {
+                    comp.callback_once_mut(move |state: &mut Tooltip| {
+                        state.anchor_size = Some((rect.width(), rect.height()));
+                        crate::ShouldRender::No
+                    })
+                    .call();
+                });
+            });
+        }
+    }
+
+    impl crate::application::Application for Tooltip {
+        fn init(_: &crate::Comp<Self>) -> Self {
+            Self { anchor_size: None }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn measure() {
+        let (root, rc) = crate::test_utils::mount_in_body::<Tooltip>();
+        assert_eq!(None, rc.comp_instance().state().anchor_size);
+        crate::test_utils::sleep(0).await;
+        assert_eq!(Some((120.0, 30.0)), rc.comp_instance().state().anchor_size);
+        root.ws_element().as_ref().remove();
+    }
+}
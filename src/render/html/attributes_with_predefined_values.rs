@@ -73,6 +73,19 @@ TraitName: HamsWithPredefinedValues
         form_enc_type "formenctype"
     }
 }
+{
+    EnterKeyHintAttributeValue
+    EnterKeyHint {
+        Enter => "enter",
+        Done => "done",
+        Go => "go",
+        Next => "next",
+        Previous => "previous",
+        Search => "search",
+        Send => "send",
+    }
+    { enter_key_hint "enterkeyhint" }
+}
 {
     FormMethodAttributeValue
     FormMethod {
@@ -96,7 +109,7 @@ TraitName: HamsWithPredefinedValues
         Email => "email",
         Url => "url",
     }
-    {}
+    { input_mode "inputmode" }
 }
 {
     InputTypeAttributeValue
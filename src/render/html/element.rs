@@ -1,7 +1,7 @@
 use super::{AttributesOnly, StaticAttributes, StaticAttributesOnly};
 use crate::{
     component::{Comp, Component},
-    dom::{AttributeValueList, ElementType, WsElement},
+    dom::{AttributeValueList, ElementStatus, ElementType, WsElement},
     render::base::{ElementUpdater, ElementUpdaterMut, MethodsForEvents},
 };
 use wasm_bindgen::JsCast;
@@ -136,6 +136,10 @@ impl<'er, C: Component> HtmlElementUpdater<'er, C> {
         self.element_updater.element().ws_element()
     }
 
+    pub fn status(&self) -> ElementStatus {
+        self.element_updater.status()
+    }
+
     fn set_selected_value_string(&mut self, value: Option<String>) {
         if let Some(manager) = self.select_element_value_manager.as_mut() {
             manager.set_selected_value(value);
@@ -163,19 +167,21 @@ impl<'er, C: Component> HtmlElementUpdater<'er, C> {
     }
 
     pub(super) fn selected_value_str(&mut self, value: &str) {
-        if !self
-            .element_updater
-            .must_update_attribute(value, AttributeValueList::check_str_attribute)
-        {
+        if !self.element_updater.must_update_property(
+            "value",
+            value,
+            AttributeValueList::check_str_attribute,
+        ) {
             return;
         }
         self.set_value(value);
     }
     pub(super) fn selected_value_string(&mut self, value: String) {
-        if !self
-            .element_updater
-            .must_update_attribute(value.as_str(), AttributeValueList::check_str_attribute)
-        {
+        if !self.element_updater.must_update_property(
+            "value",
+            value.as_str(),
+            AttributeValueList::check_str_attribute,
+        ) {
             return;
         }
         self.set_value(&value);
@@ -183,15 +189,18 @@ impl<'er, C: Component> HtmlElementUpdater<'er, C> {
     pub(super) fn selected_value_optional_str(&mut self, value: Option<&str>) {
         match self.element_updater.element_mut().element_type() {
             ElementType::Select => {
-                if !self
-                    .element_updater
-                    .must_update_attribute(value, AttributeValueList::check_optional_str_attribute)
-                {
+                if !self.element_updater.must_update_property(
+                    "value",
+                    value,
+                    AttributeValueList::check_optional_str_attribute,
+                ) {
                     return;
                 }
                 self.set_selected_value(value);
             }
-            _ => log::warn!("Should a value:Option<String> only can be set on a select element?"),
+            _ => crate::diagnostics::warn(format_args!(
+                "Should a value:Option<String> only can be set on a select element?"
+            )),
         }
     }
     pub(super) fn selected_value_optional_string(&mut self, value: Option<String>) {
@@ -201,15 +210,18 @@ impl<'er, C: Component> HtmlElementUpdater<'er, C> {
     fn selected_index(&mut self, value: i32) {
         match self.element_updater.element_mut().element_type() {
             ElementType::Select => {
-                if !self
-                    .element_updater
-                    .must_update_attribute(value, AttributeValueList::check_i32_attribute)
-                {
+                if !self.element_updater.must_update_property(
+                    "selectedIndex",
+                    value,
+                    AttributeValueList::check_i32_attribute,
+                ) {
                     return;
                 }
                 self.set_selected_index(Some(value));
             }
-            _ => log::warn!("Should a selected_index only can be set on a select element?"),
+            _ => crate::diagnostics::warn(format_args!(
+                "Should a selected_index only can be set on a select element?"
+            )),
         }
     }
     pub(super) fn selected_index_usize(&mut self, value: usize) {
@@ -34,6 +34,23 @@ pub trait HemsForList<'a, C: Component>:
         self.make_nodes_extensions()
     }
 
+    /// Same as `list_with_render` but `render` also receives the index of the item.
+    fn indexed_list_with_render<I, II, R>(
+        self,
+        items: II,
+        mode: ListElementCreation,
+        tag: &'static str,
+        render: R,
+    ) -> NodesExtensions<'a>
+    where
+        II: Iterator<Item = I>,
+        R: Fn(usize, I, crate::Element<C>),
+    {
+        self.list_with_render(items.enumerate(), mode, tag, |(index, item), element| {
+            render(index, item, element)
+        })
+    }
+
     fn lwr_clone<I, II, R>(self, items: II, tag: &'static str, render: R) -> NodesExtensions<'a>
     where
         II: Iterator<Item = I>,
@@ -91,3 +108,36 @@ impl<'a, C: Component> HemsForList<'a, C> for HtmlElementUpdater<'a, C> {}
 impl<'a, C: Component> HemsForList<'a, C> for AttributesOnly<'a, C> {}
 impl<'a, C: Component> HemsForList<'a, C> for StaticAttributes<'a, C> {}
 impl<'a, C: Component> HemsForList<'a, C> for StaticAttributesOnly<'a, C> {}
+
+#[cfg(test)]
+mod list_tests {
+    use wasm_bindgen_test::*;
+
+    use crate::dom::ElementStatus;
+    use crate::render::html::{HemsForList, MethodsForHtmlElementContent};
+    use crate::render::ListElementCreation;
+    use crate::test_utils::TestElement;
+
+    #[wasm_bindgen_test]
+    fn indexed_list() {
+        let mut root = TestElement::new("ol");
+        let mut render = |items: &[&str]| {
+            let _ = root
+                .updater(ElementStatus::Existing)
+                .indexed_list_with_render(
+                    items.iter(),
+                    ListElementCreation::Clone,
+                    "li",
+                    |index, item, li| {
+                        li.rupdate(index).rupdate(". ").rupdate(*item);
+                    },
+                );
+        };
+        render(&["a", "b", "c"]);
+        render(&["c", "a"]);
+        assert_eq!(
+            Some("0. c1. a".to_string()),
+            root.ws_element().text_content()
+        );
+    }
+}
@@ -61,6 +61,60 @@ pub trait HemsHandMade<C: Component>: Sized {
         this
     }
 
+    /// Render the nodes in `f` only if `key` is different from the key of the
+    /// last render. Otherwise, `f` is not executed and the nodes are left untouched.
+    /// This is useful to skip rendering expensive subtrees when their inputs do
+    /// not change.
+    fn memo<K: PartialEq + 'static>(self, key: K, f: impl FnOnce(Nodes<C>)) -> Self::Output {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        if let Some(nodes_updater) = render.get_memo_updater(key) {
+            let mut nodes_updater = HtmlNodesUpdater {
+                nodes_updater,
+                _select_element_value_manager: None,
+            };
+            f(Nodes::new(&mut nodes_updater));
+        }
+        this
+    }
+
+    /// Render `f` for each item of `items` inline, without the machinery of `list`.
+    /// The nodes are reconciled by index: the nodes rendered by an item are updated
+    /// by the item at the same position in the next render, and the nodes of extra
+    /// items are removed when there are fewer items. This is suitable for small
+    /// collections, every item should render the same kind of nodes.
+    fn for_each<I, II>(self, items: II, f: impl Fn(I, Nodes<C>)) -> Self::Output
+    where
+        II: IntoIterator<Item = I>,
+    {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        let mut nodes_updater = HtmlNodesUpdater {
+            nodes_updater: render.get_for_each_updater(),
+            _select_element_value_manager: None,
+        };
+        for item in items {
+            f(item, Nodes::new(&mut nodes_updater));
+        }
+        nodes_updater.nodes_updater.remove_nodes_after_index();
+        this
+    }
+
+    /// Render the nodes in `f` into `target` (e.g. `document.body` for a modal)
+    /// instead of the current element. The nodes are still owned by the component,
+    /// they are removed from `target` when the portal is removed or the
+    /// component is dropped.
+    fn portal(self, target: web_sys::Element, f: impl FnOnce(Nodes<C>)) -> Self::Output {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        let mut nodes_updater = HtmlNodesUpdater {
+            nodes_updater: render.get_portal_updater(target.as_ref()),
+            _select_element_value_manager: None,
+        };
+        f(Nodes::new(&mut nodes_updater));
+        this
+    }
+
     #[cfg(feature = "queue-render")]
     fn qr_match_if<T: 'static>(
         self,
@@ -104,6 +158,19 @@ pub trait HemsHandMade<C: Component>: Sized {
         this
     }
 
+    /// Use this instead of `component_ref` to render a child component in a list
+    /// item. Each item stores its own component, so the list can be updated
+    /// incrementally even if the components of the items change.
+    fn component_in_list<CC: Component>(self, child: &ChildComp<CC>) -> Self::Output {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        if render.require_update() {
+            render.component_in_list(child);
+        }
+        render.next_index();
+        this
+    }
+
     fn component_owned<CC: Component, T: 'static + Clone + PartialEq>(
         self,
         create_child_comp: impl FnOnce(&C, &Comp<C>) -> Child<C, CC, T>,
@@ -137,6 +204,13 @@ where
         render.next_index();
         this
     }
+
+    /// Render an element of any tag, such as a custom element (`<my-widget>`), that
+    /// does not have its own method. JS properties of the element (for example, the
+    /// inputs of a web component) can be set by `.property()`.
+    fn element(self, tag: &'static str, element_updater: impl FnOnce(HtmlElementUpdater<C>)) -> O {
+        self.render_element(tag, element_updater)
+    }
 }
 
 #[cfg(test)]
@@ -353,6 +427,12 @@ pub trait MethodsForHtmlElementContent<'n, C: Component>:
         n.rupdate(render)
     }
 
+    /// Render a text with `format_args!`, like `.text_fmt(format_args!("{} items", count))`.
+    /// The text is formatted into a reused buffer, the DOM is only updated if the text changes.
+    fn text_fmt(self, args: std::fmt::Arguments) -> NodesOwned<'n, C> {
+        self.rupdate(args)
+    }
+
     fn rstatic(self, render: impl StaticRender<C>) -> NodesOwned<'n, C> {
         let n: NodesOwned<C> = self.into();
         n.rstatic(render)
@@ -381,6 +461,17 @@ impl<'h, 'n: 'h, C: Component> Nodes<'h, 'n, C> {
         self.0.nodes_updater.update_text(text);
     }
 
+    pub(super) fn update_text_fmt(self, args: std::fmt::Arguments) {
+        self.0.nodes_updater.update_text_fmt(args);
+    }
+
+    /// Render `value` as a text, the text is formatted into a reused buffer instead
+    /// of a new `String` on every render. Useful to implement `Render` for a type
+    /// that implements `Display`, such as an enum.
+    pub fn render_display(self, value: &impl std::fmt::Display) {
+        self.0.nodes_updater.render_display(value);
+    }
+
     pub fn done(self) {}
 
     pub fn state(&self) -> &'n C {
@@ -402,6 +493,10 @@ impl<'h, 'n: 'h, C: Component> Nodes<'h, 'n, C> {
         self
     }
 
+    pub fn text_fmt(self, args: std::fmt::Arguments) -> Self {
+        self.rupdate(args)
+    }
+
     pub fn rstatic(mut self, render: impl StaticRender<C>) -> Self {
         let n = StaticNodes::new(self.0);
         render.render(n);
@@ -418,6 +513,14 @@ impl<'h, 'n: 'h, C: Component> Nodes<'h, 'n, C> {
         func(n);
         self
     }
+
+    /// Render the nodes in `f` in static mode, they are only rendered when the
+    /// parent element is just created. The update mode is restored after `f`.
+    pub fn static_section(mut self, f: impl FnOnce(StaticNodes<C>)) -> Self {
+        f(StaticNodes::new(self.0));
+        self.nodes_updater_mut().set_update_mode();
+        self
+    }
 }
 
 impl<'h, 'n: 'h, C: Component> StaticNodes<'h, 'n, C> {
@@ -448,6 +551,15 @@ impl<'h, 'n: 'h, C: Component> StaticNodes<'h, 'n, C> {
         //self.nodes_updater_mut().set_static_mode();
         self
     }
+
+    /// Render the nodes in `f` in update mode, they are updated on every render.
+    /// The static mode is restored after `f`. This is useful for a child that must
+    /// be updated (such as a counter) in an otherwise static block.
+    pub fn dynamic_section(mut self, f: impl FnOnce(Nodes<C>)) -> Self {
+        f(Nodes::new(self.0));
+        self.nodes_updater_mut().set_static_mode();
+        self
+    }
 }
 
 impl<'n, C: Component> NodesOwned<'n, C> {
@@ -464,6 +576,10 @@ impl<'n, C: Component> NodesOwned<'n, C> {
         self
     }
 
+    pub fn text_fmt(self, args: std::fmt::Arguments) -> Self {
+        self.rupdate(args)
+    }
+
     pub fn rstatic(mut self, render: impl StaticRender<C>) -> Self {
         let n = StaticNodes::new(&mut self.0);
         render.render(n);
@@ -480,6 +596,14 @@ impl<'n, C: Component> NodesOwned<'n, C> {
         func(n);
         self
     }
+
+    /// Render the nodes in `f` in static mode, they are only rendered when the
+    /// parent element is just created. The update mode is restored after `f`.
+    pub fn static_section(mut self, f: impl FnOnce(StaticNodes<C>)) -> Self {
+        f(StaticNodes::new(&mut self.0));
+        self.nodes_updater_mut().set_update_mode();
+        self
+    }
 }
 
 impl<'n, C: Component> StaticNodesOwned<'n, C> {
@@ -506,6 +630,15 @@ impl<'n, C: Component> StaticNodesOwned<'n, C> {
     pub fn relement<R: ElementRender<C>>(self, render: R) -> Self {
         self.render_element(R::ELEMENT_TAG, |e| render.render(e))
     }
+
+    /// Render the nodes in `f` in update mode, they are updated on every render.
+    /// The static mode is restored after `f`. This is useful for a child that must
+    /// be updated (such as a counter) in an otherwise static block.
+    pub fn dynamic_section(mut self, f: impl FnOnce(Nodes<C>)) -> Self {
+        f(Nodes::new(&mut self.0));
+        self.nodes_updater_mut().set_static_mode();
+        self
+    }
 }
 
 impl<'h, 'n: 'h, C: Component> UpdateHtmlElement<C, Nodes<'h, 'n, C>> for Nodes<'h, 'n, C> {}
@@ -568,6 +701,12 @@ impl<'a, C: Component> HtmlMatchIfUpdater<'a, C> {
         })
     }
 
+    /// The index of the arm that was rendered by the last render, `None` on the
+    /// first render.
+    pub fn active_index(&self) -> Option<u32> {
+        self.0.active_index()
+    }
+
     pub fn state(&self) -> &'a C {
         self.0.state()
     }
@@ -576,3 +715,303 @@ impl<'a, C: Component> HtmlMatchIfUpdater<'a, C> {
         self.0.comp()
     }
 }
+
+#[cfg(test)]
+mod nodes_tests {
+    use wasm_bindgen::{JsCast, UnwrapThrowExt};
+    use wasm_bindgen_test::*;
+
+    use super::MethodsForHtmlElementContent;
+    use crate::dom::ElementStatus;
+    use crate::test_utils::TestElement;
+
+    #[wasm_bindgen_test]
+    fn text_fmt() {
+        let mut root = TestElement::new("div");
+        let render = |root: &mut TestElement, status, count: u32| {
+            root.updater(status)
+                .text_fmt(format_args!("{} items", count))
+                .text_fmt(format_args!("static"));
+        };
+
+        render(&mut root, ElementStatus::JustCreated, 2);
+        assert_eq!(
+            Some("2 itemsstatic".to_string()),
+            root.ws_element().text_content()
+        );
+
+        let observer = web_sys::MutationObserver::new(&js_sys::Function::new_no_args(""))
+            .expect_throw("Create a MutationObserver");
+        let options = web_sys::MutationObserverInit::new();
+        options.set_character_data(true);
+        options.set_subtree(true);
+        observer
+            .observe_with_options(root.ws_element(), &options)
+            .expect_throw("Observe the text nodes");
+        let text_writes = || {
+            observer
+                .take_records()
+                .iter()
+                .map(|record| record.unchecked_into::<web_sys::MutationRecord>())
+                .filter(|record| record.type_() == "characterData")
+                .count()
+        };
+
+        render(&mut root, ElementStatus::Existing, 2);
+        assert_eq!(0, text_writes());
+
+        render(&mut root, ElementStatus::Existing, 3);
+        assert_eq!(1, text_writes());
+        assert_eq!(
+            Some("3 itemsstatic".to_string()),
+            root.ws_element().text_content()
+        );
+        observer.disconnect();
+    }
+
+    #[wasm_bindgen_test]
+    fn custom_element() {
+        use crate::render::html::{HamsHandMade, UpdateHtmlElement};
+        let mut root = TestElement::new("div");
+        let render = |root: &mut TestElement, status, value: &str| {
+            root.updater(status).element("my-widget", |w| {
+                w.property("config", value.into());
+            });
+        };
+        let ws_root = root.ws_element().clone();
+        let config = || {
+            let widget = ws_root
+                .query_selector("my-widget")
+                .expect_throw("nodes_tests::custom_element query")
+                .expect_throw("nodes_tests::custom_element no my-widget");
+            js_sys::Reflect::get(&widget, &"config".into())
+                .expect_throw("nodes_tests::custom_element get property")
+                .as_string()
+        };
+
+        render(&mut root, ElementStatus::JustCreated, "first");
+        assert_eq!(Some("first".to_string()), config());
+        render(&mut root, ElementStatus::Existing, "second");
+        assert_eq!(Some("second".to_string()), config());
+        assert_eq!(1, ws_root.child_element_count());
+    }
+
+    #[wasm_bindgen_test]
+    fn static_and_dynamic_sections() {
+        use crate::render::html::HemsForDistinctNames;
+        let mut root = TestElement::new("div");
+        let render = |root: &mut TestElement, status, title: &str, count: u32| {
+            root.updater(status)
+                .update_nodes()
+                .static_section(|s| {
+                    s.h2(|h| h.rstatic(title).done())
+                        .dynamic_section(|n| {
+                            n.strong(|s| s.rupdate(count).done());
+                        })
+                        .p(|p| p.rstatic(title).done());
+                })
+                .strong(|s| s.rupdate(count).done());
+        };
+        let ws_root = root.ws_element().clone();
+
+        render(&mut root, ElementStatus::JustCreated, "card", 1);
+        assert_eq!(Some("card1card1".to_string()), ws_root.text_content());
+        render(&mut root, ElementStatus::Existing, "changed", 2);
+        assert_eq!(Some("card2card2".to_string()), ws_root.text_content());
+        assert_eq!(4, ws_root.child_element_count());
+    }
+
+    #[wasm_bindgen_test]
+    fn render_option_and_result() {
+        use crate::render::html::HemsForDistinctNames;
+        let mut root = TestElement::new("div");
+        let render =
+            |root: &mut TestElement, status, name: Option<&str>, count: Result<u32, &str>| {
+                root.updater(status)
+                    .strong(|s| s.rupdate(name).done())
+                    .em(|e| e.rupdate(count).done());
+            };
+        let ws_root = root.ws_element().clone();
+        let strong = || {
+            ws_root
+                .query_selector("strong")
+                .expect_throw("nodes_tests::render_option_and_result query")
+                .expect_throw("nodes_tests::render_option_and_result no strong")
+        };
+
+        render(&mut root, ElementStatus::JustCreated, Some("spair"), Ok(1));
+        assert_eq!(Some("spair1".to_string()), ws_root.text_content());
+
+        render(&mut root, ElementStatus::Existing, None, Err("failed"));
+        assert_eq!(Some("failed".to_string()), ws_root.text_content());
+        // Only the (empty) match-if group remains
+        assert!(strong().text_content().unwrap_or_default().is_empty());
+        assert_eq!(0, strong().child_element_count());
+
+        render(&mut root, ElementStatus::Existing, Some("back"), Ok(2));
+        assert_eq!(Some("back2".to_string()), ws_root.text_content());
+    }
+
+    #[wasm_bindgen_test]
+    fn dynamic_child_in_static_block() {
+        use crate::render::html::HemsForDistinctNames;
+        let mut root = TestElement::new("div");
+        let render = |root: &mut TestElement, status, label: &str, count: u32| {
+            root.updater(status)
+                .static_nodes()
+                .rstatic(label)
+                .dynamic_section(|n| {
+                    n.strong(|s| s.rupdate(count).done());
+                })
+                .rstatic(label);
+        };
+        let ws_root = root.ws_element().clone();
+
+        render(&mut root, ElementStatus::JustCreated, "clicks", 0);
+        assert_eq!(Some("clicks0clicks".to_string()), ws_root.text_content());
+
+        let observer = web_sys::MutationObserver::new(&js_sys::Function::new_no_args(""))
+            .expect_throw("Create a MutationObserver");
+        let options = web_sys::MutationObserverInit::new();
+        options.set_character_data(true);
+        options.set_child_list(true);
+        options.set_subtree(true);
+        observer
+            .observe_with_options(&ws_root, &options)
+            .expect_throw("Observe the static block");
+
+        render(&mut root, ElementStatus::Existing, "ignored", 1);
+        render(&mut root, ElementStatus::Existing, "ignored", 2);
+        assert_eq!(Some("clicks2clicks".to_string()), ws_root.text_content());
+        // Only the text of the counter is written
+        let records = observer.take_records();
+        assert_eq!(2, records.length());
+        let strong = ws_root
+            .query_selector("strong")
+            .expect_throw("nodes_tests::dynamic_child_in_static_block query")
+            .expect_throw("nodes_tests::dynamic_child_in_static_block no strong");
+        for record in records.iter() {
+            let record: web_sys::MutationRecord = record.unchecked_into();
+            assert_eq!("characterData", record.type_());
+            let target = record.target().expect_throw("mutation target");
+            assert!(target
+                .parent_node()
+                .is_some_and(|p| p.is_same_node(Some(&strong))));
+        }
+        observer.disconnect();
+    }
+
+    #[wasm_bindgen_test]
+    fn render_primitive_references() {
+        let mut root = TestElement::new("div");
+        // Such as the fields of a borrowed state
+        let render = |root: &mut TestElement, status, count: &i32, ratio: &f64, done: &bool| {
+            root.updater(status)
+                .rupdate(count)
+                .rupdate(" ")
+                .rupdate(ratio)
+                .rupdate(" ")
+                .rupdate(done)
+                .rstatic(count);
+        };
+
+        render(&mut root, ElementStatus::JustCreated, &-3, &0.5, &false);
+        assert_eq!(
+            Some("-3 0.5 false-3".to_string()),
+            root.ws_element().text_content()
+        );
+
+        render(&mut root, ElementStatus::Existing, &42, &0.5, &true);
+        assert_eq!(
+            Some("42 0.5 true-3".to_string()),
+            root.ws_element().text_content()
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[wasm_bindgen_test]
+    fn render_dates() {
+        use chrono::TimeZone;
+        let mut root = TestElement::new("div");
+        let render = |root: &mut TestElement, status, date: chrono::NaiveDate| {
+            let time = chrono::Utc
+                .with_ymd_and_hms(2024, 1, 31, 8, 30, 0)
+                .single()
+                .expect_throw("nodes_tests::render_dates with_ymd_and_hms");
+            root.updater(status)
+                .rupdate(date)
+                .rupdate(" ")
+                .rupdate(&time)
+                .rupdate(" ")
+                .rstatic(time);
+        };
+        let date = |day| {
+            chrono::NaiveDate::from_ymd_opt(2024, 1, day)
+                .expect_throw("nodes_tests::render_dates from_ymd_opt")
+        };
+
+        render(&mut root, ElementStatus::JustCreated, date(31));
+        assert_eq!(
+            Some("2024-01-31 2024-01-31 08:30:00 UTC 2024-01-31 08:30:00 UTC".to_string()),
+            root.ws_element().text_content()
+        );
+
+        render(&mut root, ElementStatus::Existing, date(1));
+        assert_eq!(
+            Some("2024-01-01 2024-01-31 08:30:00 UTC 2024-01-31 08:30:00 UTC".to_string()),
+            root.ws_element().text_content()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn for_each() {
+        use crate::render::html::{HemsForDistinctNames, HemsHandMade};
+        let mut root = TestElement::new("div");
+        let render = |root: &mut TestElement, status, items: &[&str]| {
+            root.updater(status)
+                .for_each(items.iter().copied(), |item, nodes| {
+                    nodes.strong(|s| s.rupdate(item).done());
+                })
+                .em(|e| e.rstatic("end").done());
+        };
+        let ws_root = root.ws_element().clone();
+
+        render(&mut root, ElementStatus::JustCreated, &["a", "b", "c"]);
+        assert_eq!(Some("abcend".to_string()), ws_root.text_content());
+        assert_eq!(4, ws_root.child_element_count());
+
+        render(&mut root, ElementStatus::Existing, &["x"]);
+        assert_eq!(Some("xend".to_string()), ws_root.text_content());
+        assert_eq!(2, ws_root.child_element_count());
+
+        render(&mut root, ElementStatus::Existing, &["x", "y"]);
+        assert_eq!(Some("xyend".to_string()), ws_root.text_content());
+        assert_eq!(
+            Some("EM".to_string()),
+            ws_root.last_element_child().map(|e| e.tag_name())
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn match_if_active_index() {
+        use crate::render::html::HemsHandMade;
+        let mut root = TestElement::new("div");
+        let active_before_render = std::cell::Cell::new(None);
+        let render = |root: &mut TestElement, status, arm: u32| {
+            root.updater(status).match_if(|mi| {
+                active_before_render.set(mi.active_index());
+                mi.render_on_arm_index(arm).rupdate(arm).done();
+            });
+        };
+
+        render(&mut root, ElementStatus::JustCreated, 0);
+        assert_eq!(None, active_before_render.get());
+        render(&mut root, ElementStatus::Existing, 2);
+        assert_eq!(Some(0), active_before_render.get());
+        render(&mut root, ElementStatus::Existing, 2);
+        assert_eq!(Some(2), active_before_render.get());
+        render(&mut root, ElementStatus::Existing, 1);
+        assert_eq!(Some(2), active_before_render.get());
+        assert_eq!(Some("1".to_string()), root.ws_element().text_content());
+    }
+}
@@ -22,9 +22,7 @@ pub trait HemsForPartialList<'a, C: Component>: Sized + NodesUpdaterMut<C> {
     {
         let tag = HtmlTag(tag);
 
-        let (comp, state, mut r) = self
-            .nodes_updater_mut()
-            .get_list_updater(mode.use_template());
+        let (comp, state, mut r) = self.nodes_updater_mut().get_list_updater(mode);
         let _do_we_have_to_care_about_this_returned_value_ =
             r.render(comp, state, items, tag, |item: I, er: ElementUpdater<C>| {
                 render(item, er.into())
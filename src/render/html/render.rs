@@ -1,7 +1,9 @@
 /// This module provides traits that help users define how their types should be rendered.
 /// Implementation for primitive types are also provided.
-use super::{Nodes, StaticNodes};
+use super::{HemsHandMade, Nodes, StaticNodes};
 use crate::component::Component;
+#[cfg(feature = "chrono")]
+use chrono::NaiveDate;
 
 pub trait Render<C: Component> {
     fn render(self, nodes: Nodes<C>);
@@ -11,12 +13,12 @@ pub trait StaticRender<C: Component> {
     fn render(self, nodes: StaticNodes<C>);
 }
 
-macro_rules! impl_render_with_to_string {
+macro_rules! impl_render_with_display {
     ($($type:ident)+) => {
         $(
             impl<C: Component> Render<C> for $type {
                 fn render(self, nodes: Nodes<C>) {
-                    nodes.update_text(&self.to_string());
+                    nodes.render_display(&self);
                 }
             }
 
@@ -25,14 +27,74 @@ macro_rules! impl_render_with_to_string {
                     nodes.static_text(&self.to_string());
                 }
             }
+
+            impl<C: Component> Render<C> for &$type {
+                fn render(self, nodes: Nodes<C>) {
+                    nodes.render_display(self);
+                }
+            }
+
+            impl<C: Component> StaticRender<C> for &$type {
+                fn render(self, nodes: StaticNodes<C>) {
+                    nodes.static_text(&self.to_string());
+                }
+            }
         )+
     }
 }
 
-impl_render_with_to_string! {
+impl_render_with_display! {
     i8 i16 i32 i64 u8 u16 u32 u64 isize usize f32 f64 bool char
 }
 
+// Render the date as `2024-01-31`.
+#[cfg(feature = "chrono")]
+impl_render_with_display! {
+    NaiveDate
+}
+
+/// Render the date and time with the `Display` format of `chrono`, such as
+/// `2024-01-31 08:30:00 UTC`.
+#[cfg(feature = "chrono")]
+impl<C: Component, Tz: chrono::TimeZone> Render<C> for chrono::DateTime<Tz>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn render(self, nodes: Nodes<C>) {
+        nodes.render_display(&self);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<C: Component, Tz: chrono::TimeZone> StaticRender<C> for chrono::DateTime<Tz>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn render(self, nodes: StaticNodes<C>) {
+        nodes.static_text(&self.to_string());
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<C: Component, Tz: chrono::TimeZone> Render<C> for &chrono::DateTime<Tz>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn render(self, nodes: Nodes<C>) {
+        nodes.render_display(self);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<C: Component, Tz: chrono::TimeZone> StaticRender<C> for &chrono::DateTime<Tz>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    fn render(self, nodes: StaticNodes<C>) {
+        nodes.static_text(&self.to_string());
+    }
+}
+
 impl<C: Component> StaticRender<C> for &str {
     fn render(self, nodes: StaticNodes<C>) {
         nodes.static_text(self);
@@ -69,6 +131,35 @@ impl<C: Component> Render<C> for String {
     }
 }
 
+/// Render `format_args!(...)` without allocating a new `String` on every render.
+impl<C: Component> Render<C> for std::fmt::Arguments<'_> {
+    fn render(self, nodes: Nodes<C>) {
+        nodes.update_text_fmt(self);
+    }
+}
+
+/// Render the value if it is `Some`, otherwise, render nothing. The nodes of the
+/// value are removed when it becomes `None`.
+impl<C: Component, T: Render<C>> Render<C> for Option<T> {
+    fn render(self, nodes: Nodes<C>) {
+        nodes.match_if(|mi| match self {
+            Some(value) => mi.render_on_arm_index(0).rupdate(value).done(),
+            None => mi.render_on_arm_index(1).done(),
+        });
+    }
+}
+
+/// Render either the `Ok` or the `Err` value, the nodes are replaced when the
+/// variant changes.
+impl<C: Component, T: Render<C>, E: Render<C>> Render<C> for Result<T, E> {
+    fn render(self, nodes: Nodes<C>) {
+        nodes.match_if(|mi| match self {
+            Ok(value) => mi.render_on_arm_index(0).rupdate(value).done(),
+            Err(error) => mi.render_on_arm_index(1).rupdate(error).done(),
+        });
+    }
+}
+
 pub trait ElementRender<C: Component> {
     const ELEMENT_TAG: &'static str;
     fn render(self, item: crate::Element<C>);
@@ -185,14 +185,33 @@ macro_rules! make_trait_for_attributes_with_predefined_values {
         }
 
         pub trait $AttributeValueTrait<C: Component> {
-            fn render(self, attribute_name: &str, element: &mut crate::render::base::ElementUpdater<C>);
+            fn render(self, attribute_name: &'static str, element: &mut crate::render::base::ElementUpdater<C>);
         }
 
         impl<C: Component> $AttributeValueTrait<C> for $AttributeValueType {
-            fn render(self, attribute_name: &str, element: &mut crate::render::base::ElementUpdater<C>) {
+            fn render(self, attribute_name: &'static str, element: &mut crate::render::base::ElementUpdater<C>) {
                 element.set_str_attribute(attribute_name, self.as_str());
             }
         }
+
+        // For values that are not covered by the enum
+        impl<C: Component> $AttributeValueTrait<C> for &str {
+            fn render(self, attribute_name: &'static str, element: &mut crate::render::base::ElementUpdater<C>) {
+                element.set_str_attribute(attribute_name, self);
+            }
+        }
+
+        impl<C: Component> $AttributeValueTrait<C> for &String {
+            fn render(self, attribute_name: &'static str, element: &mut crate::render::base::ElementUpdater<C>) {
+                element.set_str_attribute(attribute_name, self);
+            }
+        }
+
+        impl<C: Component> $AttributeValueTrait<C> for String {
+            fn render(self, attribute_name: &'static str, element: &mut crate::render::base::ElementUpdater<C>) {
+                element.set_str_attribute(attribute_name, &self);
+            }
+        }
     };
     (
         @each_fn
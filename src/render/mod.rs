@@ -8,17 +8,36 @@ pub mod svg;
 
 pub struct SeeDeprecationNoteOrMethodDocForInformation;
 
+/// How the elements of list items are created.
+///
+/// The lifetime was added with `Template`, which borrows the given element. It is
+/// elided in function arguments, but a struct that stores a `ListElementCreation`
+/// must now name it, e.g. `ListElementCreation<'static>` if only `Clone` and `New`
+/// are stored.
 #[derive(Copy, Clone)]
-pub enum ListElementCreation {
+pub enum ListElementCreation<'a> {
     Clone,
     New,
+    /// Create list items by cloning the given element (deeply, including its
+    /// attributes and children) instead of the first rendered item. The rendered
+    /// content of an item is appended after the children of the template.
+    /// New items are cloned from the template given in the current render, in both
+    /// keyed and non-keyed lists. Existing items are kept as they are.
+    Template(&'a web_sys::Element),
 }
 
-impl ListElementCreation {
+impl<'a> ListElementCreation<'a> {
     pub fn use_template(&self) -> bool {
         match self {
-            Self::Clone => true,
+            Self::Clone | Self::Template(_) => true,
             Self::New => false,
         }
     }
+
+    pub fn template(&self) -> Option<&'a web_sys::Element> {
+        match self {
+            Self::Template(template) => Some(template),
+            Self::Clone | Self::New => None,
+        }
+    }
 }
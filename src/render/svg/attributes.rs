@@ -24,6 +24,31 @@ pub trait SamsHandMade<C: Component>: Sized + ElementUpdaterMut<C> {
         self
     }
 
+    /// Like `class_if`, but toggles a group of classes together with only one
+    /// attribute slot.
+    fn classes_if(mut self, class_on: bool, class_names: &[&str]) -> Self {
+        self.element_updater_mut().classes_if(class_on, class_names);
+        self
+    }
+
+    /// Add or remove `class_name` when `class_on` changes, without rendering the component.
+    #[cfg(feature = "queue-render")]
+    fn qr_class_if(
+        mut self,
+        class_name: &'static str,
+        class_on: &crate::queue_render::val::QrVal<bool>,
+    ) -> Self {
+        self.element_updater_mut().qr_class_if(class_name, class_on);
+        self
+    }
+
+    /// Set classes from a whitespace-separated list, classes that are not in the
+    /// list are not touched.
+    fn class_tokens(mut self, class_names: &str) -> Self {
+        self.element_updater_mut().class_tokens(class_names);
+        self
+    }
+
     /// Set the `first_class` if `first` is true, otherwise, set the `second_class`
     fn class_or(mut self, first: bool, first_class: &str, second_class: &str) -> Self {
         self.element_updater_mut()
@@ -36,6 +61,27 @@ pub trait SamsHandMade<C: Component>: Sized + ElementUpdaterMut<C> {
         self
     }
 
+    /// Execute `f` with the element only when it is just created (or cloned), not
+    /// on subsequent renders.
+    fn once_created(mut self, f: impl FnOnce(&web_sys::Element)) -> Self {
+        self.element_updater_mut().once_created(f);
+        self
+    }
+
+    /// Execute `f` with the element on every render.
+    fn with_ws_element(mut self, f: impl FnOnce(&web_sys::Element)) -> Self {
+        self.element_updater_mut().with_ws_element(f);
+        self
+    }
+
+    /// Execute `f` with the bounding client rect of the element after the current
+    /// render, such as to position a tooltip. Reading the rect forces a layout, avoid
+    /// measuring many elements on every render.
+    fn measure(mut self, f: impl FnOnce(web_sys::DomRect) + 'static) -> Self {
+        self.element_updater_mut().measure(f);
+        self
+    }
+
     /// This method only accepts a &Route. If you want set `href` with a str, please use `href_str()`.
     /// It is possible to make this method accept both a Route and a str, but I intentionally make
     /// them two separate methods. The purpose is to remind users to use a Route when it's possible.
@@ -44,11 +90,32 @@ pub trait SamsHandMade<C: Component>: Sized + ElementUpdaterMut<C> {
         self
     }
 
+    /// Set both `href` and `xlink:href` to `value`, such as `"#icon-id"` for a `<use>`.
+    fn xlink_href(mut self, value: &str) -> Self {
+        self.element_updater_mut().svg_href(value);
+        self
+    }
+
     fn id(mut self, id: &str) -> Self {
         self.element_updater_mut().id(id);
         self
     }
 
+    /// Set `fill` to `url(#id)` to paint the element with a paint server (such as a
+    /// `<linearGradient>` in `<defs>`) that has the given `id`.
+    fn fill_url(mut self, id: &str) -> Self {
+        self.element_updater_mut()
+            .set_string_attribute("fill", format!("url(#{})", id));
+        self
+    }
+
+    /// Set `stroke` to `url(#id)`, similar to [`SamsHandMade::fill_url`].
+    fn stroke_url(mut self, id: &str) -> Self {
+        self.element_updater_mut()
+            .set_string_attribute("stroke", format!("url(#{})", id));
+        self
+    }
+
     fn scroll_to_top_if(self, need_to_scroll: bool) -> Self {
         if need_to_scroll {
             self.element_updater()
@@ -163,7 +230,7 @@ make_trait_for_attribute_methods! {
         LengthPercentage          height
         str          horiz_adv_x "horiz-adv-x"
         str          horiz_origin_x "horiz-origin-x"
-        str          href
+        str          href_str "href" // method named `href` is used for routing
         str          hreflang
         //str          id
         str          ideographic
@@ -233,7 +300,7 @@ make_trait_for_attribute_methods! {
         str          preserve_alpha "preserveAlpha"
         str          preserve_aspect_ratio "preserveAspectRatio"
         str          primitive_units "primitiveUnits"
-        f64          r
+        LengthPercentage          r
         str          radius
         str          ref_x "refX"
         str          ref_y "refY"
@@ -247,8 +314,8 @@ make_trait_for_attribute_methods! {
         str          restart
         str          result
         str          rotate
-        str          rx
-        str          ry
+        LengthPercentage          rx
+        LengthPercentage          ry
         str          scale
         str          seed
         str          shape_rendering "shape-rendering"
@@ -275,7 +342,7 @@ make_trait_for_attribute_methods! {
         str          stroke_linejoin "stroke-linejoin"
         str          stroke_miterlimit "stroke-miterlimit"
         str          stroke_opacity "stroke-opacity"
-        f64          stroke_width "stroke-width"
+        LengthPercentage          stroke_width "stroke-width"
         str          style
         str          surface_scale "surfaceScale"
         str          system_language "systemLanguage"
@@ -413,3 +480,224 @@ impl<'er, C: Component> SamsHandMade<C> for SvgStaticAttributesOnly<'er, C> {}
 
 impl<'er, C: Component> SamsForDistinctNames<C> for SvgAttributesOnly<'er, C> {}
 impl<'er, C: Component> SamsHandMade<C> for SvgAttributesOnly<'er, C> {}
+
+#[cfg(test)]
+mod svg_attributes_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::render::html::HemsHandMade;
+    use crate::render::svg::{SamsForDistinctNames, SemsForAmbiguousNames, SemsForDistinctNames};
+
+    const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+    const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+    const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+    struct Chart;
+
+    impl Component for Chart {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.svg(|s| {
+                s.view_box("0 0 100 100")
+                    .circle(|c| {
+                        c.cx(50)
+                            .cy(50.5)
+                            .r("40%")
+                            .fill("red")
+                            .stroke("black")
+                            .stroke_width(2);
+                    })
+                    .path(|p| {
+                        p.d("M 10 10 L 90 90")
+                            .fill("none")
+                            .stroke_width(1.5)
+                            .transform("rotate(45 50 50)");
+                    })
+                    .polygon(|p| {
+                        p.points("0,0 10,0 10,10");
+                    });
+            });
+        }
+    }
+
+    impl crate::application::Application for Chart {
+        fn init(_: &Comp<Self>) -> Self {
+            Self
+        }
+    }
+
+    fn query(root: &crate::dom::Element, selector: &str) -> web_sys::Element {
+        root.ws_element()
+            .as_ref()
+            .query_selector(selector)
+            .expect_throw("svg_attributes_tests::query")
+            .expect_throw("svg_attributes_tests::query no element")
+    }
+
+    #[wasm_bindgen_test]
+    fn circle_and_path() {
+        let (root, _rc) = crate::test_utils::mount::<Chart>();
+
+        let svg = query(&root, "svg");
+        assert_eq!(Some(SVG_NAMESPACE), svg.namespace_uri().as_deref());
+        assert_eq!(Some("0 0 100 100"), svg.get_attribute("viewBox").as_deref());
+
+        let circle = query(&root, "circle");
+        assert_eq!(Some(SVG_NAMESPACE), circle.namespace_uri().as_deref());
+        // Attributes of SVG elements are in the null namespace
+        let attribute = |name| circle.get_attribute_ns(None, name);
+        assert_eq!(Some("50"), attribute("cx").as_deref());
+        assert_eq!(Some("50.5"), attribute("cy").as_deref());
+        assert_eq!(Some("40%"), attribute("r").as_deref());
+        assert_eq!(Some("red"), attribute("fill").as_deref());
+        assert_eq!(Some("black"), attribute("stroke").as_deref());
+        assert_eq!(Some("2"), attribute("stroke-width").as_deref());
+
+        let path = query(&root, "path");
+        assert_eq!(Some(SVG_NAMESPACE), path.namespace_uri().as_deref());
+        let attribute = |name| path.get_attribute_ns(None, name);
+        assert_eq!(Some("M 10 10 L 90 90"), attribute("d").as_deref());
+        assert_eq!(Some("none"), attribute("fill").as_deref());
+        assert_eq!(Some("1.5"), attribute("stroke-width").as_deref());
+        assert_eq!(Some("rotate(45 50 50)"), attribute("transform").as_deref());
+
+        let polygon = query(&root, "polygon");
+        assert_eq!(
+            Some("0,0 10,0 10,10"),
+            polygon.get_attribute_ns(None, "points").as_deref()
+        );
+    }
+
+    struct Icons;
+
+    impl Component for Icons {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::html::MethodsForHtmlElementContent;
+            use crate::render::svg::{SamsHandMade, SemsHandMade};
+            element.svg(|s| {
+                s.defs(|d| {
+                    d.symbol(|s| {
+                        s.id("icon-dot").view_box("0 0 10 10").circle(|c| {
+                            c.cx(5).cy(5).r(5);
+                        });
+                    });
+                })
+                .use_("#icon-dot", |u| {
+                    u.width(20).height(20);
+                })
+                .foreign_object(|f| {
+                    f.width(100).height(20).html_element("div", |d| {
+                        d.rupdate("Hello");
+                    });
+                });
+            });
+        }
+    }
+
+    impl crate::application::Application for Icons {
+        fn init(_: &Comp<Self>) -> Self {
+            Self
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn use_symbol_and_foreign_object() {
+        let (root, _rc) = crate::test_utils::mount::<Icons>();
+
+        let symbol = query(&root, "symbol");
+        assert_eq!(Some(SVG_NAMESPACE), symbol.namespace_uri().as_deref());
+        assert_eq!(Some("icon-dot"), symbol.get_attribute("id").as_deref());
+
+        let use_element = query(&root, "use");
+        assert_eq!(Some(SVG_NAMESPACE), use_element.namespace_uri().as_deref());
+        assert_eq!(
+            Some("#icon-dot"),
+            use_element.get_attribute_ns(None, "href").as_deref()
+        );
+        assert_eq!(
+            Some("#icon-dot"),
+            use_element
+                .get_attribute_ns(Some(XLINK_NAMESPACE), "href")
+                .as_deref()
+        );
+        assert_eq!(Some("20"), use_element.get_attribute("width").as_deref());
+
+        let foreign_object = query(&root, "foreignObject");
+        assert_eq!(
+            Some(SVG_NAMESPACE),
+            foreign_object.namespace_uri().as_deref()
+        );
+        let div = foreign_object
+            .first_element_child()
+            .expect_throw("svg_attributes_tests::use_symbol_and_foreign_object div");
+        assert_eq!(Some(HTML_NAMESPACE), div.namespace_uri().as_deref());
+        assert_eq!("DIV", div.tag_name());
+        assert_eq!(Some("Hello".to_string()), div.text_content());
+    }
+
+    struct Gradient;
+
+    impl Component for Gradient {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::svg::SamsHandMade;
+            element.svg(|s| {
+                s.defs(|d| {
+                    d.linear_gradient(|g| {
+                        g.id("sky")
+                            .x1(0)
+                            .y1(0)
+                            .x2(0)
+                            .y2(1)
+                            .stop(|s| {
+                                s.offset("0%").stop_color("blue");
+                            })
+                            .stop(|s| {
+                                s.offset("100%").stop_color("white");
+                            });
+                    })
+                    .radial_gradient(|g| {
+                        g.id("sun").stop(|s| {
+                            s.offset("0%").stop_color("yellow");
+                        });
+                    });
+                })
+                .rect(|r| {
+                    r.width(100).height(100).fill_url("sky").stroke_url("sun");
+                });
+            });
+        }
+    }
+
+    impl crate::application::Application for Gradient {
+        fn init(_: &Comp<Self>) -> Self {
+            Self
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn gradient_in_defs() {
+        let (root, _rc) = crate::test_utils::mount::<Gradient>();
+
+        let gradient = query(&root, "defs > linearGradient");
+        assert_eq!(Some(SVG_NAMESPACE), gradient.namespace_uri().as_deref());
+        assert_eq!(Some("sky"), gradient.get_attribute("id").as_deref());
+        assert_eq!(2, gradient.child_element_count());
+        let stop = gradient
+            .last_element_child()
+            .expect_throw("svg_attributes_tests::gradient_in_defs stop");
+        assert_eq!(Some(SVG_NAMESPACE), stop.namespace_uri().as_deref());
+        assert_eq!(Some("100%"), stop.get_attribute("offset").as_deref());
+        assert_eq!(Some("white"), stop.get_attribute("stop-color").as_deref());
+
+        let radial = query(&root, "defs > radialGradient");
+        assert_eq!(Some(SVG_NAMESPACE), radial.namespace_uri().as_deref());
+
+        let rect = query(&root, "rect");
+        assert_eq!(Some("url(#sky)"), rect.get_attribute("fill").as_deref());
+        assert_eq!(Some("url(#sun)"), rect.get_attribute("stroke").as_deref());
+    }
+}
@@ -84,6 +84,34 @@ pub trait SemsForKeyedList<'a, C: Component>:
             I::render,
         )
     }
+
+    /// Render the entries of a map as a keyed list, the keys of the map are used as
+    /// the keys of the list. The entries are rendered in the iteration order of the map,
+    /// which is sorted by keys for a `BTreeMap`. The iteration order of a `HashMap` is
+    /// not specified and may change when the map changes, so prefer a `BTreeMap` if the
+    /// order matters.
+    fn keyed_map<'m, K, V, M, R>(
+        self,
+        map: M,
+        mode: ListElementCreation,
+        tag: &'static str,
+        fn_render: R,
+    ) -> NodesExtensions<'a>
+    where
+        M: IntoIterator<Item = (&'m K, &'m V)>,
+        K: 'm + PartialEq<ListItemKey>,
+        V: 'm,
+        R: Fn(&'m K, &'m V, SvgElementUpdater<C>),
+        ListItemKey: for<'k> From<&'k K>,
+    {
+        self.keyed_list_with_render(
+            map,
+            mode,
+            tag,
+            |(key, _): &(&'m K, &'m V)| *key,
+            |(key, value), element| fn_render(key, value, element),
+        )
+    }
 }
 
 impl<'a, C: Component> SemsForKeyedList<'a, C> for SvgElementUpdater<'a, C> {}
@@ -36,6 +36,23 @@ pub trait SemsForList<'a, C: Component>:
         self.make_nodes_extensions()
     }
 
+    /// Same as `list_with_render` but `render` also receives the index of the item.
+    fn indexed_list_with_render<I, II, R>(
+        self,
+        items: II,
+        mode: ListElementCreation,
+        tag: &'static str,
+        render: R,
+    ) -> NodesExtensions<'a>
+    where
+        II: Iterator<Item = I>,
+        R: Fn(usize, I, crate::SvgElement<C>),
+    {
+        self.list_with_render(items.enumerate(), mode, tag, |(index, item), element| {
+            render(index, item, element)
+        })
+    }
+
     fn lwr_clone<I, II, R>(self, items: II, tag: &'static str, render: R) -> NodesExtensions<'a>
     where
         II: Iterator<Item = I>,
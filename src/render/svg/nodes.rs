@@ -8,6 +8,7 @@ use super::{
 use crate::{
     component::{Child, ChildComp, Comp, Component},
     render::base::{ElementUpdaterMut, MatchIfUpdater, NodesUpdater, NodesUpdaterMut},
+    render::html::HtmlTag,
 };
 
 #[cfg(feature = "queue-render")]
@@ -66,6 +67,33 @@ pub trait SemsHandMade<C: Component>: Sized {
         this
     }
 
+    /// Render a `<use>` that references `href` (such as `"#icon-id"`), both `href`
+    /// and `xlink:href` are set.
+    fn use_(self, href: &str, f: impl FnOnce(SvgElementUpdater<C>)) -> Self::Output {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        if render.require_update() {
+            let mut e = render.get_element_updater(SvgTag("use"));
+            e.svg_href(href);
+            f(e.into());
+        }
+        render.next_index();
+        this
+    }
+
+    /// Render an HTML element, for example, as a child of a `<foreignObject>`. The
+    /// element and its content are in the HTML namespace.
+    fn html_element(self, tag: &'static str, f: impl FnOnce(crate::Element<C>)) -> Self::Output {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        if render.require_update() {
+            let e = render.get_element_updater(HtmlTag(tag));
+            f(e.into());
+        }
+        render.next_index();
+        this
+    }
+
     fn component_ref<CC: Component>(self, child: &ChildComp<CC>) -> Self::Output {
         let mut this: Self::Output = self.into();
         let render = this.nodes_updater_mut();
@@ -76,6 +104,17 @@ pub trait SemsHandMade<C: Component>: Sized {
         this
     }
 
+    /// Use this instead of `component_ref` to render a child component in a list item.
+    fn component_in_list<CC: Component>(self, child: &ChildComp<CC>) -> Self::Output {
+        let mut this: Self::Output = self.into();
+        let render = this.nodes_updater_mut();
+        if render.require_update() {
+            render.component_in_list(child);
+        }
+        render.next_index();
+        this
+    }
+
     fn component_owned<CC: Component, T: 'static + Clone + PartialEq>(
         self,
         create_child_comp: impl FnOnce(&C, &Comp<C>) -> Child<C, CC, T>,
@@ -296,6 +335,12 @@ impl<'n, C: Component> SemsHandMade<C> for SvgNodesOwned<'n, C> {
 impl<'n, C: Component> SemsHandMade<C> for SvgStaticNodesOwned<'n, C> {
     type Output = Self;
 }
+impl<'n, C: Component> SemsHandMade<C> for SvgStaticAttributes<'n, C> {
+    type Output = SvgNodesOwned<'n, C>;
+}
+impl<'n, C: Component> SemsHandMade<C> for SvgElementUpdater<'n, C> {
+    type Output = SvgNodesOwned<'n, C>;
+}
 
 impl<'h, 'n: 'h, C: Component> SemsForDistinctNames<C> for SvgNodes<'h, 'n, C> {
     type Output = Self;
@@ -321,6 +366,17 @@ impl<'h, 'n: 'h, C: Component> SvgNodes<'h, 'n, C> {
         self.0.update_text(text);
     }
 
+    pub(super) fn update_text_fmt(self, args: std::fmt::Arguments) {
+        self.0.update_text_fmt(args);
+    }
+
+    /// Render `value` as a text, the text is formatted into a reused buffer instead
+    /// of a new `String` on every render. Useful to implement `Render` for a type
+    /// that implements `Display`, such as an enum.
+    pub fn render_display(self, value: &impl std::fmt::Display) {
+        self.0.render_display(value);
+    }
+
     pub fn done(self) {}
 
     pub fn state(&self) -> &'n C {
@@ -358,6 +414,14 @@ impl<'h, 'n: 'h, C: Component> SvgNodes<'h, 'n, C> {
         func(n);
         self
     }
+
+    /// Render the nodes in `f` in static mode, they are only rendered when the
+    /// parent element is just created. The update mode is restored after `f`.
+    pub fn static_section(mut self, f: impl FnOnce(SvgStaticNodes<C>)) -> Self {
+        f(SvgStaticNodes::new(self.0));
+        self.nodes_updater_mut().set_update_mode();
+        self
+    }
 }
 
 impl<'h, 'n: 'h, C: Component> SvgStaticNodes<'h, 'n, C> {
@@ -392,6 +456,14 @@ impl<'h, 'n: 'h, C: Component> SvgStaticNodes<'h, 'n, C> {
     pub fn relement<R: SvgElementRender<C>>(self, render: R) -> Self {
         self.render_element(R::ELEMENT_TAG, |e| render.render(e))
     }
+
+    /// Render the nodes in `f` in update mode, they are updated on every render.
+    /// The static mode is restored after `f`.
+    pub fn dynamic_section(mut self, f: impl FnOnce(SvgNodes<C>)) -> Self {
+        f(SvgNodes::new(self.0));
+        self.nodes_updater_mut().set_static_mode();
+        self
+    }
 }
 
 impl<'n, C: Component> SvgNodesOwned<'n, C> {
@@ -424,6 +496,14 @@ impl<'n, C: Component> SvgNodesOwned<'n, C> {
         func(n);
         self
     }
+
+    /// Render the nodes in `f` in static mode, they are only rendered when the
+    /// parent element is just created. The update mode is restored after `f`.
+    pub fn static_section(mut self, f: impl FnOnce(SvgStaticNodes<C>)) -> Self {
+        f(SvgStaticNodes::new(&mut self.0));
+        self.nodes_updater_mut().set_update_mode();
+        self
+    }
 }
 
 impl<'n, C: Component> SvgStaticNodesOwned<'n, C> {
@@ -450,6 +530,14 @@ impl<'n, C: Component> SvgStaticNodesOwned<'n, C> {
     pub fn relement<R: SvgElementRender<C>>(self, render: R) -> Self {
         self.render_element(R::ELEMENT_TAG, |e| render.render(e))
     }
+
+    /// Render the nodes in `f` in update mode, they are updated on every render.
+    /// The static mode is restored after `f`.
+    pub fn dynamic_section(mut self, f: impl FnOnce(SvgNodes<C>)) -> Self {
+        f(SvgNodes::new(&mut self.0));
+        self.nodes_updater_mut().set_static_mode();
+        self
+    }
 }
 
 pub trait MethodsForSvgElementContent<'n, C: Component>:
@@ -528,6 +616,12 @@ impl<'a, C: Component> SvgMatchIfUpdater<'a, C> {
         SvgNodesOwned::new(self.0.render_on_arm_index(index))
     }
 
+    /// The index of the arm that was rendered by the last render, `None` on the
+    /// first render.
+    pub fn active_index(&self) -> Option<u32> {
+        self.0.active_index()
+    }
+
     pub fn state(&self) -> &'a C {
         self.0.state()
     }
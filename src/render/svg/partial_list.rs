@@ -21,9 +21,7 @@ pub trait SemsForPartialList<'a, C: Component>: Sized + NodesUpdaterMut<C> {
         II: Iterator<Item = I>,
         R: Fn(I, crate::SvgElement<C>),
     {
-        let (comp, state, mut r) = self
-            .nodes_updater_mut()
-            .get_list_updater(mode.use_template());
+        let (comp, state, mut r) = self.nodes_updater_mut().get_list_updater(mode);
         let _do_we_have_to_care_about_this_returned_value_ = r.render(
             comp,
             state,
@@ -0,0 +1,113 @@
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+
+/// A wrapper of `ResizeObserver`. The callback is called with the content-box
+/// `(width, height)` of an observed element every time its size changes. The
+/// observer is disconnected when it is dropped, so it should be stored (for example,
+/// in the component state) as long as the element needs to be observed.
+pub struct ResizeObserver {
+    observer: web_sys::ResizeObserver,
+    _closure: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl ResizeObserver {
+    pub fn new(callback: crate::CallbackArg<(f64, f64)>) -> Self {
+        let closure = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+            for entry in entries.iter() {
+                let rect = entry
+                    .unchecked_into::<web_sys::ResizeObserverEntry>()
+                    .content_rect();
+                callback.call_or_queue((rect.width(), rect.height()));
+            }
+        }) as Box<dyn FnMut(js_sys::Array)>);
+        let observer = web_sys::ResizeObserver::new(closure.as_ref().unchecked_ref())
+            .expect_throw("resize_observer::ResizeObserver::new");
+        Self {
+            observer,
+            _closure: closure,
+        }
+    }
+
+    /// Start observing the element. Nothing happens if the `WsRef` is not set yet.
+    pub fn observe<T: JsCast + AsRef<web_sys::Element>>(&self, element: &crate::WsRef<T>) {
+        element.execute(|element| self.observer.observe(element.as_ref()));
+    }
+
+    pub fn unobserve<T: JsCast + AsRef<web_sys::Element>>(&self, element: &crate::WsRef<T>) {
+        element.execute(|element| self.observer.unobserve(element.as_ref()));
+    }
+
+    /// Stop observing all elements.
+    pub fn disconnect(&self) {
+        self.observer.disconnect();
+    }
+}
+
+impl Drop for ResizeObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod resize_observer_tests {
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::render::html::{HamsForDistinctNames, HemsForDistinctNames};
+    use crate::test_utils::sleep;
+
+    struct State {
+        width: u32,
+        size: Option<(f64, f64)>,
+        div: crate::WsRef<web_sys::HtmlElement>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, element: crate::Element<Self>) {
+            element.div(|d| {
+                self.div.set(&d);
+                d.style(format!("width: {}px; height: 20px;", self.width));
+            });
+        }
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                width: 100,
+                size: None,
+                div: crate::WsRef::none(),
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn observe_resizing() {
+        let (root, rc) = crate::test_utils::mount_in_body::<State>();
+        let size = || rc.comp_instance().state().size;
+
+        let observer = super::ResizeObserver::new(
+            rc.comp()
+                .callback_arg_mut(|state: &mut State, size| state.size = Some(size)),
+        );
+        observer.observe(&rc.comp_instance().state().div);
+        sleep(50).await;
+        assert_eq!(Some((100.0, 20.0)), size());
+
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.width = 150)
+            .call();
+        sleep(50).await;
+        assert_eq!(Some((150.0, 20.0)), size());
+
+        observer.unobserve(&rc.comp_instance().state().div);
+        rc.comp()
+            .callback_once_mut(|state: &mut State| state.width = 200)
+            .call();
+        sleep(50).await;
+        assert_eq!(Some((150.0, 20.0)), size());
+
+        root.ws_element().as_ref().remove();
+    }
+}
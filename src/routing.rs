@@ -6,17 +6,27 @@ use crate::component::{Comp, Component};
 struct SpairRouter {
     router: Box<dyn std::any::Any>,
     current_url: Rc<RefCell<Option<String>>>,
-    _pop_state_closure: Option<wasm_bindgen::closure::Closure<dyn Fn(web_sys::PopStateEvent)>>,
+    _pop_state_closure: Option<PopStateListener>,
+}
+
+/// Remove the `popstate` listener from the window when the router is replaced or cleared.
+struct PopStateListener(wasm_bindgen::closure::Closure<dyn Fn(web_sys::PopStateEvent)>);
+
+impl Drop for PopStateListener {
+    fn drop(&mut self) {
+        crate::utils::remove_event_listener_on_window("popstate", self.0.as_ref().unchecked_ref());
+    }
 }
 
 impl SpairRouter {
-    fn execute_routing<R: Router>(&self) {
+    fn execute_routing<R: Router>(&self, state: wasm_bindgen::JsValue) {
+        self::normalize_current_url(R::TRAILING_SLASH, &state);
         let location = match get_new_location(&self.current_url) {
             Some(location) => location,
             None => return,
         };
         if let Some(router) = self.router.downcast_ref::<R>() {
-            router.routing(location);
+            router.routing_with_state(location, state);
         }
     }
 }
@@ -29,22 +39,87 @@ thread_local! {
     }));
 }
 
+/// How the trailing slash of the path of an url is canonicalized, so that `/about`
+/// and `/about/` are not treated as different routes. See [`Router::TRAILING_SLASH`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// Paths are left as they are.
+    Keep,
+    /// `/about/` becomes `/about`. The root path `/` is kept.
+    Strip,
+    /// `/about` becomes `/about/`.
+    Require,
+}
+
+impl TrailingSlash {
+    /// Normalize the path of `url`, the query and the fragment are kept as they are.
+    /// An url without a path (such as `#section` or `?page=2`) is not changed.
+    pub fn normalize(self, url: &str) -> String {
+        let path_end = url.find(['?', '#']).unwrap_or(url.len());
+        let (path, rest) = url.split_at(path_end);
+        if path.is_empty() {
+            return url.to_string();
+        }
+        match self {
+            Self::Keep => url.to_string(),
+            Self::Strip => {
+                let stripped = path.trim_end_matches('/');
+                let stripped = if stripped.is_empty() { "/" } else { stripped };
+                format!("{}{}", stripped, rest)
+            }
+            Self::Require => format!("{}/{}", path.trim_end_matches('/'), rest),
+        }
+    }
+}
+
 pub trait Router: std::any::Any {
+    /// How trailing slashes are canonicalized. Urls of the routes are normalized
+    /// before they are pushed to the history, and if the current url is not in the
+    /// canonical form when the routing is executed, it is replaced (without adding a
+    /// history entry) before `routing` is called.
+    const TRAILING_SLASH: TrailingSlash = TrailingSlash::Keep;
+
     fn routing(&self, location: web_sys::Location);
+
+    /// Same as `routing` but also receives `history.state` of the current entry
+    /// (`JsValue::NULL` if there is none). On `popstate`, this is the state that
+    /// was given to [`push_state_with`], which is useful for restoring the scroll
+    /// position or some view state when the user navigates back. The default
+    /// implementation ignores the state and just calls `routing`.
+    fn routing_with_state(&self, location: web_sys::Location, _state: wasm_bindgen::JsValue) {
+        self.routing(location);
+    }
 }
 
 pub trait Routes {
     type Router: Router;
     fn url(&self) -> String;
+
+    /// `url()` with its trailing slash normalized by `Self::Router::TRAILING_SLASH`.
+    fn normalized_url(&self) -> String {
+        <Self::Router as Router>::TRAILING_SLASH.normalize(&self.url())
+    }
+
     fn update_address_bar(&self) {
+        self.update_address_bar_with_state(&wasm_bindgen::JsValue::NULL);
+    }
+
+    /// Push a new history entry for this route, with `state` as its `history.state`.
+    fn update_address_bar_with_state(&self, state: &wasm_bindgen::JsValue) {
         crate::utils::window()
             .history()
             .expect_throw("Unable to get history")
-            .push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&self.url()))
+            .push_state_with_url(state, "", Some(&self.normalized_url()))
             .expect_throw("Error on push_state_with_url");
     }
 
+    /// Navigate to this route. Nothing happens if the url of the route is the current
+    /// url (for example, when the user clicks the link to the active page), so no new
+    /// history entry is added and the routing is not executed again.
     fn execute_routing(&self) {
+        if self::is_current_url(&self.normalized_url()) {
+            return;
+        }
         self.update_address_bar();
         crate::component::update_component(self::execute_routing::<Self::Router>);
     }
@@ -70,14 +145,50 @@ pub fn set_router<R: Router>(r: R) {
     });
 }
 
+/// Remove the router if it is an `R`. Its `popstate` listener is removed from the window,
+/// and the current url is forgotten, so a router that is set later executes its routing
+/// on the first `execute_routing`.
+pub fn remove_router<R: Router>() {
+    ROUTER.with(|router| {
+        if let Ok(mut router) = router.try_borrow_mut() {
+            if router.router.downcast_ref::<R>().is_none() {
+                return;
+            }
+            router.router = Box::new(());
+            router._pop_state_closure = None;
+            *router
+                .current_url
+                .try_borrow_mut()
+                .expect_throw("Multiple mutable borrow on current_url") = None;
+        }
+    });
+}
+
 pub fn execute_routing<R: Router>() {
     ROUTER.with(|router| {
         if let Ok(router) = router.try_borrow() {
-            router.execute_routing::<R>();
+            router.execute_routing::<R>(current_history_state());
         }
     });
 }
 
+/// Push a new entry with the given `state` to the history, then execute the routing.
+/// The state will be given back to `Router::routing_with_state` when the user navigates
+/// back to this entry.
+pub fn push_state_with<R: Routes>(routes: &R, state: wasm_bindgen::JsValue) {
+    routes.update_address_bar_with_state(&state);
+    crate::component::update_component(self::execute_routing::<R::Router>);
+}
+
+/// Get `history.state` of the current entry, `JsValue::NULL` if there is none.
+pub fn current_history_state() -> wasm_bindgen::JsValue {
+    crate::utils::window()
+        .history()
+        .expect_throw("Unable to get history")
+        .state()
+        .expect_throw("Unable to get history.state")
+}
+
 pub fn register_routing_callback<C: Component>(comp: &Comp<C>) {
     modify_router::<C, _>(|router| C::register_routing_callback(router, comp))
 }
@@ -101,12 +212,11 @@ pub fn modify_router<C: Component, F: FnOnce(&mut <<C as Component>::Routes as R
     })
 }
 
-fn register_pop_state_event<R: Router>(
-) -> wasm_bindgen::closure::Closure<dyn Fn(web_sys::PopStateEvent)> {
-    let closure = move |_: web_sys::PopStateEvent| {
+fn register_pop_state_event<R: Router>() -> PopStateListener {
+    let closure = move |event: web_sys::PopStateEvent| {
         ROUTER.with(|router| {
             if let Ok(router) = router.try_borrow() {
-                router.execute_routing::<R>();
+                router.execute_routing::<R>(event.state());
             }
         })
     };
@@ -117,7 +227,48 @@ fn register_pop_state_event<R: Router>(
 
     crate::utils::register_event_listener_on_window("popstate", closure.as_ref().unchecked_ref());
 
-    closure
+    PopStateListener(closure)
+}
+
+fn is_current_url(url: &str) -> bool {
+    let href = crate::utils::window()
+        .location()
+        .href()
+        .expect_throw("Unable to get window.location.href");
+    // `url` may be relative to the current location
+    match web_sys::Url::new_with_base(url, &href) {
+        Ok(url) => url.href() == href,
+        Err(_) => false,
+    }
+}
+
+fn normalize_current_url(trailing_slash: TrailingSlash, state: &wasm_bindgen::JsValue) {
+    if trailing_slash == TrailingSlash::Keep {
+        return;
+    }
+    let location = crate::utils::window().location();
+    let path = location
+        .pathname()
+        .expect_throw("Unable to get window.location.pathname");
+    let normalized = trailing_slash.normalize(&path);
+    if normalized == path {
+        return;
+    }
+    let search = location
+        .search()
+        .expect_throw("Unable to get window.location.search");
+    let hash = location
+        .hash()
+        .expect_throw("Unable to get window.location.hash");
+    crate::utils::window()
+        .history()
+        .expect_throw("Unable to get history")
+        .replace_state_with_url(
+            state,
+            "",
+            Some(&format!("{}{}{}", normalized, search, hash)),
+        )
+        .expect_throw("Error on replace_state_with_url");
 }
 
 fn get_new_location(current_url: &Rc<RefCell<Option<String>>>) -> Option<web_sys::Location> {
@@ -136,3 +287,245 @@ fn get_new_location(current_url: &Rc<RefCell<Option<String>>>) -> Option<web_sys
         None
     }
 }
+
+#[cfg(test)]
+mod routing_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+    use crate::test_utils::sleep;
+
+    struct Page(u32);
+
+    impl super::Routes for Page {
+        type Router = AppRouter;
+        fn url(&self) -> String {
+            format!("#history-state-{}", self.0)
+        }
+    }
+
+    struct AppRouter {
+        comp: Comp<App>,
+    }
+
+    impl super::Router for AppRouter {
+        fn routing(&self, _: web_sys::Location) {}
+
+        fn routing_with_state(&self, location: web_sys::Location, state: wasm_bindgen::JsValue) {
+            let hash = location.hash().unwrap_or_else(|_| String::new());
+            self.comp
+                .callback_arg_mut(App::set_location)
+                .call_or_queue((hash, state.as_f64()));
+        }
+    }
+
+    struct App {
+        hash: String,
+        scroll: Option<f64>,
+        routings: u32,
+    }
+
+    impl App {
+        fn set_location(&mut self, (hash, scroll): (String, Option<f64>)) {
+            self.routings += 1;
+            self.hash = hash;
+            self.scroll = scroll;
+        }
+    }
+
+    impl Component for App {
+        type Routes = Page;
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::base::MethodsForEvents;
+            use crate::render::html::{HemsForDistinctNames, MethodsForHtmlElementContent};
+            let comp = element.comp();
+            element.button(|b| {
+                b.on_click(comp.navigate_callback(Page(4)))
+                    .rupdate("Page 4");
+            });
+        }
+    }
+
+    impl crate::application::Application for App {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                hash: String::new(),
+                scroll: None,
+                routings: 0,
+            }
+        }
+
+        fn init_router(comp: &Comp<Self>) -> Option<AppRouter> {
+            Some(AppRouter { comp: comp.clone() })
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn restore_history_state() {
+        let (_root, rc) = crate::test_utils::mount::<App>();
+        let location = || {
+            let state = rc.comp_instance();
+            let state = state.state();
+            (state.hash.clone(), state.scroll)
+        };
+
+        rc.comp()
+            .callback_once_mut(|_: &mut App| super::push_state_with(&Page(1), 10.0.into()))
+            .call();
+        assert_eq!(("#history-state-1".to_string(), Some(10.0)), location());
+
+        rc.comp()
+            .callback_once_mut(|_: &mut App| super::push_state_with(&Page(2), 20.0.into()))
+            .call();
+        assert_eq!(("#history-state-2".to_string(), Some(20.0)), location());
+        assert_eq!(Some(20.0), super::current_history_state().as_f64());
+
+        crate::utils::window()
+            .history()
+            .expect_throw("routing_tests::restore_history_state history")
+            .back()
+            .expect_throw("routing_tests::restore_history_state back");
+        sleep(50).await;
+        assert_eq!(("#history-state-1".to_string(), Some(10.0)), location());
+        assert_eq!(Some(10.0), super::current_history_state().as_f64());
+    }
+
+    #[wasm_bindgen_test]
+    fn navigate_to_current_route() {
+        use super::Routes;
+        let (_root, rc) = crate::test_utils::mount::<App>();
+        let history = crate::utils::window()
+            .history()
+            .expect_throw("routing_tests::navigate_to_current_route history");
+        let history_length = || {
+            history
+                .length()
+                .expect_throw("routing_tests::navigate_to_current_route history length")
+        };
+
+        rc.comp()
+            .callback_once_mut(|_: &mut App| Page(3).execute_routing())
+            .call();
+        let routings = rc.comp_instance().state().routings;
+        let length = history_length();
+        assert_eq!("#history-state-3", rc.comp_instance().state().hash);
+
+        rc.comp()
+            .callback_once_mut(|_: &mut App| Page(3).execute_routing())
+            .call();
+        assert_eq!(routings, rc.comp_instance().state().routings);
+        assert_eq!(length, history_length());
+    }
+
+    #[wasm_bindgen_test]
+    fn navigate_callback() {
+        use wasm_bindgen::JsCast;
+        let (root, rc) = crate::test_utils::mount::<App>();
+        let button: web_sys::HtmlElement = root
+            .ws_element()
+            .as_ref()
+            .first_element_child()
+            .expect_throw("routing_tests::navigate_callback button")
+            .unchecked_into();
+
+        button.click();
+        assert_eq!("#history-state-4", rc.comp_instance().state().hash);
+        assert!(crate::utils::window()
+            .location()
+            .href()
+            .expect_throw("routing_tests::navigate_callback href")
+            .ends_with("#history-state-4"));
+    }
+
+    #[wasm_bindgen_test]
+    fn strip_trailing_slash() {
+        let strip = |url| super::TrailingSlash::Strip.normalize(url);
+        assert_eq!("/about", strip("/about"));
+        assert_eq!("/about", strip("/about/"));
+        assert_eq!("/about", strip("/about//"));
+        assert_eq!("/", strip("/"));
+        assert_eq!("/about?tab=1#top", strip("/about/?tab=1#top"));
+        assert_eq!("/about#top/", strip("/about#top/"));
+        assert_eq!("#section/", strip("#section/"));
+    }
+
+    #[wasm_bindgen_test]
+    fn require_trailing_slash() {
+        let require = |url| super::TrailingSlash::Require.normalize(url);
+        assert_eq!("/about/", require("/about"));
+        assert_eq!("/about/", require("/about/"));
+        assert_eq!("/about/", require("/about//"));
+        assert_eq!("/", require("/"));
+        assert_eq!("/about/?tab=1#top", require("/about?tab=1#top"));
+        assert_eq!("?page=2", require("?page=2"));
+    }
+
+    struct StrictRouter;
+
+    impl super::Router for StrictRouter {
+        const TRAILING_SLASH: super::TrailingSlash = super::TrailingSlash::Require;
+        fn routing(&self, _: web_sys::Location) {}
+    }
+
+    struct Docs(&'static str);
+
+    impl super::Routes for Docs {
+        type Router = StrictRouter;
+        fn url(&self) -> String {
+            format!("/docs/{}", self.0)
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn normalized_url_of_routes() {
+        use super::Routes;
+        assert_eq!("/docs/intro/", Docs("intro").normalized_url());
+        assert_eq!("/docs/intro/", Docs("intro/").normalized_url());
+        assert_eq!("#history-state-1", Page(1).normalized_url());
+    }
+
+    struct DocsLinks;
+
+    impl Component for DocsLinks {
+        type Routes = Docs;
+        fn render(&self, element: crate::Element<Self>) {
+            use crate::render::html::{HamsHandMade, HemsForDistinctNames};
+            let _nodes = element.a(|a| {
+                a.href(&Docs("intro")).done();
+            });
+            #[cfg(feature = "svg")]
+            {
+                use crate::render::html::HemsHandMade;
+                use crate::render::svg::{SamsHandMade, SemsForDistinctNames};
+                _nodes.svg(|s| {
+                    s.a(|a| {
+                        a.href(&Docs("api"));
+                    });
+                });
+            }
+        }
+    }
+
+    impl crate::application::Application for DocsLinks {
+        fn init(_: &Comp<Self>) -> Self {
+            Self
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn href_is_normalized() {
+        let (root, _rc) = crate::test_utils::mount::<DocsLinks>();
+        let href = |selector| {
+            root.ws_element()
+                .as_ref()
+                .query_selector(selector)
+                .expect_throw("routing_tests::href_is_normalized query")
+                .expect_throw("routing_tests::href_is_normalized no element")
+                .get_attribute("href")
+        };
+        assert_eq!(Some("/docs/intro/".to_string()), href("div > a"));
+        #[cfg(feature = "svg")]
+        assert_eq!(Some("/docs/api/".to_string()), href("svg > a"));
+    }
+}
@@ -0,0 +1,95 @@
+use wasm_bindgen::UnwrapThrowExt;
+
+use crate::component::{Comp, Component, RcComp};
+use crate::dom::{Element, ElementStatus};
+use crate::render::base::ElementUpdater;
+use crate::render::html::HtmlTag;
+
+/// Wait for `millis` milliseconds (with `setTimeout`), let timers, futures and
+/// listeners that are scheduled by the code under test run.
+pub(crate) async fn sleep(millis: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        crate::utils::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+            .expect_throw("test_utils::sleep set_timeout");
+    });
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .expect_throw("test_utils::sleep await");
+}
+
+/// Mount the app `A` to a new `<div>` that is not attached to the document. The
+/// `<div>` is returned to inspect the rendered content.
+pub(crate) fn mount<A: crate::Application>() -> (crate::dom::Element, RcComp<A>) {
+    let root = crate::dom::Element::new_ns(HtmlTag("div"));
+    let rc = crate::application::mount_to_element(root.ws_element().clone().into_inner());
+    (root, rc)
+}
+
+/// Same as [`mount`], but the `<div>` is appended to the body of the document, for
+/// tests that require layout or focus. The test should remove the `<div>` at the end.
+pub(crate) fn mount_in_body<A: crate::Application>() -> (crate::dom::Element, RcComp<A>) {
+    let root = crate::dom::Element::new_ns(HtmlTag("div"));
+    crate::utils::document()
+        .body()
+        .expect_throw("test_utils::mount_in_body document body")
+        .append_child(root.ws_element().as_ref())
+        .expect_throw("test_utils::mount_in_body append the root to the body");
+    let rc = crate::application::mount_to_element(root.ws_element().clone().into_inner());
+    (root, rc)
+}
+
+/// A component without state and content, that owns the elements of [`TestElement`].
+pub(crate) struct Unit;
+impl Component for Unit {
+    type Routes = ();
+    fn render(&self, _: crate::Element<Self>) {}
+}
+
+/// An element that a test renders by hand, by calling `updater` once per render.
+pub(crate) struct TestElement {
+    pub element: Element,
+    _rc: RcComp<Unit>,
+    comp: Comp<Unit>,
+}
+
+impl TestElement {
+    /// Create a `<tag>` that is not attached to the document.
+    pub fn new(tag: &'static str) -> Self {
+        let element = Element::new_ns(HtmlTag(tag));
+        let _rc = RcComp::with_ws_root(element.ws_element().clone().into_inner());
+        _rc.set_state(Unit);
+        let comp = _rc.comp();
+        Self { element, _rc, comp }
+    }
+
+    /// Same as [`TestElement::new`], but the element is appended to the body of the
+    /// document, for tests that require layout or focus. It is removed when dropped.
+    pub fn in_body(tag: &'static str) -> Self {
+        let te = Self::new(tag);
+        crate::utils::document()
+            .body()
+            .expect_throw("test_utils::TestElement::in_body document body")
+            .append_child(te.ws_element())
+            .expect_throw("test_utils::TestElement::in_body append the element to the body");
+        te
+    }
+
+    pub fn updater(&mut self, status: ElementStatus) -> crate::Element<'_, Unit> {
+        ElementUpdater::new(&self.comp, &Unit, &mut self.element, status).into()
+    }
+
+    pub fn ws_element(&self) -> &web_sys::Element {
+        self.element.ws_element().as_ref()
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        self.ws_element().get_attribute(name)
+    }
+}
+
+impl Drop for TestElement {
+    fn drop(&mut self) {
+        self.ws_element().remove();
+    }
+}
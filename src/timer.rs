@@ -0,0 +1,62 @@
+use std::time::Duration;
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+
+fn duration_to_millis(duration: Duration) -> i32 {
+    duration.as_millis().min(i32::MAX as u128) as i32
+}
+
+/// A handle to a `setTimeout`. The timeout is cleared when the handle is dropped.
+pub struct Timeout {
+    id: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Timeout {
+    pub fn new(duration: Duration, f: impl FnOnce() + 'static) -> Self {
+        let closure: Closure<dyn FnMut()> = Closure::once(f);
+        let id = crate::utils::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                duration_to_millis(duration),
+            )
+            .expect_throw("timer::Timeout::new set_timeout");
+        Self {
+            id,
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        crate::utils::window().clear_timeout_with_handle(self.id);
+    }
+}
+
+/// A handle to a `setInterval`. The interval is cleared when the handle is dropped.
+pub struct Interval {
+    id: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Interval {
+    pub fn new(duration: Duration, f: impl FnMut() + 'static) -> Self {
+        let closure = Closure::wrap(Box::new(f) as Box<dyn FnMut()>);
+        let id = crate::utils::window()
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                closure.as_ref().unchecked_ref(),
+                duration_to_millis(duration),
+            )
+            .expect_throw("timer::Interval::new set_interval");
+        Self {
+            id,
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        crate::utils::window().clear_interval_with_handle(self.id);
+    }
+}
@@ -1,4 +1,4 @@
-use wasm_bindgen::UnwrapThrowExt;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
 pub fn window() -> web_sys::Window {
     web_sys::window().expect_throw("Unable to get window")
@@ -31,6 +31,100 @@ pub fn prompt(message: &str, default_value: Option<&str>) -> Option<String> {
     }
 }
 
+/// Write the text to the clipboard using `navigator.clipboard.writeText`. If the
+/// async Clipboard API is not available (e.g. not in a secure context), fallback
+/// to `document.execCommand("copy")` on a hidden `<textarea>`.
+pub fn copy_text_to_clipboard(
+    text: &str,
+) -> impl std::future::Future<Output = Result<(), wasm_bindgen::JsValue>> {
+    let clipboard = window().navigator().clipboard();
+    let promise = if clipboard.is_undefined() {
+        copy_text_with_exec_command(text).map(|_| js_sys::Promise::resolve(&JsValue::UNDEFINED))
+    } else {
+        Ok(clipboard.write_text(text))
+    };
+    async move {
+        wasm_bindgen_futures::JsFuture::from(promise?).await?;
+        Ok(())
+    }
+}
+
+fn copy_text_with_exec_command(text: &str) -> Result<(), JsValue> {
+    let document = document();
+    let body = document.body().expect_throw("Unable to get document body");
+    let text_area: web_sys::HtmlTextAreaElement =
+        document.create_element("textarea")?.unchecked_into();
+    text_area.set_value(text);
+    text_area.set_attribute("readonly", "")?;
+    text_area.set_attribute("style", "position: fixed; top: 0; left: 0; opacity: 0;")?;
+    body.append_child(&text_area)?;
+    text_area.select();
+    let copied = document
+        .unchecked_ref::<web_sys::HtmlDocument>()
+        .exec_command("copy");
+    text_area.remove();
+    match copied? {
+        true => Ok(()),
+        false => Err(JsValue::from_str(
+            "Unable to copy the text to the clipboard",
+        )),
+    }
+}
+
+/// A media query created by `window.matchMedia`, see [`media_query`].
+pub struct MediaQuery {
+    list: web_sys::MediaQueryList,
+    listener: Option<wasm_bindgen::closure::Closure<dyn FnMut()>>,
+}
+
+/// Evaluate the media query, e.g. `media_query("(max-width: 600px)")`. Use
+/// `.matches()` to get the current state and `.on_change()` to get notified
+/// when it changes. The change listener is removed when the `MediaQuery` is dropped.
+pub fn media_query(query: &str) -> MediaQuery {
+    let list = window()
+        .match_media(query)
+        .expect_throw("Unable to evaluate the media query")
+        .expect_throw("No MediaQueryList for the media query");
+    MediaQuery {
+        list,
+        listener: None,
+    }
+}
+
+impl MediaQuery {
+    pub fn matches(&self) -> bool {
+        self.list.matches()
+    }
+
+    /// Call the callback with the new value of `.matches()` every time the media
+    /// query changes. Calling this again replaces the previous callback.
+    pub fn on_change(&mut self, callback: crate::CallbackArg<bool>) {
+        self.remove_listener();
+        let list = self.list.clone();
+        let listener = wasm_bindgen::closure::Closure::wrap(Box::new(move || {
+            callback.call_or_queue(list.matches())
+        }) as Box<dyn FnMut()>);
+        self.list
+            .add_event_listener_with_callback("change", listener.as_ref().unchecked_ref())
+            .expect_throw("Unable to register the change listener on the MediaQueryList");
+        self.listener = Some(listener);
+    }
+
+    fn remove_listener(&mut self) {
+        if let Some(listener) = self.listener.take() {
+            self.list
+                .remove_event_listener_with_callback("change", listener.as_ref().unchecked_ref())
+                .expect_throw("Unable to remove the change listener from the MediaQueryList");
+        }
+    }
+}
+
+impl Drop for MediaQuery {
+    fn drop(&mut self) {
+        self.remove_listener();
+    }
+}
+
 pub(crate) fn register_event_listener_on_window(event: &str, listener: &js_sys::Function) {
     let window = crate::utils::window();
     let window: &web_sys::EventTarget = window.as_ref();
@@ -38,3 +132,245 @@ pub(crate) fn register_event_listener_on_window(event: &str, listener: &js_sys::
         .add_event_listener_with_callback(event, listener)
         .expect_throw("Unable to register event listener on window");
 }
+
+pub(crate) fn remove_event_listener_on_window(event: &str, listener: &js_sys::Function) {
+    let window = crate::utils::window();
+    let window: &web_sys::EventTarget = window.as_ref();
+    window
+        .remove_event_listener_with_callback(event, listener)
+        .expect_throw("Unable to remove event listener from window");
+}
+
+#[cfg(test)]
+mod utils_tests {
+    use wasm_bindgen::UnwrapThrowExt;
+    use wasm_bindgen_test::*;
+
+    use crate::component::{Comp, Component};
+
+    struct State {
+        changes: Vec<bool>,
+    }
+
+    impl Component for State {
+        type Routes = ();
+        fn render(&self, _: crate::Element<Self>) {}
+    }
+
+    impl crate::application::Application for State {
+        fn init(_: &Comp<Self>) -> Self {
+            Self {
+                changes: Vec::new(),
+            }
+        }
+    }
+
+    // The real `navigator.clipboard` requires a user activation or a permission that
+    // headless browsers do not give, so it is replaced by a stub that records the text.
+    #[wasm_bindgen_test]
+    async fn copy_text_to_clipboard() {
+        let navigator = super::window().navigator();
+        let stub = js_sys::Function::new_no_args(
+            "const written = []; \
+            return { written, writeText(text) { written.push(text); return Promise.resolve(); } };",
+        )
+        .call0(&wasm_bindgen::JsValue::UNDEFINED)
+        .expect_throw("utils_tests::copy_text_to_clipboard make the stub");
+        let descriptor = js_sys::Object::new();
+        js_sys::Reflect::set(&descriptor, &"value".into(), &stub)
+            .expect_throw("utils_tests::copy_text_to_clipboard descriptor value");
+        js_sys::Reflect::set(&descriptor, &"configurable".into(), &true.into())
+            .expect_throw("utils_tests::copy_text_to_clipboard descriptor configurable");
+        js_sys::Object::define_property(&navigator, &"clipboard".into(), &descriptor);
+
+        let result = super::copy_text_to_clipboard("Spair").await;
+        js_sys::Reflect::delete_property(&navigator, &"clipboard".into())
+            .expect_throw("utils_tests::copy_text_to_clipboard remove the stub");
+
+        assert_eq!(Ok(()), result);
+        let written: js_sys::Array = js_sys::Reflect::get(&stub, &"written".into())
+            .expect_throw("utils_tests::copy_text_to_clipboard written")
+            .into();
+        assert_eq!(
+            vec!["Spair".to_string()],
+            written
+                .iter()
+                .filter_map(|text| text.as_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn media_query() {
+        let (_root, rc) = crate::test_utils::mount::<State>();
+        let changes = || rc.comp_instance().state().changes.clone();
+
+        let mut query = super::media_query("(min-width: 1px)");
+        assert!(query.matches());
+        query.on_change(
+            rc.comp()
+                .callback_arg_mut(|state: &mut State, matches| state.changes.push(matches)),
+        );
+
+        // Simulate a change of the media query
+        let change = web_sys::Event::new("change").expect_throw("Create a change event");
+        query
+            .list
+            .dispatch_event(&change)
+            .expect_throw("Dispatch the change event");
+        assert_eq!(vec![true], changes());
+
+        // The listener is removed when the query is dropped
+        let list = query.list.clone();
+        drop(query);
+        list.dispatch_event(&change)
+            .expect_throw("Dispatch the change event");
+        assert_eq!(vec![true], changes());
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use storage::*;
+
+#[cfg(feature = "serde")]
+mod storage {
+    use wasm_bindgen::JsValue;
+
+    #[derive(thiserror::Error, Debug)]
+    pub enum StorageError {
+        #[error("The storage is not available")]
+        NotAvailable,
+        #[error("Error on accessing the storage: {0:?}")]
+        Js(JsValue),
+        #[error("Error on (de)serializing the value: {0}")]
+        Serde(#[from] serde_json::Error),
+    }
+
+    fn local_storage() -> Result<web_sys::Storage, StorageError> {
+        super::window()
+            .local_storage()
+            .map_err(StorageError::Js)?
+            .ok_or(StorageError::NotAvailable)
+    }
+
+    fn session_storage() -> Result<web_sys::Storage, StorageError> {
+        super::window()
+            .session_storage()
+            .map_err(StorageError::Js)?
+            .ok_or(StorageError::NotAvailable)
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(
+        storage: web_sys::Storage,
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        match storage.get_item(key).map_err(StorageError::Js)? {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn set<T: serde::Serialize + ?Sized>(
+        storage: web_sys::Storage,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let value = serde_json::to_string(value)?;
+        storage.set_item(key, &value).map_err(StorageError::Js)
+    }
+
+    fn remove(storage: web_sys::Storage, key: &str) -> Result<(), StorageError> {
+        storage.remove_item(key).map_err(StorageError::Js)
+    }
+
+    /// Get the value of `key` from `window.localStorage`, deserialized from JSON.
+    /// Returns `Ok(None)` if there is no such key.
+    pub fn local_storage_get<T: serde::de::DeserializeOwned>(
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        get(local_storage()?, key)
+    }
+
+    /// Serialize the value to JSON and store it in `window.localStorage`.
+    pub fn local_storage_set<T: serde::Serialize + ?Sized>(
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        set(local_storage()?, key, value)
+    }
+
+    pub fn local_storage_remove(key: &str) -> Result<(), StorageError> {
+        remove(local_storage()?, key)
+    }
+
+    /// Get the value of `key` from `window.sessionStorage`, deserialized from JSON.
+    /// Returns `Ok(None)` if there is no such key.
+    pub fn session_storage_get<T: serde::de::DeserializeOwned>(
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        get(session_storage()?, key)
+    }
+
+    /// Serialize the value to JSON and store it in `window.sessionStorage`.
+    pub fn session_storage_set<T: serde::Serialize + ?Sized>(
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        set(session_storage()?, key, value)
+    }
+
+    pub fn session_storage_remove(key: &str) -> Result<(), StorageError> {
+        remove(session_storage()?, key)
+    }
+
+    #[cfg(test)]
+    mod storage_tests {
+        use wasm_bindgen_test::*;
+
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Settings {
+            name: String,
+            count: u32,
+            enabled: bool,
+        }
+
+        fn settings() -> Settings {
+            Settings {
+                name: "spair".to_string(),
+                count: 42,
+                enabled: true,
+            }
+        }
+
+        #[wasm_bindgen_test]
+        fn local_storage_round_trip() {
+            let key = "spair-local-storage-round-trip";
+            super::local_storage_set(key, &settings()).unwrap();
+            assert_eq!(Some(settings()), super::local_storage_get(key).unwrap());
+
+            super::local_storage_remove(key).unwrap();
+            assert_eq!(None, super::local_storage_get::<Settings>(key).unwrap());
+        }
+
+        #[wasm_bindgen_test]
+        fn session_storage_round_trip() {
+            let key = "spair-session-storage-round-trip";
+            super::session_storage_set(key, &settings()).unwrap();
+            assert_eq!(Some(settings()), super::session_storage_get(key).unwrap());
+
+            super::session_storage_remove(key).unwrap();
+            assert_eq!(None, super::session_storage_get::<Settings>(key).unwrap());
+        }
+
+        #[wasm_bindgen_test]
+        fn invalid_json() {
+            let key = "spair-local-storage-invalid-json";
+            super::local_storage().unwrap().set_item(key, "{").unwrap();
+            assert!(matches!(
+                super::local_storage_get::<Settings>(key),
+                Err(super::StorageError::Serde(_))
+            ));
+            super::local_storage_remove(key).unwrap();
+        }
+    }
+}
@@ -10,6 +10,10 @@ cargo clippy --all -- -D warnings
 # wasm-pack test --headless --chrome -- --all-features
 wasm-pack test --headless --firefox -- --all-features
 
+# The keyed list must also build without the `uuid` dependency
+cargo build $TARGET --features=keyed-list
+cargo build $TARGET --features=keyed-list-uuid
+
 for x in ./examples/*; do
     if [ -f $x/Cargo.toml ]; then
         cargo build $TARGET --manifest-path=$x/Cargo.toml